@@ -0,0 +1,157 @@
+use futures::{ future, stream, Stream, StreamExt };
+use serde::{ Deserialize, Serialize };
+use std::collections::VecDeque;
+
+use crate::{ Quote, Result };
+
+/// Something to watch a symbol's quotes for
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Condition {
+   /// The price goes from below the level to at or above it
+   CrossesAbove(f64),
+
+   /// The price goes from above the level to at or below it
+   CrossesBelow(f64),
+
+   /// The change from the previous close reaches a percentage either way -
+   /// ie. 5.0 for a move of 5% up or down.  Alerts again once the move has
+   /// dropped back under it.
+   PercentMove(f64),
+
+   /// The volume traded since the quote before is at least `multiple` times
+   /// the average over the last `window` quotes
+   VolumeSpike { multiple: f64, window: usize }
+}
+
+/// A condition that was met by a quote
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Alert {
+   pub symbol: String,
+   pub condition: Condition,
+
+   /// The quote that met the condition
+   pub quote: Quote
+}
+
+/// A condition registered for a symbol, along with what it needs to
+/// remember from the quotes before
+#[derive(Clone, Debug)]
+struct Rule {
+   symbol: String,
+   condition: Condition,
+
+   price: Option<f64>,
+   triggered: bool,
+
+   /// The day's volume as of the last quote & the volumes traded between the quotes before it
+   volume: Option<u64>,
+   volumes: VecDeque<u64>
+}
+impl Rule {
+   /// Checks the quote against the condition, remembering what's needed for the next one
+   fn check(&mut self, quote: &Quote) -> bool {
+      match self.condition {
+         Condition::CrossesAbove(level) => {
+            let crossed = self.price.is_some_and(|price| price < level) && quote.price >= level;
+            self.price = Some(quote.price);
+            crossed
+         },
+         Condition::CrossesBelow(level) => {
+            let crossed = self.price.is_some_and(|price| price > level) && quote.price <= level;
+            self.price = Some(quote.price);
+            crossed
+         },
+         Condition::PercentMove(percent) => {
+            let moved = quote.change_percent.is_some_and(|change| change.abs() >= percent);
+            let alert = moved && !self.triggered;
+            self.triggered = moved;
+            alert
+         },
+         Condition::VolumeSpike { multiple, window } => {
+            let traded = match self.volume {
+               // the day's volume going down means a new day
+               Some(before) if before <= quote.volume => Some(quote.volume - before),
+               Some(_) => { self.volumes.clear(); None },
+               None => None
+            };
+            self.volume = Some(quote.volume);
+
+            let traded = match traded {
+               Some(traded) => traded,
+               None => return false
+            };
+            let window = window.max(1);
+            let spike = self.volumes.len() == window && {
+               let average = self.volumes.iter().sum::<u64>() as f64 / window as f64;
+               average > 0.0 && traded as f64 >= average * multiple
+            };
+
+            self.volumes.push_back(traded);
+            if self.volumes.len() > window { self.volumes.pop_front(); }
+            spike
+         }
+      }
+   }
+}
+
+/// Watches streamed quotes for price & volume conditions.
+///
+/// The crossings need a quote on either side of the level, so the very first
+/// quote for a symbol never crosses anything.  Volume spikes are measured
+/// between quotes, since Yahoo! streams the day's volume, & aren't looked for
+/// until there's a full window of them.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use yahoo_finance::Streamer;
+/// use yahoo_finance::alerts::{ Alerts, Condition };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(vec!["AAPL", "TSLA"]);
+///    let alerts = Alerts::new()
+///       .add("AAPL", Condition::CrossesAbove(200.0))
+///       .add("TSLA", Condition::PercentMove(5.0))
+///       .add("TSLA", Condition::VolumeSpike { multiple: 3.0, window: 20 });
+///
+///    alerts.watch(streamer.stream().await)
+///       .for_each(|alert| future::ready(println!("{} {:?} at {}", alert.symbol, alert.condition, alert.quote.price)))
+///       .await;
+/// }
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct Alerts {
+   rules: Vec<Rule>
+}
+impl Alerts {
+   pub fn new() -> Alerts { Alerts::default() }
+
+   /// Registers a condition to watch a symbol for
+   pub fn add(mut self, symbol: &str, condition: Condition) -> Alerts {
+      self.rules.push(Rule { symbol: symbol.to_string(), condition, price: None, triggered: false, volume: None, volumes: VecDeque::new() });
+      self
+   }
+
+   /// Checks a quote against the conditions for its symbol, handing back an
+   /// alert for each one it met
+   pub fn push(&mut self, quote: &Quote) -> Vec<Alert> {
+      self.rules.iter_mut()
+         .filter(|rule| rule.symbol == quote.symbol)
+         .filter_map(|rule| match rule.check(quote) {
+            true => Some(Alert { symbol: quote.symbol.clone(), condition: rule.condition.clone(), quote: quote.clone() }),
+            false => None
+         })
+         .collect()
+   }
+
+   /// Turns a stream of quotes into a stream of alerts.  Quotes that
+   /// couldn't be read are skipped.
+   pub fn watch<S>(mut self, quotes: S) -> impl Stream<Item = Alert>
+      where S: Stream<Item = Result<Quote>> {
+      quotes
+         .filter_map(|quote| future::ready(quote.ok()))
+         .flat_map(move |quote| stream::iter(self.push(&quote)))
+   }
+}
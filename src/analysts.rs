@@ -0,0 +1,251 @@
+use chrono::{ NaiveDate, TimeZone, Utc };
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+use std::collections::HashMap;
+
+use crate::{ error, many, yahoo, Result };
+
+/// How many analysts rated a symbol each way in a month.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Ratings {
+   /// How many months before the current one the ratings are for - 0 is the current month
+   pub months_ago: u32,
+
+   pub strong_buy: u32,
+   pub buy: u32,
+   pub hold: u32,
+   pub sell: u32,
+   pub strong_sell: u32
+}
+impl Ratings {
+   /// The number of analysts with a rating
+   pub fn total(&self) -> u32 { self.strong_buy + self.buy + self.hold + self.sell + self.strong_sell }
+}
+
+pub(crate) fn ratings(data: yahoo::QuoteSummary) -> Result<Vec<Ratings>> {
+   let trend = data.recommendation_trend.context(error::MissingData { reason: "no recommendation trend" })?.trend;
+
+   let mut ratings = Vec::new();
+   for month in trend {
+      // Yahoo names the months relative to the current one.  ie. '-1m'
+      let months_ago = month.period.trim_start_matches('-').trim_end_matches('m').parse()
+         .ok()
+         .context(error::MissingData { reason: format!("'{}' is not a month", month.period) })?;
+
+      ratings.push(Ratings { months_ago, strong_buy: month.strong_buy, buy: month.buy, hold: month.hold, sell: month.sell, strong_sell: month.strong_sell });
+   }
+   ratings.sort_by_key(|ratings| std::cmp::Reverse(ratings.months_ago));
+   Ok(ratings)
+}
+
+/// Retrieves the analysts' ratings of a symbol for the current & last few
+/// months, from oldest to newest.  Yahoo usually has the last four months.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::analysts;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for month in analysts::recommendation_trend("AAPL").await.unwrap() {
+///       println!("{} months ago - {} of {} analysts rated Apple a buy", month.months_ago,
+///          month.strong_buy + month.buy, month.total());
+///    }
+/// }
+/// ```
+pub async fn recommendation_trend(symbol: &str) -> Result<Vec<Ratings>> {
+   ratings(yahoo::load_quote_summary(symbol, &["recommendationTrend"]).await?)
+}
+
+/// Blocking version of `recommendation_trend` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_recommendation_trend(symbol: &str) -> Result<Vec<Ratings>> { crate::blocking::run(recommendation_trend(symbol)) }
+
+/// What a firm did with its rating of a symbol
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Action {
+   Upgrade,
+   Downgrade,
+
+   /// The firm started covering the symbol
+   Initiate,
+
+   /// The firm kept its rating, usually with a new price target
+   Maintain,
+
+   /// The firm restated its rating
+   Reiterate,
+
+   /// An action Yahoo has that we don't know about
+   Other(String)
+}
+impl From<&str> for Action {
+   fn from(value: &str) -> Action {
+      match value {
+         "up" => Action::Upgrade,
+         "down" => Action::Downgrade,
+         "init" => Action::Initiate,
+         "main" => Action::Maintain,
+         "reit" => Action::Reiterate,
+         other => Action::Other(other.to_string())
+      }
+   }
+}
+
+/// A change a firm made to its rating of a symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RatingChange {
+   pub date: NaiveDate,
+
+   /// The firm the analyst works for.  ie. 'Morgan Stanley'
+   pub firm: String,
+   pub action: Action,
+
+   /// The rating before the change, if the firm had one.  ie. 'Equal-Weight'
+   pub from_grade: Option<String>,
+
+   /// The rating after the change.  ie. 'Overweight'
+   pub to_grade: Option<String>
+}
+
+pub(crate) fn rating_history(data: yahoo::QuoteSummary) -> Result<Vec<RatingChange>> {
+   let history = data.upgrade_downgrade_history.context(error::MissingData { reason: "no upgrade / downgrade history" })?.history;
+
+   // Yahoo sends empty grades rather than leaving them out
+   let grade = |grade: Option<String>| grade.filter(|grade| !grade.is_empty());
+
+   let mut changes = Vec::new();
+   for change in history {
+      let date = Utc.timestamp_opt(change.epoch_grade_date, 0).single()
+         .context(error::MissingData { reason: format!("{} is not a date", change.epoch_grade_date) })?
+         .date_naive();
+
+      changes.push(RatingChange {
+         date,
+         firm: change.firm,
+         action: change.action.as_deref().map(Action::from).unwrap_or_else(|| Action::Other(String::new())),
+         from_grade: grade(change.from_grade),
+         to_grade: grade(change.to_grade)
+      });
+   }
+   changes.sort_by_key(|change| change.date);
+   Ok(changes)
+}
+
+/// Retrieves the rating changes analysts' firms have made for a symbol -
+/// upgrades, downgrades, new coverage & so on - from oldest to newest.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::analysts::{ self, Action };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for change in analysts::rating_changes("AAPL").await.unwrap() {
+///       if change.action == Action::Downgrade {
+///          println!("{} - {} downgraded Apple to {}", change.date, change.firm, change.to_grade.unwrap_or_default());
+///       }
+///    }
+/// }
+/// ```
+pub async fn rating_changes(symbol: &str) -> Result<Vec<RatingChange>> {
+   rating_history(yahoo::load_quote_summary(symbol, &["upgradeDowngradeHistory"]).await?)
+}
+
+/// Blocking version of `rating_changes` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_rating_changes(symbol: &str) -> Result<Vec<RatingChange>> { crate::blocking::run(rating_changes(symbol)) }
+
+/// The consensus of the analysts' price targets for a symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PriceTargets {
+   /// The price the targets were given against
+   pub current_price: Option<f64>,
+
+   pub high: Option<f64>,
+   pub low: Option<f64>,
+   pub mean: Option<f64>,
+   pub median: Option<f64>,
+
+   /// The number of analysts with a target
+   pub analysts: Option<u32>
+}
+impl PriceTargets {
+   pub(crate) fn new(data: yahoo::QuoteSummary) -> Result<PriceTargets> {
+      let data = data.financial_data.context(error::MissingData { reason: "no financial data" })?;
+      Ok(PriceTargets::from_financial_data(&data))
+   }
+
+   pub(crate) fn from_financial_data(data: &yahoo::FinancialData) -> PriceTargets {
+      PriceTargets {
+         current_price: data.current_price,
+         high: data.target_high_price,
+         low: data.target_low_price,
+         mean: data.target_mean_price,
+         median: data.target_median_price,
+         analysts: data.number_of_analyst_opinions
+      }
+   }
+
+   /// How far the mean target is above (or below) the current price, as a
+   /// fraction of the price - ie. 0.1 is 10% upside
+   pub fn upside(&self) -> Option<f64> {
+      match (self.mean, self.current_price) {
+         (Some(mean), Some(price)) if price > 0.0 => Some(mean / price - 1.0),
+         _ => None
+      }
+   }
+}
+
+/// Retrieves the analysts' price targets for a symbol.  The targets are all
+/// `None` for a symbol no analysts cover.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::analysts;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let targets = analysts::price_targets("AAPL").await.unwrap();
+///    println!("{} analysts target {:.2} on average", targets.analysts.unwrap_or_default(), targets.mean.unwrap_or_default());
+/// }
+/// ```
+pub async fn price_targets(symbol: &str) -> Result<PriceTargets> {
+   PriceTargets::new(yahoo::load_quote_summary(symbol, &["financialData"]).await?)
+}
+
+/// Blocking version of `price_targets` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_price_targets(symbol: &str) -> Result<PriceTargets> { crate::blocking::run(price_targets(symbol)) }
+
+/// Retrieves the analysts' price targets for many symbols at once, as
+/// described in [Many symbols at once](crate#many-symbols-at-once).
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::analysts;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for (symbol, targets) in analysts::price_targets_many(&["AAPL", "MSFT", "GOOG"]).await {
+///       match targets.map(|targets| targets.upside()) {
+///          Ok(Some(upside)) => println!("{} has {:.1}% upside", symbol, upside * 100.0),
+///          Ok(None) => println!("{} has no price targets", symbol),
+///          Err(e) => println!("Failed to load {}: {}", symbol, e)
+///       }
+///    }
+/// }
+/// ```
+pub async fn price_targets_many(symbols: &[&str]) -> HashMap<String, Result<PriceTargets>> {
+   many::load_many(symbols, price_targets).await
+}
+
+/// Blocking version of `price_targets_many` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_price_targets_many(symbols: &[&str]) -> Result<HashMap<String, Result<PriceTargets>>> {
+   crate::blocking::run(async { Ok(price_targets_many(symbols).await) })
+}
@@ -0,0 +1,20 @@
+use snafu::{ ensure, ResultExt };
+use std::future::Future;
+
+use crate::{ error, Result };
+
+/// Runs a retrieval to completion on a runtime of its own, for callers that
+/// don't use tokio.
+///
+/// This fails when called from within a tokio runtime - async code should
+/// use the async functions instead.
+pub(crate) fn run<T>(retrieval: impl Future<Output = Result<T>>) -> Result<T> {
+   ensure!(tokio::runtime::Handle::try_current().is_err(), error::BlockingInAsync);
+
+   let mut runtime = tokio::runtime::Builder::new()
+      .basic_scheduler()
+      .enable_all()
+      .build()
+      .context(error::RuntimeFailed)?;
+   runtime.block_on(retrieval)
+}
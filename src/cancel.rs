@@ -0,0 +1,78 @@
+use futures::future;
+use std::future::Future;
+use std::sync::Arc;
+use tokio::sync::watch;
+
+use crate::{ error, Result };
+
+/// A token used to cancel retrievals that are in progress.
+///
+/// None of the retrievals in this crate spawn background work, so dropping
+/// a retrieval future cancels it immediately - including any retries or
+/// follow up calls it would have made.  The token makes that easy to do from
+/// somewhere other than where the future is being awaited - ie. when a user
+/// cancels a request to your server.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, CancellationToken };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let token = CancellationToken::new();
+///
+///    let cancel = token.clone();
+///    tokio::spawn(async move { cancel.cancel() });
+///
+///    match token.run(history::retrieve("AAPL")).await {
+///       Err(e) => println!("Didn't finish: {}", e),
+///       Ok(bars) => println!("Loaded {} bars", bars.len())
+///    }
+/// }
+/// ```
+#[derive(Clone)]
+pub struct CancellationToken {
+   sender: Arc<watch::Sender<bool>>,
+   receiver: watch::Receiver<bool>
+}
+impl CancellationToken {
+   pub fn new() -> CancellationToken {
+      let (sender, receiver) = watch::channel(false);
+      CancellationToken { sender: Arc::new(sender), receiver }
+   }
+
+   /// Cancels everything being run with this token (or any of its clones)
+   pub fn cancel(&self) {
+      // there is always a receiver since we hold one
+      let _ = self.sender.broadcast(true);
+   }
+
+   pub fn is_cancelled(&self) -> bool { *self.receiver.borrow() }
+
+   /// Waits until the token is cancelled
+   pub async fn cancelled(&self) {
+      let mut receiver = self.receiver.clone();
+      while let Some(cancelled) = receiver.recv().await {
+         if cancelled { return; }
+      }
+
+      // we hold the sender, so we can't get here - but never finish if we do
+      future::pending::<()>().await
+   }
+
+   /// Runs a retrieval until it completes or the token is cancelled, whichever
+   /// comes first.  A cancelled retrieval is dropped and fails with a
+   /// `Cancelled` error.
+   pub async fn run<T>(&self, retrieval: impl Future<Output = Result<T>>) -> Result<T> {
+      if self.is_cancelled() { return error::Cancelled.fail().map_err(Into::into); }
+
+      tokio::select! {
+         _ = self.cancelled() => error::Cancelled.fail().map_err(Into::into),
+         result = retrieval => result
+      }
+   }
+}
+impl Default for CancellationToken {
+   fn default() -> Self { Self::new() }
+}
@@ -0,0 +1,121 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ history, yahoo, Bar, Interval, Result, Streamer };
+
+/// Month codes used in futures contract symbols - January is 'F'
+const MONTH_CODES: [char; 12] = [ 'F', 'G', 'H', 'J', 'K', 'M', 'N', 'Q', 'U', 'V', 'X', 'Z' ];
+
+/// Commodities with actively traded futures on Yahoo.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Commodity {
+   /// West Texas Intermediate crude oil
+   WTI,
+   /// Brent crude oil
+   Brent,
+   NatGas,
+   Gasoline,
+   HeatingOil,
+   Gold,
+   Silver,
+   Copper,
+   Platinum,
+   Palladium,
+   Corn,
+   Wheat,
+   Soybeans,
+   Coffee,
+   Sugar,
+   Cocoa,
+   Cotton,
+   LiveCattle,
+   LeanHogs
+}
+impl Commodity {
+   /// The futures root & the Yahoo suffix for the exchange it trades on
+   fn root(&self) -> (&'static str, &'static str) {
+      match self {
+         Commodity::WTI => ("CL", "NYM"),
+         Commodity::Brent => ("BZ", "NYM"),
+         Commodity::NatGas => ("NG", "NYM"),
+         Commodity::Gasoline => ("RB", "NYM"),
+         Commodity::HeatingOil => ("HO", "NYM"),
+         Commodity::Gold => ("GC", "CMX"),
+         Commodity::Silver => ("SI", "CMX"),
+         Commodity::Copper => ("HG", "CMX"),
+         Commodity::Platinum => ("PL", "NYM"),
+         Commodity::Palladium => ("PA", "NYM"),
+         Commodity::Corn => ("ZC", "CBT"),
+         Commodity::Wheat => ("ZW", "CBT"),
+         Commodity::Soybeans => ("ZS", "CBT"),
+         Commodity::Coffee => ("KC", "NYB"),
+         Commodity::Sugar => ("SB", "NYB"),
+         Commodity::Cocoa => ("CC", "NYB"),
+         Commodity::Cotton => ("CT", "NYB"),
+         Commodity::LiveCattle => ("LE", "CME"),
+         Commodity::LeanHogs => ("HE", "CME")
+      }
+   }
+
+   /// The Yahoo symbol for the continuous front month contract.  ie. 'CL=F'
+   ///
+   /// Yahoo rolls this symbol to the next contract on its own, so it is the
+   /// one to use for quotes, history & streaming.
+   pub fn symbol(&self) -> String { format!("{}=F", self.root().0) }
+
+   /// The Yahoo symbol for a specific contract month.  ie. 'CLZ20.NYM' for
+   /// the December 2020 WTI contract
+   pub fn contract(&self, month: u32, year: i32) -> Option<String> {
+      let (root, exchange) = self.root();
+      let code = MONTH_CODES.get((month as usize).checked_sub(1)?)?;
+      Some(format!("{}{}{:02}.{}", root, code, year.rem_euclid(100), exchange))
+   }
+}
+
+/// The current price for a commodity's front month contract.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommodityQuote {
+   pub commodity: Commodity,
+
+   /// The symbol the quote was loaded for.  ie. 'GC=F'
+   pub symbol: String,
+
+   pub price: f64,
+   pub previous_close: f64
+}
+
+/// Retrieves the current front month price for a commodity.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::commodities::{ self, Commodity };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let gold = commodities::quote(Commodity::Gold).await.unwrap();
+///    println!("Gold is trading at ${:.2}", gold.price);
+/// }
+/// ```
+pub async fn quote(commodity: Commodity) -> Result<CommodityQuote> {
+   let symbol = commodity.symbol();
+   let meta = yahoo::load_daily(&symbol, Interval::_5d).await?.meta;
+   Ok(CommodityQuote { commodity, symbol, price: meta.current_price as f64, previous_close: meta.previous_close as f64 })
+}
+
+/// Blocking version of `quote` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_quote(commodity: Commodity) -> Result<CommodityQuote> { crate::blocking::run(quote(commodity)) }
+
+/// Retrieves the front month history for a commodity.
+pub async fn history(commodity: Commodity, interval: Interval) -> Result<Vec<Bar>> {
+   history::retrieve_interval(&commodity.symbol(), interval).await
+}
+
+/// Blocking version of `history` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_history(commodity: Commodity, interval: Interval) -> Result<Vec<Bar>> { crate::blocking::run(history(commodity, interval)) }
+
+/// Creates a streamer for the front month contracts of the commodities.
+pub fn streamer(commodities: &[Commodity]) -> Streamer {
+   Streamer::new(commodities.iter().map(Commodity::symbol))
+}
@@ -0,0 +1,64 @@
+use serde::{Deserialize, Serialize};
+
+use crate::yahoo;
+
+/// Converts amounts between currencies.
+///
+/// Implement this to plug your own exchange rates into the crate.  Any
+/// closure taking the `from` and `to` currency codes and returning the
+/// exchange rate works too.
+pub trait CurrencyConverter {
+   /// The rate to multiply an amount in the `from` currency by to get an
+   /// amount in the `to` currency.  ie. `rate("TWD", "USD")`
+   fn rate(&self, from: &str, to: &str) -> Option<f64>;
+}
+impl<F: Fn(&str, &str) -> Option<f64>> CurrencyConverter for F {
+   fn rate(&self, from: &str, to: &str) -> Option<f64> { self(from, to) }
+}
+
+/// The currencies associated with a symbol.
+///
+/// Yahoo reports financial statements in the currency the company files in,
+/// which is not necessarily the currency the symbol trades in.  ie. TSM trades
+/// in USD but reports in TWD.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Currencies {
+   /// The currency the symbol trades in.  ie. 'USD'
+   pub trading: Option<String>,
+
+   /// The currency financial statements are reported in.  ie. 'TWD'
+   pub financial: Option<String>
+}
+impl Currencies {
+   /// The currencies out of whichever quote summary modules were loaded -
+   /// `price` or `summaryDetail` for the trading currency & `financialData`
+   /// or `earnings` for the financial one
+   pub(crate) fn from_summary(data: &yahoo::QuoteSummary) -> Currencies {
+      Currencies {
+         trading: data.price.as_ref().and_then(|price| price.currency.clone())
+            .or_else(|| data.summary_detail.as_ref().and_then(|detail| detail.currency.clone())),
+         financial: data.financial_data.as_ref().and_then(|financials| financials.financial_currency.clone())
+            .or_else(|| data.earnings.as_ref().and_then(|earnings| earnings.financial_currency.clone()))
+      }
+   }
+
+   /// Whether the financial statements are reported in a different currency
+   /// than the symbol trades in.
+   pub fn is_mixed(&self) -> bool {
+      match (&self.trading, &self.financial) {
+         (Some(trading), Some(financial)) => trading != financial,
+         _ => false
+      }
+   }
+
+   /// Converts an amount in the financial currency into the trading currency.
+   ///
+   /// Returns `None` when either currency is unknown or the converter has no rate.
+   pub fn to_trading(&self, amount: f64, converter: &impl CurrencyConverter) -> Option<f64> {
+      let trading = self.trading.as_ref()?;
+      let financial = self.financial.as_ref()?;
+
+      if trading == financial { return Some(amount); }
+      converter.rate(financial, trading).map(|rate| amount * rate)
+   }
+}
@@ -0,0 +1,298 @@
+use chrono::{ NaiveDate, TimeZone, Utc };
+use serde::{Deserialize, Serialize};
+use snafu::{ ensure, OptionExt };
+
+use crate::{ error, yahoo, Result };
+
+/// The quote summary modules the earnings are made of
+const MODULES: &[&str] = &["earnings", "earningsTrend"];
+
+/// The quote summary modules with the upcoming earnings dates
+const DATE_MODULES: &[&str] = &["calendarEvents", "earnings"];
+
+fn date(timestamp: i64) -> Option<NaiveDate> {
+   Utc.timestamp_opt(timestamp, 0).single().map(|date| date.date_naive())
+}
+
+/// When a company's next earnings are expected to be announced.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsDate {
+   /// The first day the announcement could be on
+   pub earliest: NaiveDate,
+
+   /// The last day the announcement could be on - the same as the earliest
+   /// once the company has set the date
+   pub latest: NaiveDate,
+
+   /// Whether Yahoo is estimating the date rather than the company having set it
+   pub estimated: bool
+}
+impl EarningsDate {
+   /// The date from Yahoo's one or two timestamps - a window while it's an estimate
+   fn new(timestamps: Vec<yahoo::Raw<i64>>, estimated: Option<bool>) -> Option<EarningsDate> {
+      let dates: Vec<NaiveDate> = timestamps.into_iter().filter_map(|timestamp| timestamp.raw).filter_map(date).collect();
+      Some(EarningsDate {
+         earliest: *dates.iter().min()?,
+         latest: *dates.iter().max()?,
+         estimated: estimated.unwrap_or(dates.len() > 1)
+      })
+   }
+
+   pub(crate) fn from_summary(data: yahoo::QuoteSummary) -> Option<EarningsDate> {
+      let calendar = data.calendar_events.and_then(|events| events.earnings)
+         .and_then(|earnings| EarningsDate::new(earnings.earnings_date, earnings.is_earnings_date_estimate));
+
+      let earnings = data.earnings;
+      calendar.or_else(|| {
+         let chart = earnings?.earnings_chart?;
+         EarningsDate::new(chart.earnings_date, chart.is_earnings_date_estimate)
+      })
+   }
+}
+
+/// Retrieves when a company's next earnings are expected to be announced,
+/// so positions can be closed out ahead of them.  `None` when Yahoo doesn't
+/// know, ie. for funds.
+///
+/// Yahoo keeps showing the last announcement for a little while after it
+/// happens, so the date can be in the past.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::earnings;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    if let Some(next) = earnings::next_date("AAPL").await.unwrap() {
+///       println!("Apple announces between {} & {}", next.earliest, next.latest);
+///    }
+/// }
+/// ```
+pub async fn next_date(symbol: &str) -> Result<Option<EarningsDate>> {
+   Ok(EarningsDate::from_summary(yahoo::load_quote_summary(symbol, DATE_MODULES).await?))
+}
+
+/// Blocking version of `next_date` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_next_date(symbol: &str) -> Result<Option<EarningsDate>> { crate::blocking::run(next_date(symbol)) }
+
+/// The earnings per share reported for a quarter, along with what analysts
+/// expected.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EarningsResult {
+   /// The fiscal quarter.  ie. '4Q2023'
+   pub quarter: String,
+   pub actual: Option<f64>,
+   pub estimate: Option<f64>
+}
+impl EarningsResult {
+   /// How far the actual earnings beat (or missed) the estimate, as a
+   /// fraction of the estimate - ie. 0.05 is a 5% beat
+   pub fn surprise(&self) -> Option<f64> {
+      match (self.actual, self.estimate) {
+         (Some(actual), Some(estimate)) if estimate != 0.0 => Some((actual - estimate) / estimate.abs()),
+         _ => None
+      }
+   }
+}
+
+/// How a quarter's reported earnings per share compared to the analysts'
+/// consensus estimate.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Surprise {
+   /// The last day of the fiscal quarter
+   pub quarter: NaiveDate,
+
+   /// The quarter relative to now.  ie. '-1q' for the last one reported
+   pub period: Option<String>,
+   pub estimate: Option<f64>,
+   pub actual: Option<f64>,
+
+   /// The actual less the estimate
+   pub difference: Option<f64>,
+
+   /// The difference as a fraction of the estimate - ie. 0.05 is a 5% beat
+   pub surprise: Option<f64>
+}
+
+pub(crate) fn surprise_history(data: yahoo::QuoteSummary) -> Result<Vec<Surprise>> {
+   let history = data.earnings_history.context(error::MissingData { reason: "no earnings history" })?.history;
+
+   let mut surprises: Vec<Surprise> = history.into_iter()
+      .filter_map(|quarter| Some(Surprise {
+         quarter: date(quarter.quarter?)?,
+         period: quarter.period,
+         estimate: quarter.eps_estimate,
+         actual: quarter.eps_actual,
+         difference: quarter.eps_difference,
+         surprise: quarter.surprise_percent
+      }))
+      .collect();
+   surprises.sort_by_key(|surprise| surprise.quarter);
+   Ok(surprises)
+}
+
+/// Retrieves how a company's earnings per share compared to the analysts'
+/// estimates for the last few quarters, from oldest to newest.  Yahoo
+/// usually has the last four.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::earnings;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for surprise in earnings::surprises("AAPL").await.unwrap() {
+///       println!("{} - {:+.1}% against the estimate", surprise.quarter, surprise.surprise.unwrap_or_default() * 100.0);
+///    }
+/// }
+/// ```
+pub async fn surprises(symbol: &str) -> Result<Vec<Surprise>> {
+   surprise_history(yahoo::load_quote_summary(symbol, &["earningsHistory"]).await?)
+}
+
+/// Blocking version of `surprises` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_surprises(symbol: &str) -> Result<Vec<Surprise>> { crate::blocking::run(surprises(symbol)) }
+
+/// The revenue & earnings for a fiscal year or quarter.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct FinancialPeriod {
+   /// The fiscal year or quarter.  ie. '2023' or '4Q2023'
+   pub period: String,
+   pub revenue: Option<f64>,
+   pub earnings: Option<f64>
+}
+
+/// What analysts expect for an upcoming (or current) period.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Estimate {
+   /// The period relative to now - ie. '0q' for the current quarter, '+1q'
+   /// for the next one, '0y' & '+1y' for the fiscal years & '+5y' for the
+   /// next five years
+   pub period: String,
+
+   /// The last day of the period.  Yahoo has none for the 5 year periods.
+   pub end_date: Option<NaiveDate>,
+
+   /// The expected growth in earnings over the same period a year ago
+   pub growth: Option<f64>,
+
+   pub eps_average: Option<f64>,
+   pub eps_low: Option<f64>,
+   pub eps_high: Option<f64>,
+   pub eps_year_ago: Option<f64>,
+   pub eps_analysts: Option<u32>,
+
+   pub revenue_average: Option<f64>,
+   pub revenue_low: Option<f64>,
+   pub revenue_high: Option<f64>,
+   pub revenue_year_ago: Option<f64>,
+   pub revenue_analysts: Option<u32>,
+
+   /// The expected growth in revenue over the same period a year ago
+   pub revenue_growth: Option<f64>
+}
+impl Estimate {
+   fn new(trend: yahoo::Trend) -> Estimate {
+      let eps = trend.earnings_estimate.as_ref();
+      let revenue = trend.revenue_estimate.as_ref();
+
+      Estimate {
+         end_date: trend.end_date.and_then(|date| NaiveDate::parse_from_str(&date, "%Y-%m-%d").ok()),
+         growth: trend.growth,
+         eps_average: eps.and_then(|eps| eps.avg),
+         eps_low: eps.and_then(|eps| eps.low),
+         eps_high: eps.and_then(|eps| eps.high),
+         eps_year_ago: eps.and_then(|eps| eps.year_ago_eps),
+         eps_analysts: eps.and_then(|eps| eps.number_of_analysts),
+         revenue_average: revenue.and_then(|revenue| revenue.avg),
+         revenue_low: revenue.and_then(|revenue| revenue.low),
+         revenue_high: revenue.and_then(|revenue| revenue.high),
+         revenue_year_ago: revenue.and_then(|revenue| revenue.year_ago_revenue),
+         revenue_analysts: revenue.and_then(|revenue| revenue.number_of_analysts),
+         revenue_growth: revenue.and_then(|revenue| revenue.growth),
+         period: trend.period
+      }
+   }
+}
+
+/// A company's earnings - how it did against analyst estimates over the
+/// last few quarters, its revenue & earnings over time and what analysts
+/// expect next.
+///
+/// Everything is oldest to newest, except the estimates which are in the
+/// order Yahoo gives them.  Amounts are in the `currency`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Earnings {
+   /// The currency the revenue & earnings are reported in.  ie. 'USD'
+   pub currency: Option<String>,
+
+   /// The day the next earnings are expected to be announced
+   pub next_earnings_date: Option<NaiveDate>,
+
+   /// The actual vs estimated EPS for the last few quarters
+   pub history: Vec<EarningsResult>,
+
+   /// The revenue & earnings for the last few fiscal years
+   pub yearly: Vec<FinancialPeriod>,
+
+   /// The revenue & earnings for the last few fiscal quarters
+   pub quarterly: Vec<FinancialPeriod>,
+
+   /// The analyst estimates for the upcoming periods
+   pub estimates: Vec<Estimate>
+}
+impl Earnings {
+   /// Loads the earnings for a symbol.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::Earnings;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let earnings = Earnings::load("AAPL").await.unwrap();
+   ///    for result in &earnings.history {
+   ///       println!("{} - {:.2} against an estimate of {:.2}", result.quarter,
+   ///          result.actual.unwrap_or_default(), result.estimate.unwrap_or_default());
+   ///    }
+   /// }
+   /// ```
+   pub async fn load(symbol: &str) -> Result<Earnings> {
+      Self::new(yahoo::load_quote_summary(symbol, MODULES).await?)
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Earnings> { crate::blocking::run(Self::load(symbol)) }
+
+   pub(crate) fn new(data: yahoo::QuoteSummary) -> Result<Earnings> {
+      ensure!(data.earnings.is_some() || data.earnings_trend.is_some(), error::MissingData { reason: "no earnings" });
+
+      let (currency, chart, financials) = match data.earnings {
+         Some(earnings) => (earnings.financial_currency, earnings.earnings_chart, earnings.financials_chart),
+         None => (None, None, None)
+      };
+      let (history, earnings_date) = chart.map_or_else(Default::default, |chart| (chart.quarterly, chart.earnings_date));
+      let (yearly, quarterly) = financials.map_or_else(Default::default, |chart| (chart.yearly, chart.quarterly));
+
+      Ok(Earnings {
+         currency,
+         next_earnings_date: earnings_date.into_iter().filter_map(|timestamp| timestamp.raw).find_map(date),
+         history: history.into_iter()
+            .map(|quarter| EarningsResult { quarter: quarter.date, actual: quarter.actual, estimate: quarter.estimate })
+            .collect(),
+         yearly: yearly.into_iter()
+            .map(|year| FinancialPeriod { period: year.date.to_string(), revenue: year.revenue, earnings: year.earnings })
+            .collect(),
+         quarterly: quarterly.into_iter()
+            .map(|quarter| FinancialPeriod { period: quarter.date, revenue: quarter.revenue, earnings: quarter.earnings })
+            .collect(),
+         estimates: data.earnings_trend.map(|trend| trend.trend).unwrap_or_default().into_iter().map(Estimate::new).collect()
+      })
+   }
+}
@@ -1,26 +1,67 @@
 use crate::Interval;
-use reqwest;
 use snafu::Snafu;
 
 /// All possible errors that can occur when using yahoo finance
 #[derive(Debug, Snafu)]
 #[snafu(visibility = "pub(crate)")]
 pub enum InnerError {
+   #[snafu(display("Yahoo! failed to load {} - {}.", code, description))]
+   ApiFailed { code: String, description: String },
+
+   #[snafu(display("Could not convert the data to Arrow / Parquet - {}", reason))]
+   ArrowFailed { reason: String },
+
    #[snafu(display("Yahoo! returned invalid data - {}", source.to_string()))]
    BadData { source: serde_json::Error },
 
-   #[snafu(display("Yahoo! call failed. '{}' returned a {} result.", url, status))]
-   CallFailed { url: String, status: u16 },
+   #[snafu(display("Yahoo! streamed a quote that can't be read - {}", reason))]
+   BadFrame { reason: String },
+
+   #[snafu(display("Yahoo! blocked the call to '{}' with a captcha or rate limit page", url))]
+   Blocked { url: String },
+
+   #[snafu(display("Blocking calls can't be made from async code - use the async version instead"))]
+   BlockingInAsync,
+
+   #[snafu(display("Yahoo! call failed. '{}' returned a {} result.{}", url, status, if body.is_empty() { String::new() } else { format!(" {}", body) }))]
+   CallFailed { url: String, status: u16, body: String },
+
+   #[snafu(display("Yahoo! is rate limiting calls to '{}'{}", url, retry_after.map_or(String::new(), |wait| format!(" - retry after {}s", wait.as_secs()))))]
+   CallThrottled { url: String, retry_after: Option<std::time::Duration> },
+
+   #[snafu(display("Could not use the history cache at '{}' - {}", path, source.to_string()))]
+   CacheFailed { path: String, source: std::io::Error },
+
+   #[snafu(display("The retrieval was cancelled"))]
+   Cancelled,
 
    #[snafu(display("Yahoo! chart failed to load {} - {}.", code, description))]
    ChartFailed { code: String, description: String },
 
+   #[snafu(display("Could not agree to the Yahoo! cookie consent page at '{}'", url))]
+   ConsentFailed { url: String },
+
+   #[snafu(display("Yahoo! asked for cookie consent at '{}'", url))]
+   ConsentRequired { url: String },
+
+   #[snafu(display("Could not write the exported data - {}", source.to_string()))]
+   ExportFailed { source: std::io::Error },
+
    #[snafu(display("An internal error occurred - please report that '{}'", reason))]
    InternalLogic { reason: String },
 
    #[snafu(display("An internal error occurred - please report that '{}' cannot be parsed because {}", url, source.to_string()))]
    InternalURL { url: String, source: url::ParseError },
 
+   #[snafu(display("'{}' cannot be sent as a header", name))]
+   InvalidHeader { name: String },
+
+   #[snafu(display("'{}' is not an option contract symbol like 'AAPL240119C00190000'", symbol))]
+   InvalidOptionSymbol { symbol: String },
+
+   #[snafu(display("'{}' cannot be used as a proxy", url))]
+   InvalidProxy { url: String },
+
    #[snafu(display("Start date cannot be after the end date"))]
    InvalidStartDate,
 
@@ -30,9 +71,33 @@ pub enum InnerError {
    #[snafu(display("Intraday intervals like {} are not allowed", interval))]
    NoIntraday { interval: Interval },
 
+   #[snafu(display("Only intraday intervals are allowed, not {}", interval))]
+   NotIntraday { interval: Interval },
+
+   #[snafu(display("Could not convert the data to / from a Polars data frame - {}", reason))]
+   PolarsFailed { reason: String },
+
+   #[snafu(display("Yahoo! does not serve {} bars over a range of {}", granularity, range))]
+   RangeTooLong { range: String, granularity: String },
+
+   #[snafu(display("Could not use the stream recording at '{}' - {}", path, source.to_string()))]
+   RecordingFailed { path: String, source: std::io::Error },
+
    #[snafu(display("Yahoo! call failed for unknown reason."))]
    RequestFailed { source: reqwest::Error },
 
+   #[snafu(display("Could not start a runtime for a blocking call - {}", source.to_string()))]
+   RuntimeFailed { source: std::io::Error },
+
+   #[snafu(display("Yahoo! is down for maintenance - '{}' returned its maintenance page", url))]
+   ServiceUnavailable { url: String },
+
+   #[snafu(display("The quote stream failed - {}", source.to_string()))]
+   StreamFailed { source: tokio_tungstenite::tungstenite::Error },
+
+   #[snafu(display("Yahoo! does not recognize the symbol '{}'", symbol))]
+   SymbolNotFound { symbol: String },
+
    #[snafu(display("Unexpected Yahoo! failure. '{}' returned a {}", url, code))]
    UnexectedFailure { url: String, code: u16 },
 
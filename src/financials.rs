@@ -0,0 +1,117 @@
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
+use crate::{ error, yahoo, Result };
+use crate::analysts::PriceTargets;
+use crate::currency::Currencies;
+
+/// The quote summary modules the financials are made of
+const MODULES: &[&str] = &["financialData", "price"];
+
+/// The financial health of a company along with what analysts make of it -
+/// price targets, recommendations, cash, debt, revenue, margins & cash flow.
+///
+/// Prices are in the trading currency & other amounts in the financial one.
+/// Figures that don't apply are left out - see
+/// [missing figures & fractions](crate#missing-figures--fractions).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Financials {
+   /// The currencies the symbol trades & reports its financials in
+   pub currencies: Currencies,
+
+   /// The analysts' price targets, against the current price
+   pub targets: PriceTargets,
+
+   /// The average of the analysts' recommendations, from 1 (strong buy) to 5 (sell)
+   pub recommendation_mean: Option<f64>,
+
+   /// The recommendation the mean works out to.  ie. 'buy' or 'hold'
+   pub recommendation_key: Option<String>,
+
+   pub total_cash: Option<f64>,
+   pub total_cash_per_share: Option<f64>,
+   pub total_debt: Option<f64>,
+
+   /// The debt over the shareholders' equity, as a percentage - ie. 150.0 is 150%
+   pub debt_to_equity: Option<f64>,
+   pub current_ratio: Option<f64>,
+   pub quick_ratio: Option<f64>,
+
+   /// The revenue over the last 12 months
+   pub total_revenue: Option<f64>,
+   pub revenue_per_share: Option<f64>,
+
+   /// The growth in revenue over the same quarter a year ago
+   pub revenue_growth: Option<f64>,
+
+   /// The growth in earnings over the same quarter a year ago
+   pub earnings_growth: Option<f64>,
+   pub gross_profits: Option<f64>,
+   pub ebitda: Option<f64>,
+
+   pub gross_margins: Option<f64>,
+   pub operating_margins: Option<f64>,
+   pub ebitda_margins: Option<f64>,
+   pub profit_margins: Option<f64>,
+
+   pub free_cash_flow: Option<f64>,
+   pub operating_cash_flow: Option<f64>,
+   pub return_on_assets: Option<f64>,
+   pub return_on_equity: Option<f64>
+}
+impl Financials {
+   /// Loads the financial data for a symbol.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::Financials;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let financials = Financials::load("AAPL").await.unwrap();
+   ///    println!("Analysts rate Apple a {} with a mean target of {:.2}",
+   ///       financials.recommendation_key.unwrap_or_default(),
+   ///       financials.targets.mean.unwrap_or_default());
+   /// }
+   /// ```
+   pub async fn load(symbol: &str) -> Result<Financials> {
+      Self::new(yahoo::load_quote_summary(symbol, MODULES).await?)
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Financials> { crate::blocking::run(Self::load(symbol)) }
+
+   pub(crate) fn new(data: yahoo::QuoteSummary) -> Result<Financials> {
+      let currencies = Currencies::from_summary(&data);
+      let data = data.financial_data.context(error::MissingData { reason: "no financial data" })?;
+
+      Ok(Financials {
+         currencies,
+         targets: PriceTargets::from_financial_data(&data),
+         recommendation_mean: data.recommendation_mean,
+         recommendation_key: data.recommendation_key,
+         total_cash: data.total_cash,
+         total_cash_per_share: data.total_cash_per_share,
+         total_debt: data.total_debt,
+         debt_to_equity: data.debt_to_equity,
+         current_ratio: data.current_ratio,
+         quick_ratio: data.quick_ratio,
+         total_revenue: data.total_revenue,
+         revenue_per_share: data.revenue_per_share,
+         revenue_growth: data.revenue_growth,
+         earnings_growth: data.earnings_growth,
+         gross_profits: data.gross_profits,
+         ebitda: data.ebitda,
+         gross_margins: data.gross_margins,
+         operating_margins: data.operating_margins,
+         ebitda_margins: data.ebitda_margins,
+         profit_margins: data.profit_margins,
+         free_cash_flow: data.free_cashflow,
+         operating_cash_flow: data.operating_cashflow,
+         return_on_assets: data.return_on_assets,
+         return_on_equity: data.return_on_equity
+      })
+   }
+}
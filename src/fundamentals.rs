@@ -0,0 +1,346 @@
+use chrono::{ DateTime, NaiveDate, Utc };
+use futures::try_join;
+use serde::{Deserialize, Serialize};
+use snafu::{ ensure, OptionExt };
+use std::collections::{ BTreeMap, HashMap };
+
+use crate::{ error, yahoo, Result };
+use crate::currency::Currencies;
+
+/// Where the fundamentals are loaded from, in seconds since the EPOCH.  This
+/// is the `period1` Yahoo's own financials pages ask for (1985-08-23), which
+/// reaches back before any statement Yahoo keeps.
+const EARLIEST: i64 = 493_590_046;
+
+/// How often the statements are for
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Period {
+   /// A statement per fiscal year
+   Annual,
+
+   /// A statement per fiscal quarter
+   Quarterly
+}
+impl Period {
+   /// What Yahoo puts in front of the line items for the period.  ie. `quarterlyTotalRevenue`
+   fn prefix(&self) -> &'static str {
+      match self {
+         Period::Annual => "annual",
+         Period::Quarterly => "quarterly"
+      }
+   }
+}
+
+/// The income statement line items, as Yahoo names them
+const INCOME_STATEMENT: &[&str] = &[
+   "TotalRevenue", "CostOfRevenue", "GrossProfit", "OperatingExpense", "OperatingIncome",
+   "PretaxIncome", "TaxProvision", "NetIncome", "EBITDA", "BasicEPS", "DilutedEPS"
+];
+
+/// The balance sheet line items, as Yahoo names them
+const BALANCE_SHEET: &[&str] = &[
+   "TotalAssets", "CurrentAssets", "TotalLiabilitiesNetMinorityInterest", "CurrentLiabilities", "StockholdersEquity",
+   "CashAndCashEquivalents", "CashCashEquivalentsAndShortTermInvestments", "TotalDebt", "LongTermDebt", "NetDebt",
+   "WorkingCapital", "OrdinarySharesNumber"
+];
+
+/// The line items Yahoo reported for a single period
+struct Statement {
+   date: NaiveDate,
+   currency: Option<String>,
+   items: HashMap<String, f64>
+}
+impl Statement {
+   fn item(&self, name: &str) -> Option<f64> { self.items.get(name).copied() }
+}
+
+/// The day a value was reported as of
+fn as_of(value: &yahoo::Reported) -> Result<NaiveDate> {
+   Ok(NaiveDate::parse_from_str(&value.as_of_date, "%Y-%m-%d")
+      .ok()
+      .context(error::MissingData { reason: format!("'{}' is not a date", value.as_of_date) })?)
+}
+
+/// Gathers the reported values into a statement per period, from oldest to newest
+fn statements(timeseries: HashMap<String, Vec<yahoo::Reported>>) -> Result<Vec<Statement>> {
+   let mut statements = BTreeMap::new();
+   for (kind, values) in timeseries {
+      let name = [Period::Annual, Period::Quarterly].iter()
+         .find_map(|period| kind.strip_prefix(period.prefix()))
+         .unwrap_or(&kind)
+         .to_string();
+      for value in values {
+         let date = as_of(&value)?;
+         let statement = statements.entry(date).or_insert_with(|| Statement { date, currency: None, items: HashMap::new() });
+
+         if statement.currency.is_none() { statement.currency = value.currency_code; }
+         if let Some(amount) = value.reported_value { statement.items.insert(name.clone(), amount); }
+      }
+   }
+   Ok(statements.into_values().collect())
+}
+
+/// Loads the values of the line items for a symbol over each period
+async fn load(symbol: &str, items: &[&str], period: Period) -> Result<HashMap<String, Vec<yahoo::Reported>>> {
+   let types: Vec<String> = items.iter().map(|item| format!("{}{}", period.prefix(), item)).collect();
+   yahoo::load_timeseries(symbol, &types.iter().map(String::as_str).collect::<Vec<_>>(), EARLIEST, Utc::now().timestamp()).await
+}
+
+/// Loads the currency the symbol trades in - the timeseries only has the
+/// currency the values are reported in
+async fn trading_currency(symbol: &str) -> Result<Option<String>> {
+   match yahoo::load_quote_summary(symbol, &["price"]).await {
+      Ok(summary) => Ok(Currencies::from_summary(&summary).trading),
+      Err(e) if e.is_not_found() => Ok(error::SymbolNotFound { symbol }.fail()?),
+      Err(e) => Err(e)
+   }
+}
+
+/// Loads the line items for each period, failing when Yahoo has none at all
+/// for the symbol
+async fn load_statements(symbol: &str, items: &[&str], period: Period) -> Result<(HashMap<String, Vec<yahoo::Reported>>, Option<String>)> {
+   let (timeseries, trading) = try_join!(load(symbol, items, period), trading_currency(symbol))?;
+   ensure!(timeseries.values().any(|values| !values.is_empty()), error::SymbolNotFound { symbol });
+   Ok((timeseries, trading))
+}
+
+/// The currencies of a reported value, given the one the symbol trades in
+fn currencies(trading: &Option<String>, reported: Option<String>) -> Currencies {
+   Currencies { trading: trading.clone(), financial: reported }
+}
+
+/// A company's income statement for a fiscal year or quarter.
+///
+/// Yahoo leaves out line items that a company doesn't report (ie. cost of
+/// revenue for a bank) so each is optional.  Amounts are in the financial
+/// currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IncomeStatement {
+   /// The last day of the fiscal period
+   pub date: NaiveDate,
+
+   /// The currencies the symbol trades & reports its financials in
+   pub currencies: Currencies,
+
+   pub total_revenue: Option<f64>,
+   pub cost_of_revenue: Option<f64>,
+   pub gross_profit: Option<f64>,
+   pub operating_expense: Option<f64>,
+   pub operating_income: Option<f64>,
+   pub pretax_income: Option<f64>,
+   pub tax_provision: Option<f64>,
+   pub net_income: Option<f64>,
+   pub ebitda: Option<f64>,
+
+   /// The net income per share
+   pub basic_eps: Option<f64>,
+
+   /// The net income per share, counting the shares that options & convertibles would add
+   pub diluted_eps: Option<f64>
+}
+impl IncomeStatement {
+   fn new(statement: Statement, trading: &Option<String>) -> IncomeStatement {
+      IncomeStatement {
+         total_revenue: statement.item("TotalRevenue"),
+         cost_of_revenue: statement.item("CostOfRevenue"),
+         gross_profit: statement.item("GrossProfit"),
+         operating_expense: statement.item("OperatingExpense"),
+         operating_income: statement.item("OperatingIncome"),
+         pretax_income: statement.item("PretaxIncome"),
+         tax_provision: statement.item("TaxProvision"),
+         net_income: statement.item("NetIncome"),
+         ebitda: statement.item("EBITDA"),
+         basic_eps: statement.item("BasicEPS"),
+         diluted_eps: statement.item("DilutedEPS"),
+         date: statement.date,
+         currencies: currencies(trading, statement.currency)
+      }
+   }
+}
+
+pub(crate) fn income_statements(timeseries: HashMap<String, Vec<yahoo::Reported>>, trading: Option<String>) -> Result<Vec<IncomeStatement>> {
+   Ok(statements(timeseries)?.into_iter().map(|statement| IncomeStatement::new(statement, &trading)).collect())
+}
+
+/// A company's balance sheet at the end of a fiscal year or quarter.
+///
+/// Yahoo leaves out line items that a company doesn't report (ie. current
+/// assets for a bank) so each is optional.  Amounts are in the financial
+/// currency.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BalanceSheet {
+   /// The last day of the fiscal period
+   pub date: NaiveDate,
+
+   /// The currencies the symbol trades & reports its financials in
+   pub currencies: Currencies,
+
+   pub total_assets: Option<f64>,
+   pub current_assets: Option<f64>,
+
+   /// The total liabilities, not counting minority interests
+   pub total_liabilities: Option<f64>,
+   pub current_liabilities: Option<f64>,
+   pub stockholders_equity: Option<f64>,
+
+   pub cash: Option<f64>,
+
+   /// The cash along with the investments that mature within the year
+   pub cash_and_short_term_investments: Option<f64>,
+   pub total_debt: Option<f64>,
+   pub long_term_debt: Option<f64>,
+
+   /// The debt less the cash
+   pub net_debt: Option<f64>,
+
+   /// The current assets less the current liabilities
+   pub working_capital: Option<f64>,
+   pub shares_outstanding: Option<u64>
+}
+impl BalanceSheet {
+   fn new(statement: Statement, trading: &Option<String>) -> BalanceSheet {
+      BalanceSheet {
+         total_assets: statement.item("TotalAssets"),
+         current_assets: statement.item("CurrentAssets"),
+         total_liabilities: statement.item("TotalLiabilitiesNetMinorityInterest"),
+         current_liabilities: statement.item("CurrentLiabilities"),
+         stockholders_equity: statement.item("StockholdersEquity"),
+         cash: statement.item("CashAndCashEquivalents"),
+         cash_and_short_term_investments: statement.item("CashCashEquivalentsAndShortTermInvestments"),
+         total_debt: statement.item("TotalDebt"),
+         long_term_debt: statement.item("LongTermDebt"),
+         net_debt: statement.item("NetDebt"),
+         working_capital: statement.item("WorkingCapital"),
+         shares_outstanding: statement.item("OrdinarySharesNumber").map(|shares| shares as u64),
+         date: statement.date,
+         currencies: currencies(trading, statement.currency)
+      }
+   }
+}
+
+pub(crate) fn balance_sheets(timeseries: HashMap<String, Vec<yahoo::Reported>>, trading: Option<String>) -> Result<Vec<BalanceSheet>> {
+   Ok(statements(timeseries)?.into_iter().map(|statement| BalanceSheet::new(statement, &trading)).collect())
+}
+
+/// Retrieves a company's income statements for each period, from oldest to
+/// newest.  Yahoo usually has the last four fiscal years or five quarters.
+/// Fails with `SymbolNotFound` when Yahoo has no statements for the symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::fundamentals::{ self, Period };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for statement in fundamentals::income_statement("AAPL", Period::Quarterly).await.unwrap() {
+///       println!("{} - revenue of {:.0} & EPS of {:.2}", statement.date,
+///          statement.total_revenue.unwrap_or_default(),
+///          statement.diluted_eps.unwrap_or_default());
+///    }
+/// }
+/// ```
+pub async fn income_statement(symbol: &str, period: Period) -> Result<Vec<IncomeStatement>> {
+   let (timeseries, trading) = load_statements(symbol, INCOME_STATEMENT, period).await?;
+   income_statements(timeseries, trading)
+}
+
+/// Blocking version of `income_statement` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_income_statement(symbol: &str, period: Period) -> Result<Vec<IncomeStatement>> {
+   crate::blocking::run(income_statement(symbol, period))
+}
+
+/// Retrieves a company's balance sheets for each period, from oldest to
+/// newest.  Yahoo usually has the last four fiscal years or five quarters.
+/// Fails with `SymbolNotFound` when Yahoo has no balance sheets for the symbol.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::fundamentals::{ self, Period };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for sheet in fundamentals::balance_sheet("AAPL", Period::Annual).await.unwrap() {
+///       println!("{} - {:.0} of cash against {:.0} of debt", sheet.date,
+///          sheet.cash.unwrap_or_default(),
+///          sheet.total_debt.unwrap_or_default());
+///    }
+/// }
+/// ```
+pub async fn balance_sheet(symbol: &str, period: Period) -> Result<Vec<BalanceSheet>> {
+   let (timeseries, trading) = load_statements(symbol, BALANCE_SHEET, period).await?;
+   balance_sheets(timeseries, trading)
+}
+
+/// Blocking version of `balance_sheet` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_balance_sheet(symbol: &str, period: Period) -> Result<Vec<BalanceSheet>> { crate::blocking::run(balance_sheet(symbol, period)) }
+
+/// A value Yahoo reported for a fundamental
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DataPoint {
+   /// The day the value is as of - ie. the last day of a fiscal period
+   pub date: NaiveDate,
+
+   /// How long a time the value covers.  ie. '12M', '3M' or 'TTM'
+   pub period_type: Option<String>,
+
+   /// The currencies the symbol trades & reports its financials in.
+   /// Amounts are in the financial one, which Yahoo leaves out for ratios.
+   pub currencies: Currencies,
+   pub value: f64
+}
+
+pub(crate) fn data_points(timeseries: HashMap<String, Vec<yahoo::Reported>>, trading: Option<String>) -> Result<HashMap<String, Vec<DataPoint>>> {
+   let mut series = HashMap::new();
+   for (kind, values) in timeseries {
+      let mut points = Vec::new();
+      for value in values {
+         let date = as_of(&value)?;
+         if let Some(amount) = value.reported_value {
+            points.push(DataPoint { date, period_type: value.period_type, currencies: currencies(&trading, value.currency_code), value: amount });
+         }
+      }
+      points.sort_by_key(|point| point.date);
+      series.insert(kind, points);
+   }
+   Ok(series)
+}
+
+/// Retrieves any of the fundamentals Yahoo keeps for a symbol between a
+/// start and end date, keyed by the name Yahoo gives them - ie.
+/// `annualTotalRevenue`, `quarterlyNetIncome` or `trailingPegRatio`.  Each
+/// series is from oldest to newest & fundamentals Yahoo has nothing for are
+/// left out.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use chrono::{ Duration, Utc };
+/// use yahoo_finance::fundamentals;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let start = Utc::now() - Duration::days(365 * 5);
+///    let series = fundamentals::timeseries("AAPL", &["annualTotalRevenue", "trailingPegRatio"], start, None).await.unwrap();
+///    for point in series.get("annualTotalRevenue").into_iter().flatten() {
+///       println!("{} - revenue of {:.0}", point.date, point.value);
+///    }
+/// }
+/// ```
+pub async fn timeseries(symbol: &str, metrics: &[&str], start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<HashMap<String, Vec<DataPoint>>> {
+   // pre-conditions
+   let end = end.unwrap_or_else(Utc::now);
+   ensure!(end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+   let (timeseries, trading) = try_join!(yahoo::load_timeseries(symbol, metrics, start.timestamp(), end.timestamp()), trading_currency(symbol))?;
+   data_points(timeseries, trading)
+}
+
+/// Blocking version of `timeseries` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_timeseries(symbol: &str, metrics: &[&str], start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<HashMap<String, Vec<DataPoint>>> {
+   crate::blocking::run(timeseries(symbol, metrics, start, end))
+}
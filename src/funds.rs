@@ -0,0 +1,92 @@
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+use std::collections::HashMap;
+
+use crate::{ error, yahoo, Result };
+
+/// Flattens Yahoo's list of single entry maps into a single map
+fn flatten(values: Vec<HashMap<String, yahoo::Raw<f64>>>) -> HashMap<String, f64> {
+   values.into_iter()
+      .flatten()
+      .filter_map(|(name, value)| value.raw.map(|raw| (name, raw)))
+      .collect()
+}
+
+/// The fixed income characteristics of a fund.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BondStatistics {
+   /// The average maturity in years
+   pub maturity: Option<f64>,
+
+   /// The average duration in years
+   pub duration: Option<f64>,
+
+   /// The average credit quality
+   pub credit_quality: Option<f64>
+}
+
+/// How a mutual fund or ETF allocates its assets.
+///
+/// All positions are fractions of the fund - ie. 0.25 is 25%
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Allocation {
+   pub stocks: Option<f64>,
+   pub bonds: Option<f64>,
+   pub cash: Option<f64>,
+   pub preferred: Option<f64>,
+   pub convertible: Option<f64>,
+   pub other: Option<f64>,
+
+   /// The fraction of bonds for each credit rating.  ie. 'aaa' => 0.45
+   pub bond_ratings: HashMap<String, f64>,
+
+   /// The fraction of stocks for each sector.  ie. 'technology' => 0.48
+   pub sector_weightings: HashMap<String, f64>,
+
+   /// Duration, maturity & credit quality of the bond holdings
+   pub bond_statistics: Option<BondStatistics>
+}
+impl Allocation {
+   /// Loads the asset allocation for a mutual fund or ETF.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::funds::Allocation;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let allocation = Allocation::load("VBIAX").await.unwrap();
+   ///    println!("{:.1}% stocks, {:.1}% bonds",
+   ///       allocation.stocks.unwrap_or_default() * 100.0,
+   ///       allocation.bonds.unwrap_or_default() * 100.0);
+   /// }
+   /// ```
+   pub async fn load(symbol: &str) -> Result<Allocation> {
+      Self::new(yahoo::load_quote_summary(symbol, &["topHoldings"]).await?)
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Allocation> { crate::blocking::run(Self::load(symbol)) }
+
+   pub(crate) fn new(data: yahoo::QuoteSummary) -> Result<Allocation> {
+      let data = data.top_holdings.context(error::MissingData { reason: "no fund holdings" })?;
+
+      Ok(Allocation {
+         stocks: data.stock_position,
+         bonds: data.bond_position,
+         cash: data.cash_position,
+         preferred: data.preferred_position,
+         convertible: data.convertible_position,
+         other: data.other_position,
+         bond_ratings: flatten(data.bond_ratings),
+         sector_weightings: flatten(data.sector_weightings),
+         bond_statistics: data.bond_holdings.map(|bonds| BondStatistics {
+            maturity: bonds.maturity,
+            duration: bonds.duration,
+            credit_quality: bonds.credit_quality
+         })
+      })
+   }
+}
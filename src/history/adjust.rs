@@ -0,0 +1,86 @@
+use crate::Bar;
+use super::{Dividend, Split};
+
+/// Back-adjusts bars for stock splits so prices before & after a split can
+/// be compared.
+///
+/// Bars before each split have their open, high, low & close divided by the
+/// split ratio and their volume multiplied by it.  The bar on the day of the
+/// split is already trading at the new price, so it is left alone.
+///
+/// The bars must not be split adjusted already.  Yahoo's history always is,
+/// so this is for bars from elsewhere - ie. recorded from the stream or
+/// saved before the split.
+///
+/// # Examples
+///
+/// ```
+/// use yahoo_finance::Bar;
+/// use yahoo_finance::history::{ self, Split };
+///
+/// // recorded around Apple's 4 for 1 split in August 2020
+/// let bar = |timestamp, close| Bar { timestamp, open: close, high: close, low: close, close, volume: Some(100) };
+/// let mut bars = vec![bar(1_598_621_400_000, 499.23), bar(1_598_880_600_000, 129.04)];
+/// let splits = vec![Split { timestamp: 1_598_880_600_000, numerator: 4.0, denominator: 1.0 }];
+///
+/// history::adjust_for_splits(&mut bars, &splits);
+/// assert!((bars[0].close - 124.8075).abs() < 0.0001);
+/// assert_eq!(Some(400), bars[0].volume);
+/// ```
+pub fn adjust_for_splits(bars: &mut [Bar], splits: &[Split]) {
+   for split in splits {
+      let ratio = split.ratio();
+      if !ratio.is_normal() { continue; }
+
+      for bar in bars.iter_mut().filter(|bar| bar.timestamp < split.timestamp) {
+         bar.open /= ratio;
+         bar.high /= ratio;
+         bar.low /= ratio;
+         bar.close /= ratio;
+         bar.volume = bar.volume.map(|volume| (volume as f64 * ratio).round() as u64);
+      }
+   }
+}
+
+/// Builds a total return series - the value of holding the security with
+/// every dividend reinvested on its ex-date.
+///
+/// The series has one value for each bar and starts at the first bar's
+/// close, so it can be plotted alongside the closes.  The bars are expected
+/// to be ordered oldest first.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (bars, events) = history::retrieve_with_events("T", Interval::_5y).await.unwrap();
+///    let series = history::total_return_series(&bars, &events.dividends);
+///
+///    if let (Some(first), Some(last)) = (series.first(), series.last()) {
+///       println!("Total return: {:.1}%", (last / first - 1.0) * 100.0);
+///    }
+/// }
+/// ```
+pub fn total_return_series(bars: &[Bar], dividends: &[Dividend]) -> Vec<f64> {
+   let mut result: Vec<f64> = Vec::with_capacity(bars.len());
+
+   for (i, bar) in bars.iter().enumerate() {
+      if i == 0 {
+         result.push(bar.close);
+         continue;
+      }
+
+      // dividends that went ex since the previous bar are reinvested at this bar's close
+      let previous = &bars[i - 1];
+      let paid: f64 = dividends.iter()
+         .filter(|dividend| previous.timestamp < dividend.timestamp && dividend.timestamp <= bar.timestamp)
+         .map(|dividend| dividend.amount)
+         .sum();
+
+      result.push(result[i - 1] * (bar.close + paid) / previous.close);
+   }
+   result
+}
@@ -0,0 +1,101 @@
+use arrow_array::{ArrayRef, Float64Array, RecordBatch, StringArray, TimestampMillisecondArray, UInt64Array};
+use arrow_schema::{DataType, Field, Schema, TimeUnit};
+use parquet::arrow::ArrowWriter;
+use std::io::Write;
+use std::sync::Arc;
+
+use crate::{error, Bar, Result};
+use super::{Dividend, Split};
+
+fn failed<T>(reason: impl ToString) -> Result<T> {
+   Ok(error::ArrowFailed { reason: reason.to_string() }.fail()?)
+}
+
+fn timestamp_field() -> Field {
+   Field::new("timestamp", DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".into())), false)
+}
+
+fn timestamps(values: impl Iterator<Item = i64>) -> ArrayRef {
+   Arc::new(TimestampMillisecondArray::from_iter_values(values).with_timezone("UTC"))
+}
+
+fn batch(fields: Vec<Field>, columns: Vec<ArrayRef>) -> Result<RecordBatch> {
+   RecordBatch::try_new(Arc::new(Schema::new(fields)), columns).or_else(failed)
+}
+
+/// Converts bars into an Arrow record batch with `timestamp`, `open`, `high`,
+/// `low`, `close` & `volume` columns.  Timestamps are in UTC milliseconds.
+pub fn bars_to_record_batch(bars: &[Bar]) -> Result<RecordBatch> {
+   let prices = |price: fn(&Bar) -> f64| -> ArrayRef { Arc::new(Float64Array::from_iter_values(bars.iter().map(price))) };
+
+   batch(
+      vec![
+         timestamp_field(),
+         Field::new("open", DataType::Float64, false),
+         Field::new("high", DataType::Float64, false),
+         Field::new("low", DataType::Float64, false),
+         Field::new("close", DataType::Float64, false),
+         Field::new("volume", DataType::UInt64, true)
+      ],
+      vec![
+         timestamps(bars.iter().map(|bar| bar.timestamp)),
+         prices(|bar| bar.open),
+         prices(|bar| bar.high),
+         prices(|bar| bar.low),
+         prices(|bar| bar.close),
+         Arc::new(bars.iter().map(|bar| bar.volume).collect::<UInt64Array>())
+      ]
+   )
+}
+
+/// Converts dividends into an Arrow record batch with `timestamp` & `amount` columns.
+pub fn dividends_to_record_batch(dividends: &[Dividend]) -> Result<RecordBatch> {
+   batch(
+      vec![ timestamp_field(), Field::new("amount", DataType::Float64, false) ],
+      vec![
+         timestamps(dividends.iter().map(|dividend| dividend.timestamp)),
+         Arc::new(Float64Array::from_iter_values(dividends.iter().map(|dividend| dividend.amount)))
+      ]
+   )
+}
+
+/// Converts splits into an Arrow record batch with `timestamp`, `numerator`,
+/// `denominator` & `ratio` columns - where the ratio is ie. `4:1`.
+pub fn splits_to_record_batch(splits: &[Split]) -> Result<RecordBatch> {
+   batch(
+      vec![
+         timestamp_field(),
+         Field::new("numerator", DataType::Float64, false),
+         Field::new("denominator", DataType::Float64, false),
+         Field::new("ratio", DataType::Utf8, false)
+      ],
+      vec![
+         timestamps(splits.iter().map(|split| split.timestamp)),
+         Arc::new(Float64Array::from_iter_values(splits.iter().map(|split| split.numerator))),
+         Arc::new(Float64Array::from_iter_values(splits.iter().map(|split| split.denominator))),
+         Arc::new(StringArray::from_iter_values(splits.iter().map(|split| format!("{}:{}", split.numerator, split.denominator))))
+      ]
+   )
+}
+
+/// Writes a record batch as a Parquet file.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use std::fs::File;
+/// use yahoo_finance::history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let bars = history::retrieve("AAPL").await.unwrap();
+///    let batch = history::bars_to_record_batch(&bars).unwrap();
+///    history::write_parquet(&batch, File::create("AAPL.parquet").unwrap()).unwrap();
+/// }
+/// ```
+pub fn write_parquet(batch: &RecordBatch, writer: impl Write + Send) -> Result<()> {
+   let mut parquet = ArrowWriter::try_new(writer, batch.schema(), None).or_else(failed)?;
+   parquet.write(batch).or_else(failed)?;
+   parquet.close().or_else(failed)?;
+   Ok(())
+}
@@ -0,0 +1,69 @@
+//! Blocking versions of the history functions for code that doesn't run
+//! tokio.  Each call runs on a runtime of its own, so these must not be
+//! called from async code.
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! use yahoo_finance::history;
+//!
+//! fn main() {
+//!    let data = history::blocking::retrieve("AAPL").unwrap();
+//!    for bar in &data {
+//!       println!("Apple closed at ${:.2}", bar.close)
+//!    }
+//! }
+//! ```
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+use crate::{blocking::run, Bar, Interval, Result};
+use super::{AdjustedBar, Events, Granularity, Meta, PartialBar, Range, SessionBar};
+
+/// Blocking version of `history::retrieve`
+pub fn retrieve(symbol: &str) -> Result<Vec<Bar>> { run(super::retrieve(symbol)) }
+
+/// Blocking version of `history::retrieve_interval`
+pub fn retrieve_interval(symbol: &str, interval: Interval) -> Result<Vec<Bar>> { run(super::retrieve_interval(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_many`
+pub fn retrieve_many(symbols: &[&str], interval: Interval) -> Result<HashMap<String, Result<Vec<Bar>>>> {
+   run(async { Ok(super::retrieve_many(symbols, interval).await) })
+}
+
+/// Blocking version of `history::retrieve_lenient`
+pub fn retrieve_lenient(symbol: &str, interval: Interval) -> Result<Vec<PartialBar>> { run(super::retrieve_lenient(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_adjusted`
+pub fn retrieve_adjusted(symbol: &str, interval: Interval) -> Result<Vec<AdjustedBar>> { run(super::retrieve_adjusted(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_with_meta`
+pub fn retrieve_with_meta(symbol: &str, interval: Interval) -> Result<(Vec<Bar>, Meta)> { run(super::retrieve_with_meta(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_with_events`
+pub fn retrieve_with_events(symbol: &str, interval: Interval) -> Result<(Vec<Bar>, Events)> { run(super::retrieve_with_events(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_intraday`
+pub fn retrieve_intraday(symbol: &str, interval: Interval) -> Result<Vec<Bar>> { run(super::retrieve_intraday(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_extended`
+pub fn retrieve_extended(symbol: &str, interval: Interval) -> Result<Vec<SessionBar>> { run(super::retrieve_extended(symbol, interval)) }
+
+/// Blocking version of `history::retrieve_bars`
+pub fn retrieve_bars(symbol: &str, range: Range, granularity: Granularity) -> Result<Vec<Bar>> {
+   run(super::retrieve_bars(symbol, range, granularity))
+}
+
+/// Blocking version of `history::retrieve_last_n`
+pub fn retrieve_last_n(symbol: &str, n: usize, granularity: Granularity) -> Result<Vec<Bar>> { run(super::retrieve_last_n(symbol, n, granularity)) }
+
+/// Blocking version of `history::retrieve_range`
+pub fn retrieve_range(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+   run(super::retrieve_range(symbol, start, end))
+}
+
+/// Blocking version of `history::retrieve_since`
+pub fn retrieve_since(symbol: &str, last_timestamp: i64) -> Result<Vec<Bar>> { run(super::retrieve_since(symbol, last_timestamp)) }
+
+/// Blocking version of `history::update_since`
+pub fn update_since(symbol: &str, cached: &mut Vec<Bar>) -> Result<usize> { run(super::update_since(symbol, cached)) }
@@ -0,0 +1,165 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::ensure;
+
+use crate::{error, yahoo, Bar, Result};
+use super::{aggregate_rows, check_range, range_days, Events, Granularity, Meta, Range, SessionBar};
+
+/// The window of time to load bars for
+#[derive(Clone, Copy, Debug)]
+enum Window {
+   Range(Range),
+   Dates(DateTime<Utc>, DateTime<Utc>)
+}
+
+/// Everything loaded by a `Builder`
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct History {
+   /// The bars, oldest first & tagged with their trading session
+   pub bars: Vec<SessionBar>,
+
+   /// The corporate events over the period - empty unless asked for
+   pub events: Events,
+
+   /// Information about the symbol
+   pub meta: Meta
+}
+impl History {
+   /// Just the bars, without their trading sessions
+   pub fn into_bars(self) -> Vec<Bar> {
+      self.bars.into_iter().map(|bar| bar.bar).collect()
+   }
+}
+
+/// A fluent way to describe a history request when the `retrieve_*`
+/// functions don't quite fit.  By default 6 months of daily bars are loaded.
+///
+/// # Examples
+///
+/// Get a month of split & dividend adjusted 5 minute bars for Apple, including extended hours:
+///
+/// ``` no_run
+/// use yahoo_finance::history::{ Builder, Granularity, Range };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let history = Builder::new("AAPL")
+///       .range(Range::_1mo)
+///       .granularity(Granularity::_5m)
+///       .prepost(true)
+///       .adjusted(true)
+///       .fetch().await.unwrap();
+///
+///    for bar in &history.bars {
+///       println!("{:?} - ${:.2}", bar.session, bar.bar.close);
+///    }
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Builder {
+   symbol: String,
+   window: Window,
+   granularity: Granularity,
+   events: bool,
+   prepost: bool,
+   adjusted: bool
+}
+impl Builder {
+   /// Starts a request for a symbol
+   pub fn new(symbol: &str) -> Builder {
+      Builder {
+         symbol: symbol.to_string(),
+         window: Window::Range(Range::_6mo),
+         granularity: Granularity::_1d,
+         events: false,
+         prepost: false,
+         adjusted: false
+      }
+   }
+
+   /// Loads the bars for a lookback period ending now
+   pub fn range(mut self, range: Range) -> Builder {
+      self.window = Window::Range(range);
+      self
+   }
+
+   /// Loads the bars between a start and end date
+   pub fn between(mut self, start: DateTime<Utc>, end: DateTime<Utc>) -> Builder {
+      self.window = Window::Dates(start, end);
+      self
+   }
+
+   /// The size of each bar
+   pub fn granularity(mut self, granularity: Granularity) -> Builder {
+      self.granularity = granularity;
+      self
+   }
+
+   /// Whether to load dividends, splits & capital gains along with the bars
+   pub fn events(mut self, events: bool) -> Builder {
+      self.events = events;
+      self
+   }
+
+   /// Whether to include pre & post market bars - only meaningful for intraday bars
+   pub fn prepost(mut self, prepost: bool) -> Builder {
+      self.prepost = prepost;
+      self
+   }
+
+   /// Whether to adjust the open, high, low & close for splits & dividends
+   pub fn adjusted(mut self, adjusted: bool) -> Builder {
+      self.adjusted = adjusted;
+      self
+   }
+
+   /// Loads the history from Yahoo
+   pub async fn fetch(&self) -> Result<History> {
+      let mut query = yahoo::chart_query(&self.symbol);
+
+      match self.window {
+         Window::Range(range) => {
+            check_range(range_days(range), self.granularity, || range.to_string())?;
+            query = query.range(range);
+         },
+         Window::Dates(start, end) => {
+            let days = end.signed_duration_since(start).num_days();
+            ensure!(end > start, error::InvalidStartDate);
+            check_range(Some(days as u32), self.granularity, || format!("{} days", days))?;
+            query = query.start(start.timestamp()).end(end.timestamp());
+         }
+      }
+
+      query = query.interval(self.granularity);
+      if self.prepost { query = query.include_pre_post(true); }
+      if self.events { query = query.events(&["div", "split", "capitalGain"]); }
+
+      self.build(yahoo::load_chart(&query).await?)
+   }
+
+   /// Blocking version of `fetch` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_fetch(&self) -> Result<History> { crate::blocking::run(self.fetch()) }
+
+   fn build(&self, data: yahoo::Data) -> Result<History> {
+      let meta = Meta::new(&data.meta);
+      let adjusted = data.indicators.adjusted_closes.first().map(|adj| &adj.values);
+
+      let bars = aggregate_rows(&data)?.into_iter().map(|(i, mut bar)| {
+         if self.adjusted {
+            if let Some(adjusted_close) = adjusted.and_then(|values| values.get(i).copied().flatten()) {
+               let factor = adjusted_close / bar.close;
+               bar.open *= factor;
+               bar.high *= factor;
+               bar.low *= factor;
+               bar.close = adjusted_close;
+            }
+         }
+
+         let session = meta.trading_periods.session_at(bar.timestamp);
+         SessionBar { bar, session }
+      }).collect();
+
+      Ok(History { bars, events: Events::new(data.events.clone()), meta })
+   }
+}
@@ -0,0 +1,158 @@
+use chrono::{DateTime, Utc};
+use snafu::{ensure, ResultExt};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::{error, yahoo, Bar, Result};
+use super::{aggregate_bars, load_from, merge};
+
+/// Numbers the temporary files so concurrent writes of the same symbol don't share one
+static WRITES: AtomicUsize = AtomicUsize::new(0);
+
+/// The bars stored for a symbol along with the time range they cover
+struct Entry {
+   from: i64,
+   to: i64,
+   bars: Vec<Bar>
+}
+impl Entry {
+   fn parse(text: &str) -> Option<Entry> {
+      let mut lines = text.lines();
+
+      let header: Vec<i64> = lines.next()?.strip_prefix("#range,")?.split(',').filter_map(|v| v.parse().ok()).collect();
+      if header.len() != 2 { return None; }
+
+      let mut bars = Vec::new();
+      for line in lines {
+         let values: Vec<&str> = line.split(',').collect();
+         if values.len() != 6 { return None; }
+         bars.push(Bar {
+            timestamp: values[0].parse().ok()?,
+            open: values[1].parse().ok()?,
+            high: values[2].parse().ok()?,
+            low: values[3].parse().ok()?,
+            close: values[4].parse().ok()?,
+            volume: values[5].parse().ok()
+         });
+      }
+      Some(Entry { from: header[0], to: header[1], bars })
+   }
+
+   fn to_text(&self) -> String {
+      let mut text = format!("#range,{},{}\n", self.from, self.to);
+      for bar in &self.bars {
+         let volume = bar.volume.map(|v| v.to_string()).unwrap_or_default();
+         text.push_str(&format!("{},{},{},{},{},{}\n", bar.timestamp, bar.open, bar.high, bar.low, bar.close, volume));
+      }
+      text
+   }
+}
+
+/// A persistent cache of daily bars, with a file for each symbol in a
+/// directory.
+///
+/// Bars are read from the cache when it already covers the dates asked for,
+/// and only the missing dates are loaded from Yahoo and added to the cache.
+/// Repeated backtests over the same symbols then only hit Yahoo once.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::history::DiskCache;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let cache = DiskCache::new("./history-cache");
+///
+///    let now = Utc::now();
+///    let bars = cache.retrieve_range("AAPL", now - Duration::days(365), Some(now)).await.unwrap();
+///    println!("{} bars for Apple", bars.len());
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DiskCache {
+   directory: PathBuf
+}
+impl DiskCache {
+   /// A cache stored in a directory - the directory is created if needed
+   pub fn new(directory: impl AsRef<Path>) -> DiskCache {
+      DiskCache { directory: directory.as_ref().to_path_buf() }
+   }
+
+   fn path(&self, symbol: &str) -> PathBuf {
+      // symbols like ^DJI & EURUSD=X aren't friendly file names everywhere
+      let name: String = symbol.chars().map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '_' }).collect();
+      self.directory.join(format!("{}.csv", name))
+   }
+
+   async fn read(&self, path: &Path) -> Option<Entry> {
+      Entry::parse(&tokio::fs::read_to_string(path).await.ok()?)
+   }
+
+   /// Writes the entry to a temporary file that then replaces the old one, so
+   /// a write that's interrupted never leaves a half written file behind
+   async fn write(&self, path: &Path, entry: &Entry) -> Result<()> {
+      tokio::fs::create_dir_all(&self.directory).await.context(error::CacheFailed { path: self.directory.display().to_string() })?;
+
+      let temp = path.with_extension(format!("{}.{}.tmp", std::process::id(), WRITES.fetch_add(1, Ordering::Relaxed)));
+      let written = match tokio::fs::write(&temp, entry.to_text()).await {
+         Ok(()) => tokio::fs::rename(&temp, path).await,
+         Err(e) => Err(e)
+      };
+      if written.is_err() { let _ = tokio::fs::remove_file(&temp).await; }
+      written.context(error::CacheFailed { path: path.display().to_string() })?;
+      Ok(())
+   }
+
+   /// Retrieves daily OCLHV data for a symbol between a start and end date,
+   /// using the cache where possible.
+   pub async fn retrieve_range(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+      // pre-conditions
+      let now = Utc::now();
+      let end = end.unwrap_or(now).min(now);
+      ensure!(end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+      let path = self.path(symbol);
+      let (start_secs, end_secs) = (start.timestamp(), end.timestamp());
+
+      let (mut entry, mut changed) = match self.read(&path).await {
+         Some(entry) => (entry, false),
+         None => {
+            let bars = aggregate_bars(yahoo::load_daily_range(symbol, start_secs, end_secs).await?)?;
+            (Entry { from: start_secs, to: end_secs, bars }, true)
+         }
+      };
+
+      // load anything older than we have
+      if start_secs < entry.from {
+         let mut bars = aggregate_bars(yahoo::load_daily_range(symbol, start_secs, entry.from).await?)?;
+         merge(&mut bars, std::mem::take(&mut entry.bars));
+         entry.bars = bars;
+         entry.from = start_secs;
+         changed = true;
+      }
+
+      // and anything newer, refreshing the last bar since it may have been loaded mid session
+      if end_secs > entry.to {
+         let (newer, to) = match entry.bars.last() {
+            Some(last) => (load_from(symbol, last.timestamp).await?, now.timestamp()),
+            None => (aggregate_bars(yahoo::load_daily_range(symbol, entry.to, end_secs).await?)?, end_secs)
+         };
+         merge(&mut entry.bars, newer);
+         entry.to = to;
+         changed = true;
+      }
+
+      if changed { self.write(&path, &entry).await?; }
+
+      let (from, to) = (start.timestamp_millis(), end.timestamp_millis());
+      Ok(entry.bars.into_iter().filter(|bar| from <= bar.timestamp && bar.timestamp <= to).collect())
+   }
+
+   /// Blocking version of `retrieve_range` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_retrieve_range(&self, symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+      crate::blocking::run(self.retrieve_range(symbol, start, end))
+   }
+}
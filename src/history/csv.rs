@@ -0,0 +1,61 @@
+use snafu::ResultExt;
+use std::io::Write;
+
+use crate::{error, Bar, Result};
+use super::{local_date, AdjustedBar, Dividend, Split};
+
+fn date(timestamp: i64, gmt_offset: i64) -> String {
+   local_date(timestamp, gmt_offset).format("%Y-%m-%d").to_string()
+}
+
+fn bar_row(bar: &Bar, adjusted_close: f64, gmt_offset: i64) -> String {
+   let volume = bar.volume.map(|v| v.to_string()).unwrap_or_default();
+   format!("{},{},{},{},{},{},{}", date(bar.timestamp, gmt_offset), bar.open, bar.high, bar.low, bar.close, adjusted_close, volume)
+}
+
+fn write(mut writer: impl Write, header: &str, rows: impl Iterator<Item = String>) -> Result<()> {
+   writeln!(writer, "{}", header).context(error::ExportFailed)?;
+   for row in rows { writeln!(writer, "{}", row).context(error::ExportFailed)?; }
+   writer.flush().context(error::ExportFailed)?;
+   Ok(())
+}
+
+/// Writes bars as CSV in the same format as Yahoo's downloads, with the
+/// columns `Date,Open,High,Low,Close,Adj Close,Volume`.  Dates follow the
+/// exchange's calendar, going by its `gmt_offset` in seconds (see
+/// [`Meta`](struct.Meta.html)).
+///
+/// Plain bars don't know their adjusted close, so the close is used - use
+/// `adjusted_to_csv` when adjusted closes are needed.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use std::fs::File;
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (bars, meta) = history::retrieve_with_meta("AAPL", Interval::_1y).await.unwrap();
+///    history::to_csv(&bars, meta.gmt_offset, File::create("AAPL.csv").unwrap()).unwrap();
+/// }
+/// ```
+pub fn to_csv(bars: &[Bar], gmt_offset: i64, writer: impl Write) -> Result<()> {
+   write(writer, "Date,Open,High,Low,Close,Adj Close,Volume", bars.iter().map(|bar| bar_row(bar, bar.close, gmt_offset)))
+}
+
+/// Writes bars along with their adjusted closes as CSV in the same format as
+/// Yahoo's downloads.
+pub fn adjusted_to_csv(bars: &[AdjustedBar], gmt_offset: i64, writer: impl Write) -> Result<()> {
+   write(writer, "Date,Open,High,Low,Close,Adj Close,Volume", bars.iter().map(|bar| bar_row(&bar.bar, bar.adjusted_close, gmt_offset)))
+}
+
+/// Writes dividends as CSV in the same format as Yahoo's downloads, with the columns `Date,Dividends`.
+pub fn dividends_to_csv(dividends: &[Dividend], gmt_offset: i64, writer: impl Write) -> Result<()> {
+   write(writer, "Date,Dividends", dividends.iter().map(|dividend| format!("{},{}", date(dividend.timestamp, gmt_offset), dividend.amount)))
+}
+
+/// Writes splits as CSV in the same format as Yahoo's downloads, with the columns `Date,Stock Splits`.  ie. `2020-08-31,4:1`
+pub fn splits_to_csv(splits: &[Split], gmt_offset: i64, writer: impl Write) -> Result<()> {
+   write(writer, "Date,Stock Splits", splits.iter().map(|split| format!("{},{}:{}", date(split.timestamp, gmt_offset), split.numerator, split.denominator)))
+}
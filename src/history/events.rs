@@ -0,0 +1,71 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{yahoo, Timestamped};
+
+/// A dividend paid out per share
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Dividend {
+   pub timestamp: i64,
+   pub amount: f64
+}
+impl Timestamped for Dividend {
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// A capital gain distributed per share - usually by mutual funds
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct CapitalGain {
+   pub timestamp: i64,
+   pub amount: f64
+}
+impl Timestamped for CapitalGain {
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// A stock split.  ie. a 4 for 1 split has a numerator of 4 and a denominator of 1
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Split {
+   pub timestamp: i64,
+   pub numerator: f64,
+   pub denominator: f64
+}
+impl Split {
+   /// The number of new shares for each old share
+   pub fn ratio(&self) -> f64 { self.numerator / self.denominator }
+}
+impl Timestamped for Split {
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// The corporate events that occurred over a period, oldest first
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Events {
+   pub dividends: Vec<Dividend>,
+   pub splits: Vec<Split>,
+   pub capital_gains: Vec<CapitalGain>
+}
+impl Events {
+   pub(crate) fn new(events: Option<yahoo::CorporateEvents>) -> Events {
+      let events = match events {
+         None => return Events::default(),
+         Some(events) => events
+      };
+
+      let mut dividends: Vec<Dividend> = events.dividends.values()
+         .map(|dividend| Dividend { timestamp: dividend.date * 1000, amount: dividend.amount })
+         .collect();
+      dividends.sort_by_key(|dividend| dividend.timestamp);
+
+      let mut splits: Vec<Split> = events.splits.values()
+         .map(|split| Split { timestamp: split.date * 1000, numerator: split.numerator, denominator: split.denominator })
+         .collect();
+      splits.sort_by_key(|split| split.timestamp);
+
+      let mut capital_gains: Vec<CapitalGain> = events.capital_gains.values()
+         .map(|gain| CapitalGain { timestamp: gain.date * 1000, amount: gain.amount })
+         .collect();
+      capital_gains.sort_by_key(|gain| gain.timestamp);
+
+      Events { dividends, splits, capital_gains }
+   }
+}
@@ -0,0 +1,54 @@
+use chrono::{DateTime, FixedOffset, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::symbols::Kind;
+use crate::yahoo;
+use super::TradingPeriods;
+
+/// Information about the symbol that history was loaded for - useful for
+/// labelling prices and converting timestamps to the exchange's local time.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Meta {
+   pub symbol: String,
+
+   /// The currency prices are in, if Yahoo knows it.  ie. `USD`
+   pub currency: Option<String>,
+
+   /// Yahoo's short name for the exchange.  ie. `NMS`
+   pub exchange: String,
+
+   /// The kind of security the symbol represents
+   pub kind: Kind,
+
+   /// The exchange's offset from UTC in seconds
+   pub gmt_offset: i64,
+
+   /// The exchange's timezone abbreviation.  ie. `EDT`
+   pub timezone: String,
+
+   /// The exchange's full timezone name.  ie. `America/New_York`
+   pub exchange_timezone: String,
+
+   pub first_trade_date: DateTime<Utc>,
+
+   /// When the exchange's trading sessions open & close
+   pub trading_periods: TradingPeriods
+}
+impl Meta {
+   pub(crate) fn new(meta: &yahoo::Meta) -> Meta {
+      Meta {
+         symbol: meta.symbol.clone(),
+         currency: meta.currency.clone(),
+         exchange: meta.exchange_name.clone(),
+         kind: Kind::from(meta.instrument_type.as_str()),
+         gmt_offset: meta.gmtoffset,
+         timezone: meta.timezone.clone(),
+         exchange_timezone: meta.exchange_timezone_name.clone(),
+         first_trade_date: meta.first_trade_date,
+         trading_periods: TradingPeriods::new(meta)
+      }
+   }
+
+   /// The exchange's offset from UTC, ready for converting timestamps
+   pub fn offset(&self) -> Option<FixedOffset> { FixedOffset::east_opt(self.gmt_offset as i32) }
+}
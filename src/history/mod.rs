@@ -0,0 +1,653 @@
+use chrono::{DateTime, Duration, NaiveDate, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+use snafu::{ensure, OptionExt};
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{error, yahoo, Bar, Interval, Result, Timestamped, TradingSession};
+
+mod adjust;
+pub use adjust::{adjust_for_splits, total_return_series};
+
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "arrow")]
+pub use arrow::{bars_to_record_batch, dividends_to_record_batch, splits_to_record_batch, write_parquet};
+
+#[cfg(feature = "blocking")]
+pub mod blocking;
+
+mod builder;
+pub use builder::{Builder, History};
+
+#[cfg(feature = "disk-cache")]
+mod cache;
+#[cfg(feature = "disk-cache")]
+pub use cache::DiskCache;
+
+mod csv;
+pub use csv::{adjusted_to_csv, dividends_to_csv, splits_to_csv, to_csv};
+
+mod events;
+pub use events::{CapitalGain, Dividend, Events, Split};
+
+mod meta;
+pub use meta::Meta;
+
+mod partial;
+pub use partial::{fill_missing, PartialBar};
+
+mod periods;
+pub use periods::{DaySessions, TradingPeriod, TradingPeriods};
+
+#[cfg(feature = "polars")]
+mod polars;
+#[cfg(feature = "polars")]
+pub use self::polars::{from_dataframe, to_dataframe};
+
+mod resample;
+pub use resample::{resample, Period};
+
+mod validate;
+pub use validate::{validate, Issue};
+
+/// The lookback window to load bars for - ie. `Range::_1mo` for the last month.
+pub type Range = Interval;
+
+/// The size of each bar of data, independent of the range being loaded.
+///
+/// Like `Interval` the values start with underscores. `m` is for minutes,
+/// `wk` is for weeks and `mo` is for months.
+#[allow(non_camel_case_types)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Granularity { _1m, _2m, _5m, _15m, _30m, _60m, _90m, _1h, _1d, _5d, _1wk, _1mo, _3mo }
+impl Granularity {
+   pub fn is_intraday(&self) -> bool {
+      !matches!(self, Self::_1d | Self::_5d | Self::_1wk | Self::_1mo | Self::_3mo)
+   }
+
+   /// The longest range (in days) Yahoo will serve bars of this size for
+   fn max_days(&self) -> Option<u32> {
+      match self {
+         Self::_1m => Some(7),
+         Self::_60m | Self::_1h => Some(730),
+         _ if self.is_intraday() => Some(60),
+         _ => None
+      }
+   }
+}
+impl fmt::Display for Granularity {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      f.write_str(&format!("{:?}", self)[1..]) // strip the leading underscore
+   }
+}
+
+/// Makes sure Yahoo keeps bars of this granularity for the number of days
+fn check_range(days: Option<u32>, granularity: Granularity, range: impl Fn() -> String) -> Result<()> {
+   if let Some(max) = granularity.max_days() {
+      let too_long = days.is_none_or(|days| days > max);
+      ensure!(!too_long, error::RangeTooLong { range: range(), granularity: granularity.to_string() });
+   }
+   Ok(())
+}
+
+/// The (approximate) number of days covered by a range
+fn range_days(range: Range) -> Option<u32> {
+   match range {
+      Range::_5d => Some(5),
+      Range::_1mo => Some(31),
+      Range::_3mo => Some(92),
+      Range::_6mo => Some(183),
+      Range::_1y | Range::_ytd => Some(365),
+      Range::_2y => Some(730),
+      Range::_5y => Some(5 * 365),
+      Range::_10y => Some(10 * 365),
+      Range::_max => None,
+      _ => Some(1)
+   }
+}
+
+/// The date on the exchange's calendar for a timestamp in milliseconds, going
+/// by its offset from GMT in seconds
+fn local_date(timestamp: i64, gmt_offset: i64) -> NaiveDate {
+   (Utc.timestamp_millis_opt(timestamp).single().unwrap_or_default() + Duration::seconds(gmt_offset)).date_naive()
+}
+
+pub(crate) fn aggregate_bars(data: yahoo::Data) -> Result<Vec<Bar>> {
+   Ok(aggregate_rows(&data)?.into_iter().map(|(_, bar)| bar).collect())
+}
+
+/// Builds the bars along with the index of the Yahoo data each one came from
+fn aggregate_rows(data: &yahoo::Data) -> Result<Vec<(usize, Bar)>> {
+   // skip bars where we have incomplete data
+   Ok(aggregate_partial(data)?.iter().enumerate().filter_map(|(i, bar)| bar.complete().map(|bar| (i, bar))).collect())
+}
+
+/// Builds a bar for every timestamp, even when some of the OHLC values are missing
+fn aggregate_partial(data: &yahoo::Data) -> Result<Vec<PartialBar>> {
+   let timestamps = &data.timestamps;
+   let quotes = &data.indicators.quotes;
+
+   // if we have no timestamps & no quotes we'll assume there is no data
+   if timestamps.is_empty() && quotes.is_empty() { return Ok(Vec::new()); }
+
+   // otherwise see if one is empty and reflects bad data from Yahoo!
+   ensure!(!timestamps.is_empty(), error::MissingData { reason: "no timestamps for OHLCV data" });
+   ensure!(!quotes.is_empty(), error::MissingData { reason: "no OHLCV data" });
+
+   // make sure timestamps lines up with the OHLCV data
+   let quote = &quotes[0];
+   ensure!(timestamps.len() == quote.volumes.len(), error::MissingData { reason: "timestamps do not line up with OHLCV data" });
+   ensure!(timestamps.len() == quote.opens.len(), error::MissingData { reason: "'open' values do not line up the timestamps" });
+   ensure!(timestamps.len() == quote.highs.len(), error::MissingData { reason: "'high' values do not line up the timestamps" });
+   ensure!(timestamps.len() == quote.lows.len(), error::MissingData { reason: "'low' values do not line up the timestamps" });
+   ensure!(timestamps.len() == quote.closes.len(), error::MissingData { reason: "'close' values do not line up the timestamps" });
+
+   Ok(timestamps.iter().enumerate().map(|(i, timestamp)| PartialBar {
+      timestamp: timestamp * 1000,
+      open: quote.opens[i],
+      high: quote.highs[i],
+      low: quote.lows[i],
+      close: quote.closes[i],
+      volume: quote.volumes[i]
+   }).collect())
+}
+
+fn aggregate_adjusted(data: yahoo::Data) -> Result<Vec<AdjustedBar>> {
+   let adjusted = data.indicators.adjusted_closes.first().map(|adj| &adj.values);
+
+   Ok(aggregate_rows(&data)?.into_iter().map(|(i, bar)| {
+      // fall back to the close if Yahoo didn't adjust this bar
+      let adjusted_close = adjusted.and_then(|values| values.get(i).copied().flatten()).unwrap_or(bar.close);
+      AdjustedBar { bar, adjusted_close }
+   }).collect())
+}
+
+fn aggregate_sessions(data: yahoo::Data) -> Result<Vec<SessionBar>> {
+   let periods = TradingPeriods::new(&data.meta);
+   Ok(aggregate_rows(&data)?.into_iter().map(|(_, bar)| {
+      let session = periods.session_at(bar.timestamp);
+      SessionBar { bar, session }
+   }).collect())
+}
+
+/// A bar along with the trading session it occurred in.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionBar {
+   #[serde(with = "crate::remote::BarDef")]
+   pub bar: Bar,
+
+   #[serde(with = "crate::remote::TradingSessionDef")]
+   pub session: TradingSession
+}
+impl Timestamped for SessionBar {
+   fn timestamp_millis(&self) -> i64 { self.bar.timestamp }
+}
+
+/// A bar along with its close adjusted for splits and dividends.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct AdjustedBar {
+   #[serde(with = "crate::remote::BarDef")]
+   pub bar: Bar,
+
+   /// The close adjusted for all later splits & dividends
+   pub adjusted_close: f64
+}
+impl Timestamped for AdjustedBar {
+   fn timestamp_millis(&self) -> i64 { self.bar.timestamp }
+}
+
+/// Retrieves (at most) 6 months worth of OCLHV data for a symbol
+/// ending on the last market close.
+///
+/// # Examples
+///
+/// Get 6 months worth of Apple data:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve("AAPL").await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) => 
+///          for bar in &data {
+///             println!("On {} Apple closed at ${:.2}", bar.datetime().format("%b %e %Y"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve(symbol: &str) -> Result<Vec<Bar>> {
+   aggregate_bars(yahoo::load_daily(symbol, Interval::_6mo).await?)
+}
+
+/// Retrieves a configurable amount of OCLHV data for a symbol
+/// ending on the last market close.  The amount of data returned
+/// might be less than the interval specified if the symbol
+/// is new.
+///
+/// # Examples
+///
+/// Get 5 days worth of Apple data:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_interval("AAPL", Interval::_5d).await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) => 
+///          for bar in &data {
+///             println!("On {} Apple closed at ${:.2}", bar.datetime().format("%b %e %Y"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_interval(symbol: &str, interval: Interval) -> Result<Vec<Bar>> {
+   // pre-conditions
+   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+   aggregate_bars(yahoo::load_daily(symbol, interval).await?)
+}
+
+/// Retrieves a configurable amount of OCLHV data for many symbols at once,
+/// as described in [Many symbols at once](crate#many-symbols-at-once).
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let results = history::retrieve_many(&["AAPL", "MSFT", "GOOG"], Interval::_1y).await;
+///    for (symbol, result) in &results {
+///       match result {
+///          Err(e) => println!("Failed to load {}: {}", symbol, e),
+///          Ok(data) => println!("Loaded {} bars for {}", data.len(), symbol)
+///       }
+///    }
+/// }
+/// ```
+pub async fn retrieve_many(symbols: &[&str], interval: Interval) -> HashMap<String, Result<Vec<Bar>>> {
+   crate::many::load_many(symbols, |symbol| retrieve_interval(symbol, interval)).await
+}
+
+/// Retrieves a configurable amount of daily OCLHV data for a symbol without
+/// skipping the days where Yahoo is missing some of the values.
+///
+/// The other `retrieve_*` functions drop incomplete bars; this returns them
+/// so you can tell that there was trading that day.  Use `fill_missing` to
+/// fill the gaps from the previous close.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let bars = history::retrieve_lenient("AAPL", Interval::_1y).await.unwrap();
+///    let incomplete = bars.iter().filter(|bar| bar.complete().is_none()).count();
+///    println!("{} of {} bars are incomplete", incomplete, bars.len());
+/// }
+/// ```
+pub async fn retrieve_lenient(symbol: &str, interval: Interval) -> Result<Vec<PartialBar>> {
+   // pre-conditions
+   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+   aggregate_partial(&yahoo::load_daily(symbol, interval).await?)
+}
+
+/// Retrieves a configurable amount of daily OCLHV data for a symbol
+/// along with the close adjusted for splits & dividends, which is what
+/// return calculations should be based on.
+///
+/// # Examples
+///
+/// Get a year of Apple's adjusted closes:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_adjusted("AAPL", Interval::_1y).await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("On {} Apple's adjusted close was ${:.2}", bar.datetime().format("%b %e %Y"), bar.adjusted_close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_adjusted(symbol: &str, interval: Interval) -> Result<Vec<AdjustedBar>> {
+   // pre-conditions
+   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+   aggregate_adjusted(yahoo::load_daily(symbol, interval).await?)
+}
+
+/// Retrieves a configurable amount of daily OCLHV data for a symbol along
+/// with information about the symbol - its currency, exchange, timezone, etc.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (bars, meta) = history::retrieve_with_meta("SHOP.TO", Interval::_1mo).await.unwrap();
+///    if let Some(bar) = bars.last() {
+///       println!("{} closed at {:.2} {} on {}", meta.symbol, bar.close, meta.currency.unwrap_or_default(), meta.exchange);
+///    }
+/// }
+/// ```
+pub async fn retrieve_with_meta(symbol: &str, interval: Interval) -> Result<(Vec<Bar>, Meta)> {
+   // pre-conditions
+   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+   let history = Builder::new(symbol).range(interval).fetch().await?;
+   let meta = history.meta.clone();
+   Ok((history.into_bars(), meta))
+}
+
+/// Retrieves a configurable amount of daily OCLHV data for a symbol along
+/// with the dividends, splits & capital gains that occurred over that time.
+///
+/// # Examples
+///
+/// Get the distributions for a fund over the last 5 years:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (_, events) = history::retrieve_with_events("VFIAX", Interval::_5y).await.unwrap();
+///    for gain in &events.capital_gains {
+///       println!("On {} VFIAX distributed ${:.2} of capital gains", gain.datetime().format("%b %e %Y"), gain.amount)
+///    }
+/// }
+/// ```
+pub async fn retrieve_with_events(symbol: &str, interval: Interval) -> Result<(Vec<Bar>, Events)> {
+   // pre-conditions
+   ensure!(!interval.is_intraday(), error::NoIntraday { interval });
+
+   let history = Builder::new(symbol).range(interval).events(true).fetch().await?;
+   let events = history.events.clone();
+   Ok((history.into_bars(), events))
+}
+
+/// Retrieves intraday OCLHV data for a symbol, with one bar for each
+/// `interval` (ie. 5 minutes).
+///
+/// Yahoo only keeps a limited amount of intraday data, so this returns as
+/// much as it will serve: the last 5 days for 1 minute bars and the last
+/// month for everything else.
+///
+/// # Examples
+///
+/// Get 5 minute bars for Apple:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_intraday("AAPL", Interval::_5m).await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("At {} Apple traded at ${:.2}", bar.datetime().format("%b %e %Y %H:%M"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_intraday(symbol: &str, interval: Interval) -> Result<Vec<Bar>> {
+   // pre-conditions
+   ensure!(interval.is_intraday(), error::NotIntraday { interval });
+
+   aggregate_bars(yahoo::load_bars(symbol, intraday_period(interval), interval).await?)
+}
+
+/// The longest period Yahoo serves intraday bars for
+fn intraday_period(interval: Interval) -> Interval {
+   match interval {
+      Interval::_1m => Interval::_5d,
+      _ => Interval::_1mo
+   }
+}
+
+/// Retrieves intraday OCLHV data for a symbol including the pre & post
+/// market sessions, with each bar tagged with the session it occurred in.
+///
+/// The same limits as `retrieve_intraday` apply to the amount of data.
+///
+/// # Examples
+///
+/// Get 5 minute bars for Apple including extended hours:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_extended("AAPL", Interval::_5m).await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("At {} ({:?}) Apple traded at ${:.2}", bar.datetime().format("%b %e %Y %H:%M"), bar.session, bar.bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_extended(symbol: &str, interval: Interval) -> Result<Vec<SessionBar>> {
+   // pre-conditions
+   ensure!(interval.is_intraday(), error::NotIntraday { interval });
+
+   let query = yahoo::chart_query(symbol).range(intraday_period(interval)).interval(interval).include_pre_post(true);
+   aggregate_sessions(yahoo::load_chart(&query).await?)
+}
+
+/// Retrieves OCLHV data for a symbol over a `range` ending now, with each
+/// bar covering `granularity` - just like the Yahoo chart does.
+///
+/// Yahoo limits how far back small bars are available, so asking for
+/// 1 minute bars over more than 7 days, other intraday bars over more than
+/// 60 days or hourly bars over more than 2 years is an error.
+///
+/// # Examples
+///
+/// Get a month of 5 minute bars for Apple:
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Timestamped };
+/// use yahoo_finance::history::{ Granularity, Range };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match history::retrieve_bars("AAPL", Range::_1mo, Granularity::_5m).await {
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("At {} Apple traded at ${:.2}", bar.datetime().format("%b %e %Y %H:%M"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_bars(symbol: &str, range: Range, granularity: Granularity) -> Result<Vec<Bar>> {
+   // pre-conditions
+   check_range(range_days(range), granularity, || range.to_string())?;
+
+   aggregate_bars(yahoo::load_bars(symbol, range, granularity).await?)
+}
+
+/// Retrieves the most recent `n` bars of a given granularity for a symbol -
+/// handy for feeding fixed length indicator windows.
+///
+/// The date range is padded to allow for weekends & holidays, and then
+/// trimmed back to `n` bars.  Fewer bars are returned if the symbol hasn't
+/// traded long enough, or Yahoo doesn't keep that many intraday bars.
+///
+/// # Examples
+///
+/// Get the last 200 daily bars for Apple:
+///
+/// ``` no_run
+/// use yahoo_finance::history;
+/// use yahoo_finance::history::Granularity;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let bars = history::retrieve_last_n("AAPL", 200, Granularity::_1d).await.unwrap();
+///    let average = bars.iter().map(|bar| bar.close).sum::<f64>() / bars.len() as f64;
+///    println!("Apple's 200 day moving average is ${:.2}", average);
+/// }
+/// ```
+pub async fn retrieve_last_n(symbol: &str, n: usize, granularity: Granularity) -> Result<Vec<Bar>> {
+   let n64 = n as i64;
+
+   // bars per trading day for intraday granularity, based on a 6.5 hour regular session
+   let intraday = |minutes: i64| (n64 * minutes / 390 + 1) * 7 / 5 + 4;
+
+   let mut days = match granularity {
+      Granularity::_1m => intraday(1),
+      Granularity::_2m => intraday(2),
+      Granularity::_5m => intraday(5),
+      Granularity::_15m => intraday(15),
+      Granularity::_30m => intraday(30),
+      Granularity::_60m | Granularity::_1h => intraday(60),
+      Granularity::_90m => intraday(90),
+      Granularity::_1d => n64 * 7 / 5 + n64 / 10 + 10,
+      Granularity::_5d | Granularity::_1wk => n64 * 7 + 14,
+      Granularity::_1mo => n64 * 31 + 62,
+      Granularity::_3mo => n64 * 92 + 184
+   };
+   if let Some(max) = granularity.max_days() { days = days.min(max as i64 - 1); }
+
+   let now = Utc::now();
+   let mut bars = Builder::new(symbol).between(now - Duration::days(days), now).granularity(granularity).fetch().await?.into_bars();
+
+   let excess = bars.len().saturating_sub(n);
+   bars.drain(..excess);
+   Ok(bars)
+}
+
+/// Retrieves OCLHV data for a symbol between a start and end date.
+///
+/// # Examples
+///
+/// Get 5 days worth of Apple data:
+///
+/// ``` no_run
+/// use chrono::{Duration, TimeZone, Utc};
+/// use yahoo_finance::{ history, Timestamped };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let now = Utc::now();
+///    match history::retrieve_range("AAPL", now - Duration::days(30), Some(now - Duration::days(10))).await {
+///       Err(e) => println!("Failed to call Yahoo {:?}", e),
+///       Ok(data) =>
+///          for bar in &data {
+///             println!("On {} Apple closed at ${:.2}", bar.datetime().format("%b %e %Y"), bar.close)
+///          }
+///    }
+/// }
+/// ```
+pub async fn retrieve_range(symbol: &str, start: DateTime<Utc>, end: Option<DateTime<Utc>>) -> Result<Vec<Bar>> {
+   // pre-conditions
+   let _end = end.unwrap_or_else(Utc::now);
+   ensure!(_end.signed_duration_since(start).num_seconds() > 0, error::InvalidStartDate);
+
+   aggregate_bars(yahoo::load_daily_range(symbol, start.timestamp(), _end.timestamp()).await?)
+}
+
+/// Merges freshly retrieved bars into a cached series.
+///
+/// The last cached bar may have been retrieved while its period was still
+/// trading, so any cached bars at or after the first fresh bar are replaced
+/// by the fresh ones.  The result is ordered and has no duplicate timestamps.
+pub fn merge(cached: &mut Vec<Bar>, fresh: Vec<Bar>) {
+   if let Some(first) = fresh.iter().map(|bar| bar.timestamp).min() {
+      cached.retain(|bar| bar.timestamp < first);
+   }
+   cached.extend(fresh);
+   cached.sort_by_key(|bar| bar.timestamp);
+   cached.dedup_by_key(|bar| bar.timestamp);
+}
+
+/// Updates a cached series of daily bars with anything newer from Yahoo,
+/// returning the number of bars that were added.
+///
+/// Only the bars since the last cached bar are requested - the last cached
+/// bar is requested again since it may have changed.  An empty cache is
+/// filled with the same data as `retrieve`.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let mut bars = history::retrieve("AAPL").await.unwrap();
+///
+///    // ... some time later
+///    let added = history::update_since("AAPL", &mut bars).await.unwrap();
+///    println!("{} new bars for Apple", added);
+/// }
+/// ```
+pub async fn update_since(symbol: &str, cached: &mut Vec<Bar>) -> Result<usize> {
+   let before = cached.len();
+
+   let fresh = match cached.iter().map(|bar| bar.timestamp).max() {
+      None => retrieve(symbol).await?,
+      Some(last) => load_from(symbol, last).await?
+   };
+
+   merge(cached, fresh);
+   Ok(cached.len().saturating_sub(before))
+}
+
+/// Loads the daily bars from a timestamp (in milliseconds) up until now - including the bar at the timestamp
+async fn load_from(symbol: &str, timestamp: i64) -> Result<Vec<Bar>> {
+   let start = Utc.timestamp_millis_opt(timestamp).single().context(error::InternalLogic { reason: "bar timestamp out of range" })?;
+   let now = Utc::now();
+   if start >= now { return Ok(Vec::new()); }
+
+   let mut bars = aggregate_bars(yahoo::load_daily_range(symbol, start.timestamp(), now.timestamp()).await?)?;
+   bars.sort_by_key(|bar| bar.timestamp);
+   bars.dedup_by_key(|bar| bar.timestamp);
+   Ok(bars)
+}
+
+/// Retrieves the daily bars for a symbol that are newer than a timestamp
+/// (in milliseconds) - ie. the last bar in a local store.
+///
+/// Only the missing range is requested from Yahoo, and the bars returned
+/// are ordered with no duplicates.  Note that the last stored bar is not
+/// returned even if it has changed - use `update_since` to refresh it too.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let last_stored = 1588339800000;
+///    for bar in history::retrieve_since("AAPL", last_stored).await.unwrap() {
+///       println!("Storing bar for {}", bar.timestamp);
+///    }
+/// }
+/// ```
+pub async fn retrieve_since(symbol: &str, last_timestamp: i64) -> Result<Vec<Bar>> {
+   let mut bars = load_from(symbol, last_timestamp).await?;
+   bars.retain(|bar| bar.timestamp > last_timestamp);
+   Ok(bars)
+}
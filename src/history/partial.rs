@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{Bar, Timestamped};
+
+/// A bar where Yahoo may be missing some of the values
+#[derive(Clone, Copy, Debug, Serialize, Deserialize)]
+pub struct PartialBar {
+   pub timestamp: i64,
+   pub open: Option<f64>,
+   pub high: Option<f64>,
+   pub low: Option<f64>,
+   pub close: Option<f64>,
+   pub volume: Option<u64>
+}
+impl PartialBar {
+   /// The bar, if none of the open, high, low or close are missing
+   pub fn complete(&self) -> Option<Bar> {
+      Some(Bar {
+         timestamp: self.timestamp,
+         open: self.open?,
+         high: self.high?,
+         low: self.low?,
+         close: self.close?,
+         volume: self.volume
+      })
+   }
+
+   /// The bar with any missing values filled in from a previous close
+   pub fn or_previous(&self, previous_close: f64) -> Bar {
+      Bar {
+         timestamp: self.timestamp,
+         open: self.open.unwrap_or(previous_close),
+         high: self.high.unwrap_or(previous_close),
+         low: self.low.unwrap_or(previous_close),
+         close: self.close.unwrap_or(previous_close),
+         volume: self.volume
+      }
+   }
+}
+impl Timestamped for PartialBar {
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+
+/// Turns partial bars into bars, filling any missing values from the
+/// previous close.  Incomplete bars before the first close are skipped since
+/// there is nothing to fill them with.
+pub fn fill_missing(bars: &[PartialBar]) -> Vec<Bar> {
+   let mut result: Vec<Bar> = Vec::with_capacity(bars.len());
+
+   for bar in bars {
+      match (bar.complete(), result.last()) {
+         (Some(complete), _) => result.push(complete),
+         (None, Some(previous)) => {
+            let filled = bar.or_previous(previous.close);
+            result.push(filled);
+         },
+         (None, None) => {}
+      }
+   }
+   result
+}
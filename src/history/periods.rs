@@ -0,0 +1,102 @@
+use chrono::{DateTime, TimeZone, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{yahoo, TradingSession};
+
+/// When a trading session opens & closes on an exchange
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct TradingPeriod {
+   /// The exchange's timezone abbreviation.  ie. `EDT`
+   pub timezone: String,
+
+   pub start: DateTime<Utc>,
+   pub end: DateTime<Utc>,
+
+   /// The exchange's offset from UTC in seconds
+   pub gmt_offset: i64
+}
+impl TradingPeriod {
+   fn new(period: &yahoo::Period) -> TradingPeriod {
+      TradingPeriod {
+         timezone: period.timezone.clone(),
+         start: Utc.timestamp_opt(period.start, 0).single().unwrap_or_default(),
+         end: Utc.timestamp_opt(period.end, 0).single().unwrap_or_default(),
+         gmt_offset: period.gmtoffset
+      }
+   }
+
+   /// Whether a timestamp (in milliseconds) falls within this period
+   pub fn contains(&self, timestamp: i64) -> bool {
+      self.start.timestamp_millis() <= timestamp && timestamp < self.end.timestamp_millis()
+   }
+}
+
+/// The pre market, regular & after hours sessions for a single day
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct DaySessions {
+   pub pre: TradingPeriod,
+   pub regular: TradingPeriod,
+   pub post: TradingPeriod
+}
+
+/// The session times for an exchange, so intraday bars can be lined up with
+/// the sessions without hard coding exchange hours.
+#[derive(Clone, Debug, Default, Serialize, Deserialize)]
+pub struct TradingPeriods {
+   /// The sessions for the current (or most recent) trading day
+   pub current: Option<DaySessions>,
+
+   /// The pre market sessions for each day loaded - only filled in when pre & post market data was asked for
+   pub pre: Vec<TradingPeriod>,
+
+   /// The regular sessions for each day loaded (intraday data only)
+   pub regular: Vec<TradingPeriod>,
+
+   /// The after hours sessions for each day loaded - only filled in when pre & post market data was asked for
+   pub post: Vec<TradingPeriod>
+}
+impl TradingPeriods {
+   pub(crate) fn new(meta: &yahoo::Meta) -> TradingPeriods {
+      let flatten = |periods: &[Vec<yahoo::Period>]| periods.iter().flatten().map(TradingPeriod::new).collect();
+
+      let current = meta.current_trading_period.as_ref().map(|current| DaySessions {
+         pre: TradingPeriod::new(&current.pre),
+         regular: TradingPeriod::new(&current.regular),
+         post: TradingPeriod::new(&current.post)
+      });
+
+      match &meta.trading_periods {
+         None => TradingPeriods { current, ..TradingPeriods::default() },
+         Some(yahoo::TradingPeriods::Regular(regular)) => TradingPeriods { current, regular: flatten(regular), ..TradingPeriods::default() },
+         Some(yahoo::TradingPeriods::Extended(periods)) => TradingPeriods {
+            current,
+            pre: flatten(&periods.pre),
+            regular: flatten(&periods.regular),
+            post: flatten(&periods.post)
+         }
+      }
+   }
+
+   /// Works out which trading session a timestamp (in milliseconds) belongs to.
+   ///
+   /// The per-day periods are used when they're available, otherwise the time
+   /// of day is compared with the current day's sessions.
+   pub fn session_at(&self, timestamp: i64) -> TradingSession {
+      if !self.pre.is_empty() || !self.post.is_empty() {
+         if self.pre.iter().any(|period| period.contains(timestamp)) { return TradingSession::PreMarket; }
+         if self.post.iter().any(|period| period.contains(timestamp)) { return TradingSession::AfterHours; }
+         return TradingSession::Regular;
+      }
+
+      match &self.current {
+         None => TradingSession::Regular,
+         Some(current) => {
+            let open = current.regular.start.timestamp_millis();
+            let offset = (timestamp - open).rem_euclid(86_400_000);
+            if offset < current.regular.end.timestamp_millis() - open { TradingSession::Regular }
+            else if offset < current.post.end.timestamp_millis() - open { TradingSession::AfterHours }
+            else { TradingSession::PreMarket }
+         }
+      }
+   }
+}
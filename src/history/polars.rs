@@ -0,0 +1,66 @@
+use polars::prelude::*;
+
+use crate::{error, Bar, Result};
+
+fn failed<T>(reason: impl ToString) -> Result<T> {
+   Ok(error::PolarsFailed { reason: reason.to_string() }.fail()?)
+}
+
+/// Converts bars into a Polars data frame with `timestamp`, `open`, `high`,
+/// `low`, `close` & `volume` columns.  Timestamps are UTC datetimes.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::history;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let bars = history::retrieve("AAPL").await.unwrap();
+///    let frame = history::to_dataframe(&bars).unwrap();
+///    println!("{}", frame);
+/// }
+/// ```
+pub fn to_dataframe(bars: &[Bar]) -> Result<DataFrame> {
+   let timestamps = Series::new("timestamp".into(), bars.iter().map(|bar| bar.timestamp).collect::<Vec<i64>>())
+      .cast(&DataType::Datetime(TimeUnit::Milliseconds, Some("UTC".into())))
+      .or_else(failed)?;
+
+   DataFrame::new(vec![
+      timestamps.into(),
+      Column::new("open".into(), bars.iter().map(|bar| bar.open).collect::<Vec<f64>>()),
+      Column::new("high".into(), bars.iter().map(|bar| bar.high).collect::<Vec<f64>>()),
+      Column::new("low".into(), bars.iter().map(|bar| bar.low).collect::<Vec<f64>>()),
+      Column::new("close".into(), bars.iter().map(|bar| bar.close).collect::<Vec<f64>>()),
+      Column::new("volume".into(), bars.iter().map(|bar| bar.volume).collect::<Vec<Option<u64>>>())
+   ]).or_else(failed)
+}
+
+/// Converts a data frame with the columns made by `to_dataframe` back into
+/// bars.  Rows with a missing timestamp or price are skipped.
+pub fn from_dataframe(frame: &DataFrame) -> Result<Vec<Bar>> {
+   let column = |name: &str, kind: &DataType| -> Result<Series> {
+      let column = frame.column(name).or_else(failed)?;
+      column.as_materialized_series().cast(kind).or_else(failed)
+   };
+
+   let timestamps = column("timestamp", &DataType::Int64)?;
+   let opens = column("open", &DataType::Float64)?;
+   let highs = column("high", &DataType::Float64)?;
+   let lows = column("low", &DataType::Float64)?;
+   let closes = column("close", &DataType::Float64)?;
+   let volumes = column("volume", &DataType::UInt64)?;
+
+   let timestamps = timestamps.i64().or_else(failed)?;
+   let (opens, highs, lows, closes) = (opens.f64().or_else(failed)?, highs.f64().or_else(failed)?, lows.f64().or_else(failed)?, closes.f64().or_else(failed)?);
+   let volumes = volumes.u64().or_else(failed)?;
+
+   Ok((0..frame.height()).filter_map(|i| Some(Bar {
+      timestamp: timestamps.get(i)?,
+      open: opens.get(i)?,
+      high: highs.get(i)?,
+      low: lows.get(i)?,
+      close: closes.get(i)?,
+      volume: volumes.get(i)
+   })).collect())
+}
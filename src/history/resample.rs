@@ -0,0 +1,74 @@
+use chrono::Datelike;
+use serde::{Deserialize, Serialize};
+
+use crate::Bar;
+use super::local_date;
+
+/// The calendar periods bars can be resampled into
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Period {
+   /// Monday to Sunday weeks
+   Weekly,
+
+   /// Calendar months
+   Monthly
+}
+impl Period {
+   /// A key that is the same for every timestamp (in milliseconds) in the
+   /// same period, going by the date at the exchange
+   fn key(&self, timestamp: i64, gmt_offset: i64) -> (i32, u32) {
+      let date = local_date(timestamp, gmt_offset);
+      match self {
+         Period::Weekly => (date.iso_week().year(), date.iso_week().week()),
+         Period::Monthly => (date.year(), date.month())
+      }
+   }
+}
+
+/// Aggregates bars (ie. daily ones) into larger calendar periods.
+///
+/// Each resampled bar takes the open of the first bar in the period, the
+/// highest high, the lowest low, the close of the last bar and the total
+/// volume.  It is timestamped with the first bar in the period.  Periods
+/// follow the exchange's calendar, going by its `gmt_offset` in seconds (see
+/// [`Meta`](struct.Meta.html)), and the bars are expected to be ordered
+/// oldest first.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+/// use yahoo_finance::history::Period;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (daily, meta) = history::retrieve_with_meta("AAPL", Interval::_1y).await.unwrap();
+///    let weekly = history::resample(&daily, Period::Weekly, meta.gmt_offset);
+///    println!("{} daily bars make {} weekly ones", daily.len(), weekly.len());
+/// }
+/// ```
+pub fn resample(bars: &[Bar], period: Period, gmt_offset: i64) -> Vec<Bar> {
+   let mut result: Vec<Bar> = Vec::new();
+   let mut current = None;
+
+   for bar in bars {
+      let key = period.key(bar.timestamp, gmt_offset);
+
+      match result.last_mut() {
+         Some(last) if current == Some(key) => {
+            last.high = last.high.max(bar.high);
+            last.low = last.low.min(bar.low);
+            last.close = bar.close;
+            last.volume = match (last.volume, bar.volume) {
+               (None, None) => None,
+               (a, b) => Some(a.unwrap_or(0) + b.unwrap_or(0))
+            };
+         },
+         _ => {
+            current = Some(key);
+            result.push(*bar);
+         }
+      }
+   }
+   result
+}
@@ -0,0 +1,103 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use serde::{Deserialize, Serialize};
+
+use crate::Bar;
+use super::{local_date, Granularity};
+
+/// A problem found in a series of bars
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Issue {
+   /// More than one bar has the same timestamp
+   Duplicate { timestamp: i64 },
+
+   /// A bar is older than the bar before it
+   OutOfOrder { timestamp: i64, previous: i64 },
+
+   /// Bars are missing between two timestamps.  Daily gaps only count weekdays, so market holidays are reported too.
+   Gap { after: i64, before: i64, missing: usize },
+
+   /// A bar had no volume traded
+   ZeroVolume { timestamp: i64 }
+}
+
+/// The number of weekdays strictly between two dates
+fn weekdays_between(start: NaiveDate, end: NaiveDate) -> usize {
+   let mut count = 0;
+   let mut day = start + Duration::days(1);
+   while day < end {
+      if day.weekday() != Weekday::Sat && day.weekday() != Weekday::Sun { count += 1; }
+      day += Duration::days(1);
+   }
+   count
+}
+
+/// The number of bars expected to be missing between two consecutive bars
+fn missing(granularity: Granularity, previous: i64, timestamp: i64, gmt_offset: i64) -> usize {
+   let (from, to) = (local_date(previous, gmt_offset), local_date(timestamp, gmt_offset));
+   let minutes = |m: i64| {
+      // overnight & weekend gaps are expected for intraday bars
+      if from != to { return 0; }
+      ((timestamp - previous) / (m * 60_000) - 1).max(0) as usize
+   };
+   let months = |m: i32| {
+      let elapsed = (to.year() - from.year()) * 12 + to.month() as i32 - from.month() as i32;
+      (elapsed / m - 1).max(0) as usize
+   };
+
+   match granularity {
+      Granularity::_1m => minutes(1),
+      Granularity::_2m => minutes(2),
+      Granularity::_5m => minutes(5),
+      Granularity::_15m => minutes(15),
+      Granularity::_30m => minutes(30),
+      Granularity::_60m | Granularity::_1h => minutes(60),
+      Granularity::_90m => minutes(90),
+      Granularity::_1d => weekdays_between(from, to),
+      Granularity::_5d | Granularity::_1wk => ((to - from).num_days() / 7 - 1).max(0) as usize,
+      Granularity::_1mo => months(1),
+      Granularity::_3mo => months(3)
+   }
+}
+
+/// Checks a series of bars for problems - duplicate timestamps, bars out of
+/// order, missing bars & bars without any volume.
+///
+/// The bars are expected to be ordered oldest first with one bar for each
+/// `granularity`.  Days follow the exchange's calendar, going by its
+/// `gmt_offset` in seconds (see [`Meta`](struct.Meta.html)).  An empty result
+/// means no problems were found.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::{ history, Interval };
+/// use yahoo_finance::history::Granularity;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let (bars, meta) = history::retrieve_with_meta("AAPL", Interval::_1y).await.unwrap();
+///    for issue in history::validate(&bars, Granularity::_1d, meta.gmt_offset) {
+///       println!("{:?}", issue);
+///    }
+/// }
+/// ```
+pub fn validate(bars: &[Bar], granularity: Granularity, gmt_offset: i64) -> Vec<Issue> {
+   let mut issues = Vec::new();
+
+   for (i, bar) in bars.iter().enumerate() {
+      if bar.volume == Some(0) { issues.push(Issue::ZeroVolume { timestamp: bar.timestamp }); }
+
+      if i == 0 { continue; }
+      let previous = bars[i - 1].timestamp;
+
+      if bar.timestamp == previous {
+         issues.push(Issue::Duplicate { timestamp: bar.timestamp });
+      } else if bar.timestamp < previous {
+         issues.push(Issue::OutOfOrder { timestamp: bar.timestamp, previous });
+      } else {
+         let missing = missing(granularity, previous, bar.timestamp, gmt_offset);
+         if missing > 0 { issues.push(Issue::Gap { after: previous, before: bar.timestamp, missing }); }
+      }
+   }
+   issues
+}
@@ -0,0 +1,191 @@
+use chrono::{ NaiveDate, TimeZone, Utc };
+use serde::{Deserialize, Serialize};
+use snafu::{ ensure, OptionExt };
+use std::cmp::Reverse;
+
+use crate::{ error, yahoo, Result };
+
+/// The quote summary modules with the insiders & what they've traded
+const INSIDER_MODULES: &[&str] = &["insiderHolders", "insiderTransactions"];
+
+fn date(timestamp: Option<i64>) -> Option<NaiveDate> {
+   Utc.timestamp_opt(timestamp?, 0).single().map(|date| date.date_naive())
+}
+
+/// Yahoo sends empty text rather than leaving it out
+fn text(text: Option<String>) -> Option<String> {
+   text.filter(|text| !text.is_empty())
+}
+
+/// An institution's holding in a symbol, as of its last report.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Holder {
+   /// The institution.  ie. 'Vanguard Group Inc'
+   pub organization: String,
+
+   /// The day the holding was reported as of
+   pub report_date: Option<NaiveDate>,
+
+   pub shares: Option<u64>,
+
+   /// What the shares were worth as of the report
+   pub value: Option<f64>,
+
+   /// The fraction of the shares outstanding held - ie. 0.08 is 8%
+   pub percent_held: Option<f64>,
+
+   /// The change in the shares held since the report before, as a fraction
+   pub percent_change: Option<f64>
+}
+
+pub(crate) fn institutional_holders(data: yahoo::QuoteSummary) -> Result<Vec<Holder>> {
+   let ownership = data.institution_ownership.context(error::MissingData { reason: "no institution ownership" })?;
+
+   let mut holders: Vec<Holder> = ownership.ownership_list.into_iter()
+      .map(|holder| Holder {
+         organization: holder.organization,
+         report_date: date(holder.report_date),
+         shares: holder.position,
+         value: holder.value,
+         percent_held: holder.pct_held,
+         percent_change: holder.pct_change
+      })
+      .collect();
+   holders.sort_by_key(|holder| Reverse(holder.shares));
+   Ok(holders)
+}
+
+/// Retrieves the top institutional holders of a symbol, largest first.
+/// Yahoo usually has the top ten.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::holders;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for holder in holders::institutional("AAPL").await.unwrap() {
+///       println!("{} holds {:.2}% of Apple", holder.organization, holder.percent_held.unwrap_or_default() * 100.0);
+///    }
+/// }
+/// ```
+pub async fn institutional(symbol: &str) -> Result<Vec<Holder>> {
+   institutional_holders(yahoo::load_quote_summary(symbol, &["institutionOwnership"]).await?)
+}
+
+/// Blocking version of `institutional` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_institutional(symbol: &str) -> Result<Vec<Holder>> { crate::blocking::run(institutional(symbol)) }
+
+/// An officer or director of a company & the shares they hold.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Insider {
+   /// ie. 'COOK TIMOTHY D'
+   pub name: String,
+
+   /// Their role at the company.  ie. 'Chief Executive Officer'
+   pub relation: Option<String>,
+
+   /// What their latest transaction was.  ie. 'Sale'
+   pub latest_transaction: Option<String>,
+   pub latest_transaction_date: Option<NaiveDate>,
+
+   /// The shares they hold themselves & when that was last reported
+   pub shares_direct: Option<u64>,
+   pub shares_direct_date: Option<NaiveDate>,
+
+   /// The shares they hold through trusts, family & so on & when that was last reported
+   pub shares_indirect: Option<u64>,
+   pub shares_indirect_date: Option<NaiveDate>
+}
+
+/// A purchase, sale, grant, etc of a company's shares by one of its insiders.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct InsiderTransaction {
+   pub date: Option<NaiveDate>,
+
+   /// The insider who filed the transaction.  ie. 'COOK TIMOTHY D'
+   pub name: String,
+
+   /// Their role at the company.  ie. 'Chief Executive Officer'
+   pub relation: Option<String>,
+
+   /// What the transaction was.  ie. 'Sale at price 187.44 - 192.09 per share.'
+   /// Yahoo has none for grants & gifts.
+   pub description: Option<String>,
+   pub shares: Option<u64>,
+   pub value: Option<f64>,
+
+   /// Whether the insider holds the shares themselves rather than indirectly
+   pub direct: bool
+}
+
+/// A company's insiders along with the transactions they've made.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Insiders {
+   /// The insiders & their current positions
+   pub roster: Vec<Insider>,
+
+   /// The insiders' recent transactions, newest first
+   pub transactions: Vec<InsiderTransaction>
+}
+
+pub(crate) fn insider_holders(data: yahoo::QuoteSummary) -> Result<Insiders> {
+   ensure!(data.insider_holders.is_some() || data.insider_transactions.is_some(), error::MissingData { reason: "no insiders" });
+
+   let roster = data.insider_holders.map(|insiders| insiders.holders).unwrap_or_default().into_iter()
+      .map(|insider| Insider {
+         name: insider.name,
+         relation: text(insider.relation),
+         latest_transaction: text(insider.transaction_description),
+         latest_transaction_date: date(insider.latest_trans_date),
+         shares_direct: insider.position_direct,
+         shares_direct_date: date(insider.position_direct_date),
+         shares_indirect: insider.position_indirect,
+         shares_indirect_date: date(insider.position_indirect_date)
+      })
+      .collect();
+
+   let mut transactions: Vec<InsiderTransaction> = data.insider_transactions.map(|transactions| transactions.transactions).unwrap_or_default().into_iter()
+      .map(|transaction| InsiderTransaction {
+         date: date(transaction.start_date),
+         name: transaction.filer_name,
+         relation: text(transaction.filer_relation),
+         description: text(transaction.transaction_text),
+         shares: transaction.shares,
+         value: transaction.value,
+         direct: transaction.ownership.as_deref() != Some("I")
+      })
+      .collect();
+   transactions.sort_by_key(|transaction| Reverse(transaction.date));
+
+   Ok(Insiders { roster, transactions })
+}
+
+/// Retrieves a company's insiders - its officers & directors - with their
+/// positions, along with the transactions they've recently made.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::holders;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let insiders = holders::insiders("AAPL").await.unwrap();
+///    for insider in &insiders.roster {
+///       println!("{} holds {} shares", insider.name, insider.shares_direct.unwrap_or_default());
+///    }
+///    for transaction in &insiders.transactions {
+///       println!("{} - {}", transaction.name, transaction.description.as_deref().unwrap_or("no description"));
+///    }
+/// }
+/// ```
+pub async fn insiders(symbol: &str) -> Result<Insiders> {
+   insider_holders(yahoo::load_quote_summary(symbol, INSIDER_MODULES).await?)
+}
+
+/// Blocking version of `insiders` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_insiders(symbol: &str) -> Result<Insiders> { crate::blocking::run(insiders(symbol)) }
@@ -0,0 +1,144 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{ yahoo, Result };
+
+/// The direction Yahoo thinks a symbol is heading
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Direction {
+   Bullish,
+   Bearish,
+   Neutral
+}
+impl From<&str> for Direction {
+   fn from(value: &str) -> Direction {
+      match value {
+         "Bullish" => Direction::Bullish,
+         "Bearish" => Direction::Bearish,
+         _ => Direction::Neutral
+      }
+   }
+}
+
+/// A technical outlook for a single time horizon.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Outlook {
+   /// The direction the technical events point to
+   pub direction: Direction,
+
+   /// The strength of the evidence - higher is stronger.  ie. 3
+   pub score: Option<u32>,
+
+   /// A description of the score.  ie. 'Strong Bullish Evidence'
+   pub score_description: Option<String>,
+
+   /// A description of the recent events.  ie. 'Recent bullish events outweigh bearish events.'
+   pub description: Option<String>
+}
+impl Outlook {
+   fn new(data: yahoo::Outlook) -> Outlook {
+      Outlook {
+         direction: data.direction.as_deref().map(Direction::from).unwrap_or(Direction::Neutral),
+         score: data.score,
+         score_description: data.score_description,
+         description: data.state_description
+      }
+   }
+}
+
+/// Key technical price levels.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Levels {
+   pub support: Option<f64>,
+   pub resistance: Option<f64>,
+   pub stop_loss: Option<f64>
+}
+
+/// Valuation commentary for a symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Valuation {
+   /// A short description of the valuation.  ie. 'Overvalued'
+   pub description: Option<String>,
+
+   /// The discount (or premium) to fair value.  ie. '-11%'
+   pub discount: Option<String>,
+
+   /// The relative value compared to peers.  ie. 'Premium'
+   pub relative_value: Option<String>
+}
+
+/// The technical insights Yahoo computes for a symbol.
+///
+/// Yahoo does not have insights for every symbol so each part is optional.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Insights {
+   pub symbol: String,
+
+   /// The short term technical outlook (2-6 weeks)
+   pub short_term: Option<Outlook>,
+
+   /// The intermediate term technical outlook (6 weeks - 9 months)
+   pub intermediate_term: Option<Outlook>,
+
+   /// The long term technical outlook (9+ months)
+   pub long_term: Option<Outlook>,
+
+   /// Support & resistance levels
+   pub levels: Option<Levels>,
+
+   /// Valuation commentary
+   pub valuation: Option<Valuation>
+}
+impl Insights {
+   /// Loads the technical insights for a symbol.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::Insights;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let insights = Insights::load("AAPL").await.unwrap();
+   ///    if let Some(outlook) = insights.short_term {
+   ///       println!("Apple looks {:?} in the short term", outlook.direction);
+   ///    }
+   /// }
+   /// ```
+   pub async fn load(symbol: &str) -> Result<Insights> {
+      Ok(Self::new(yahoo::load_insights(symbol).await?))
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Insights> { crate::blocking::run(Self::load(symbol)) }
+
+   pub(crate) fn new(data: yahoo::Insights) -> Insights {
+      let info = data.instrument_info;
+
+      let (events, technicals, valuation) = match info {
+         Some(info) => (info.technical_events, info.key_technicals, info.valuation),
+         None => (None, None, None)
+      };
+      let (short_term, intermediate_term, long_term) = match events {
+         Some(events) => (
+            events.short_term_outlook.map(Outlook::new),
+            events.intermediate_term_outlook.map(Outlook::new),
+            events.long_term_outlook.map(Outlook::new)
+         ),
+         None => (None, None, None)
+      };
+
+      Insights {
+         symbol: data.symbol,
+         short_term,
+         intermediate_term,
+         long_term,
+         levels: technicals.map(|t| Levels { support: t.support, resistance: t.resistance, stop_loss: t.stop_loss }),
+         valuation: valuation.map(|v| Valuation {
+            description: v.description,
+            discount: v.discount,
+            relative_value: v.relative_value
+         })
+      }
+   }
+}
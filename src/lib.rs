@@ -4,10 +4,42 @@
 //! that information out of Yahoo for use in financial applications.
 //!
 //! Currently `yahoo_finance` provides:
-//! * Historical quote information [OHCL Data](https://en.wikipedia.org/wiki/Open-high-low-close_chart) + volume
+//! * Historical quote information [OHCL Data](https://en.wikipedia.org/wiki/Open-high-low-close_chart) + volume, daily or intraday
 //! * Relatively real-time quote informaton with comparible performance to the real-time updates on their website
+//! * Alerts when streamed prices cross a level, move by a percentage or trade a spike in volume
 //! * Company profile information including address, sector, industry, etc.
+//! * Key statistics like P/E ratios, EPS, beta, share float & short interest
+//! * Earnings history against analyst estimates, forward EPS & revenue estimates and upcoming earnings dates
+//! * Analyst price targets & recommendations - the consensus target, monthly counts of buy, hold & sell ratings and the upgrade / downgrade history
+//! * Financial health figures - cash, debt, revenue, margins & cash flow
+//! * Quote overviews with the day & 52 week ranges, average volumes and dividends
+//! * Annual & quarterly income statements and balance sheets - revenue, net income, EPS, assets, liabilities, cash & debt, along with any of Yahoo's other fundamentals over time
+//! * The top institutional holders of a symbol, along with its insiders & their transactions
+//! * Mutual fund & ETF asset allocations
+//! * US treasury yields without needing to know Yahoo's index symbols
+//! * Commodity futures quotes, history & streaming
+//! * Parsers for saved Yahoo responses, so archived data can be used without network calls
+//! * Cheap lookups of the kind of security a symbol represents & symbol validation
+//! * Technical outlooks, support / resistance levels and valuation commentary
+//! * Market movers (gainers, losers & most active) by region and exchange, including extended hours
+//! * Falling back to your own data source when Yahoo is rate limiting or unavailable
+//! * An optional response cache so frequent refreshes don't trip Yahoo's rate limits
 //! 
+//! ## Many symbols at once
+//!
+//! The `*_many` functions (ie. `history::retrieve_many`) load the same thing
+//! for a list of symbols.  Calls to Yahoo are made concurrently, but no more
+//! than 10 at a time (see `config::set_max_concurrent`), and each symbol gets
+//! its own result so a failure for one symbol doesn't stop the rest from
+//! loading.
+//!
+//! ## Missing figures & fractions
+//!
+//! Yahoo leaves out whatever doesn't apply to a symbol (ie. P/E ratios for a
+//! company without earnings) so the figures in `Statistics`, `Summary` &
+//! `Financials` are optional.  Fractions are as Yahoo sends them - ie. 0.25
+//! is 25%.
+//!
 //! ## Quick Examples
 //!
 //! To retrieve the intraday high for the last 3 months of Apple you can use something like:
@@ -39,7 +71,10 @@
 //!
 //!    streamer.stream().await
 //!       .for_each(|quote| {
-//!          println!("At {}, {} is trading for ${}", quote.timestamp, quote.symbol, quote.price);
+//!          match quote {
+//!             Ok(quote) => println!("At {}, {} is trading for ${} ({:+.2}%)", quote.timestamp, quote.symbol, quote.price, quote.change_percent.unwrap_or_default()),
+//!             Err(e) => eprintln!("{}", e)
+//!          }
 //!          future::ready(())
 //!       })
 //!       .await;
@@ -60,11 +95,14 @@
 //! }
 //! ```
 
+#[cfg(all(feature = "native-tls", feature = "rustls"))]
+compile_error!("the `native-tls` & `rustls` features can't be used together - pick one");
+
 // make sure our macros are all loaded
 #[macro_use]
 mod macros;
 
-pub use market_finance::{Bar, Interval, Quote, Timestamped, TradingSession};
+pub use market_finance::{Bar, Interval, Timestamped, TradingSession};
 
 mod error;
 use snafu::Snafu;
@@ -74,14 +112,176 @@ pub struct Error(error::InnerError);
 
 pub type Result<T> = std::result::Result<T, Error>;
 
+impl Error {
+   /// How long Yahoo! asked us to wait before calling again when it's rate limiting us
+   pub fn retry_after(&self) -> Option<std::time::Duration> {
+      match &self.0 {
+         error::InnerError::CallThrottled { retry_after, .. } => *retry_after,
+         _ => None
+      }
+   }
+
+   /// Whether the call might work if it's tried again later, ie. Yahoo! was
+   /// rate limiting us, had a temporary problem or couldn't be reached.
+   ///
+   /// # Examples
+   ///
+   /// Calls are already retried as the [retry policy](config/struct.RetryPolicy.html)
+   /// says, so this is for backing off for longer - ie. in a batch job:
+   ///
+   /// ```no_run
+   /// use std::time::Duration;
+   /// use yahoo_finance::history;
+   ///
+   /// # async fn run() -> yahoo_finance::Result<()> {
+   /// let mut wait = Duration::from_secs(30);
+   /// let mut rounds = 1;
+   /// let bars = loop {
+   ///    match history::retrieve("AAPL").await {
+   ///       Err(e) if e.is_retryable() && rounds < 5 => {
+   ///          tokio::time::delay_for(e.retry_after().unwrap_or(wait)).await;
+   ///          wait *= 2;
+   ///          rounds += 1;
+   ///       },
+   ///       result => break result?
+   ///    }
+   /// };
+   /// # Ok(())
+   /// # }
+   /// ```
+   pub fn is_retryable(&self) -> bool {
+      match &self.0 {
+         error::InnerError::CallFailed { status, .. } => matches!(status, 429 | 500 | 502 | 503 | 504),
+         error::InnerError::CallThrottled { .. } | error::InnerError::RequestFailed { .. } | error::InnerError::ServiceUnavailable { .. } => true,
+         _ => false
+      }
+   }
+
+   /// Whether Yahoo! was rate limiting us.  `retry_after` says how long to
+   /// wait when Yahoo! told us.
+   pub fn is_rate_limited(&self) -> bool {
+      matches!(&self.0, error::InnerError::Blocked { .. } | error::InnerError::CallThrottled { .. } | error::InnerError::CallFailed { status: 429, .. })
+   }
+
+   /// Whether Yahoo! doesn't know the symbol or has no data for it
+   pub fn is_not_found(&self) -> bool {
+      match &self.0 {
+         error::InnerError::SymbolNotFound { .. } => true,
+         error::InnerError::CallFailed { status, .. } => *status == 404,
+         error::InnerError::ApiFailed { code, .. } | error::InnerError::ChartFailed { code, .. } => code == "Not Found",
+         _ => false
+      }
+   }
+
+   /// The cookie consent page Yahoo! sent us to, if it did
+   pub(crate) fn consent_page(&self) -> Option<String> {
+      match &self.0 {
+         error::InnerError::ConsentRequired { url } => Some(url.clone()),
+         _ => None
+      }
+   }
+
+   /// Whether Yahoo! turned down our session cookie & crumb
+   pub(crate) fn is_unauthorized(&self) -> bool {
+      matches!(&self.0, error::InnerError::CallFailed { status: 401, .. } | error::InnerError::CallFailed { status: 403, .. })
+   }
+
+   /// Whether Yahoo! was rate limiting us or was otherwise unavailable
+   pub(crate) fn is_unavailable(&self) -> bool {
+      match &self.0 {
+         error::InnerError::CallFailed { status, .. } => *status == 429 || *status >= 500,
+         error::InnerError::Blocked { .. } | error::InnerError::CallThrottled { .. } | error::InnerError::RequestFailed { .. } => true,
+         error::InnerError::ServiceUnavailable { .. } | error::InnerError::UnexpectedErrorYahoo => true,
+         _ => false
+      }
+   }
+}
+
 mod yahoo;
 
+/// serde support for `Bar` & `TradingSession`
+pub mod remote;
+
+#[cfg(feature = "blocking")]
+mod blocking;
+
+mod cancel;
+pub use cancel::CancellationToken;
+
+mod many;
+
+/// Process wide settings
+pub mod config;
+
 /// Historical quotes
 pub mod history;
 
+/// Currency information
+pub mod currency;
+
+/// Mutual fund & ETF information
+pub mod funds;
+
+/// Commodity futures
+pub mod commodities;
+
+/// Company financial statements
+pub mod fundamentals;
+
+/// Technical insights
+pub mod insights;
+pub use insights::Insights;
+
+/// Market wide information
+pub mod market;
+
+/// Request timing & size telemetry
+pub mod metrics;
+
+/// Offline parsers for saved Yahoo responses
+pub mod parse;
+
+/// Pluggable data providers
+pub mod provider;
+
+/// Institutional & insider holders
+pub mod holders;
+
+/// Interest rates
+pub mod rates;
+
+/// Response schema telemetry
+pub mod schema;
+
+/// Symbol lookups
+pub mod symbols;
+
+/// Key statistics
+mod statistics;
+pub use statistics::Statistics;
+
+/// Analyst ratings
+pub mod analysts;
+
+/// Earnings history & estimates
+pub mod earnings;
+pub use earnings::Earnings;
+
+/// Financial data & analyst opinions
+mod financials;
+pub use financials::Financials;
+
+/// Quote page overview
+mod summary;
+pub use summary::Summary;
+
+/// Price & volume alerts on streamed quotes
+pub mod alerts;
+
 /// Realtime quotes
 mod streaming;
-pub use streaming::Streamer;
+pub use streaming::{Broadcast, Candle, CandleAggregator, OptionQuote, Overflow, Quote, Replay, StreamEvent, StreamStats, Streamer, StreamerBuilder, SubscriptionEvent, TlsConnector};
+pub use streaming::{pricing_data, PricingData};
 
 /// Symbol profile
 mod profile;
@@ -0,0 +1,25 @@
+use futures::{ stream, StreamExt };
+use std::collections::HashMap;
+use std::future::Future;
+
+use crate::{ config, Result };
+
+/// Loads something for each of the symbols, keyed by symbol, with no more
+/// than the configured `max_concurrent` loads running at once.
+pub(crate) async fn load_many<'a, T, F, Fut>(symbols: &[&'a str], load: F) -> HashMap<String, Result<T>>
+where
+   F: Fn(&'a str) -> Fut,
+   Fut: Future<Output = Result<T>>
+{
+   // the loads are collected up front so the stream's type doesn't borrow
+   // from a closure, which keeps the whole thing `Send` for spawning
+   let loads: Vec<_> = symbols.iter().map(|&symbol| keyed(symbol, load(symbol))).collect();
+   stream::iter(loads)
+      .buffer_unordered(config::max_concurrent())
+      .collect()
+      .await
+}
+
+async fn keyed<T>(symbol: &str, loading: impl Future<Output = Result<T>>) -> (String, Result<T>) {
+   (symbol.to_string(), loading.await)
+}
@@ -0,0 +1,218 @@
+use serde::{Deserialize, Serialize};
+use serde_json::{ json, Value };
+
+use crate::{ yahoo, Result };
+
+/// The minimum daily volume for a symbol to be considered a mover
+const MIN_VOLUME: u64 = 15_000;
+
+/// Regions whose markets Yahoo can screen
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Region {
+   Australia,
+   Brazil,
+   Canada,
+   France,
+   Germany,
+   HongKong,
+   India,
+   Italy,
+   Japan,
+   Spain,
+   Switzerland,
+   UnitedKingdom,
+   UnitedStates
+}
+impl Region {
+   /// The code Yahoo uses for the region.  ie. 'gb'
+   pub fn code(&self) -> &'static str {
+      match self {
+         Region::Australia => "au",
+         Region::Brazil => "br",
+         Region::Canada => "ca",
+         Region::France => "fr",
+         Region::Germany => "de",
+         Region::HongKong => "hk",
+         Region::India => "in",
+         Region::Italy => "it",
+         Region::Japan => "jp",
+         Region::Spain => "es",
+         Region::Switzerland => "ch",
+         Region::UnitedKingdom => "gb",
+         Region::UnitedStates => "us"
+      }
+   }
+}
+
+/// The different kinds of market movers
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum MoverKind {
+   /// The biggest percentage gainers for the day
+   Gainers,
+
+   /// The biggest percentage losers for the day
+   Losers,
+
+   /// The most actively traded symbols for the day
+   Actives
+}
+
+/// The extended hours trading sessions
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ExtendedSession {
+   /// The period of trading before the regular market session
+   PreMarket,
+
+   /// The period of trading immediately after the regular market session
+   AfterHours
+}
+impl ExtendedSession {
+   fn change_field(&self) -> &'static str {
+      match self {
+         ExtendedSession::PreMarket => "premarketchangepercent",
+         ExtendedSession::AfterHours => "postmarketchangepercent"
+      }
+   }
+}
+
+/// How a symbol moved during an extended hours session.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SessionMove {
+   pub price: f64,
+   pub change: Option<f64>,
+   pub change_percent: Option<f64>
+}
+impl SessionMove {
+   fn new(price: Option<f64>, change: Option<f64>, change_percent: Option<f64>) -> Option<SessionMove> {
+      price.map(|price| SessionMove { price, change, change_percent })
+   }
+}
+
+/// A symbol that is moving the market.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Mover {
+   pub symbol: String,
+
+   /// The common name for the symbol
+   pub name: Option<String>,
+
+   /// The exchange the symbol trades on.  ie. 'LSE'
+   pub exchange: Option<String>,
+
+   /// The currency the symbol trades in.  ie. 'GBp'
+   pub currency: Option<String>,
+
+   pub price: Option<f64>,
+   pub change: Option<f64>,
+   pub change_percent: Option<f64>,
+   pub volume: Option<u64>,
+
+   /// The move during the pre market session, if Yahoo has one
+   pub pre_market: Option<SessionMove>,
+
+   /// The move during the after hours session, if Yahoo has one
+   pub after_hours: Option<SessionMove>
+}
+impl Mover {
+   pub(crate) fn new(data: yahoo::ScreenerQuote) -> Mover {
+      Mover {
+         symbol: data.symbol,
+         name: data.short_name,
+         exchange: data.exchange,
+         currency: data.currency,
+         price: data.regular_market_price,
+         change: data.regular_market_change,
+         change_percent: data.regular_market_change_percent,
+         volume: data.regular_market_volume,
+         pre_market: SessionMove::new(data.pre_market_price, data.pre_market_change, data.pre_market_change_percent),
+         after_hours: SessionMove::new(data.post_market_price, data.post_market_change, data.post_market_change_percent)
+      }
+   }
+}
+
+async fn screen(mut criteria: Vec<Value>, change_field: &str, kind: MoverKind, count: u32) -> Result<Vec<Mover>> {
+   criteria.push(yahoo::criteria("gt", "dayvolume", json!(MIN_VOLUME)));
+   let (sort, ascending) = match kind {
+      MoverKind::Gainers => {
+         criteria.push(yahoo::criteria("gt", change_field, json!(3)));
+         (change_field, false)
+      },
+      MoverKind::Losers => {
+         criteria.push(yahoo::criteria("lt", change_field, json!(-2.5)));
+         (change_field, true)
+      },
+      MoverKind::Actives => ("dayvolume", false)
+   };
+
+   Ok(yahoo::load_screen(criteria, sort, ascending, count).await?.into_iter().map(Mover::new).collect())
+}
+
+/// Retrieves (at most) `count` market movers for a region.
+///
+/// # Examples
+///
+/// Get the biggest gainers on UK markets:
+///
+/// ``` no_run
+/// use yahoo_finance::market::{ self, MoverKind, Region };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for mover in market::movers(Region::UnitedKingdom, MoverKind::Gainers, 10).await.unwrap() {
+///       println!("{} is up {:.2}%", mover.symbol, mover.change_percent.unwrap_or_default());
+///    }
+/// }
+/// ```
+pub async fn movers(region: Region, kind: MoverKind, count: u32) -> Result<Vec<Mover>> {
+   screen(vec![ yahoo::criteria("eq", "region", json!(region.code())) ], "percentchange", kind, count).await
+}
+
+/// Blocking version of `movers` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_movers(region: Region, kind: MoverKind, count: u32) -> Result<Vec<Mover>> { crate::blocking::run(movers(region, kind, count)) }
+
+/// Retrieves (at most) `count` market movers for a single exchange in a region,
+/// using Yahoo's exchange codes.  ie. 'GER' for XETRA
+pub async fn movers_on(region: Region, exchange: &str, kind: MoverKind, count: u32) -> Result<Vec<Mover>> {
+   let criteria = vec![
+      yahoo::criteria("eq", "region", json!(region.code())),
+      yahoo::criteria("eq", "exchange", json!(exchange))
+   ];
+   screen(criteria, "percentchange", kind, count).await
+}
+
+/// Blocking version of `movers_on` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_movers_on(region: Region, exchange: &str, kind: MoverKind, count: u32) -> Result<Vec<Mover>> {
+   crate::blocking::run(movers_on(region, exchange, kind, count))
+}
+
+/// Retrieves (at most) `count` movers for a region based on their change
+/// during an extended hours session - useful for building gap scanners.
+///
+/// # Examples
+///
+/// Get the biggest pre market gainers in the US:
+///
+/// ``` no_run
+/// use yahoo_finance::market::{ self, ExtendedSession, MoverKind, Region };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let gappers = market::extended_movers(Region::UnitedStates, ExtendedSession::PreMarket, MoverKind::Gainers, 10).await.unwrap();
+///    for mover in gappers {
+///       if let Some(pre) = mover.pre_market {
+///          println!("{} is trading at ${:.2} before the open", mover.symbol, pre.price);
+///       }
+///    }
+/// }
+/// ```
+pub async fn extended_movers(region: Region, session: ExtendedSession, kind: MoverKind, count: u32) -> Result<Vec<Mover>> {
+   screen(vec![ yahoo::criteria("eq", "region", json!(region.code())) ], session.change_field(), kind, count).await
+}
+
+/// Blocking version of `extended_movers` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_extended_movers(region: Region, session: ExtendedSession, kind: MoverKind, count: u32) -> Result<Vec<Mover>> {
+   crate::blocking::run(extended_movers(region, session, kind, count))
+}
@@ -0,0 +1,75 @@
+use std::sync::{ Arc, RwLock };
+use std::time::Duration;
+
+static OBSERVER: RwLock<Option<Arc<dyn RequestObserver>>> = RwLock::new(None);
+
+/// The kind of call that was made to Yahoo
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Operation {
+   Get,
+   Post,
+
+   /// Opening the streaming websocket
+   Connect,
+
+   /// A message received on the streaming websocket
+   Message
+}
+
+/// Describes a single call to Yahoo, including every retry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestEvent {
+   pub operation: Operation,
+
+   /// The URL without its query parameters.  ie. `https://query1.finance.yahoo.com/v8/finance/chart/AAPL`
+   pub url: String,
+
+   /// The HTTP status, if Yahoo answered at all
+   pub status: Option<u16>,
+
+   /// The size of the response body as read, or of the websocket message
+   pub bytes: u64,
+
+   /// How long the call took.  Zero for websocket messages.
+   pub elapsed: Duration
+}
+
+/// Gets told about every call made to Yahoo, ie. to feed Prometheus.
+///
+/// Any `Fn(&RequestEvent)` closure is an observer.
+pub trait RequestObserver: Send + Sync {
+   fn observe(&self, event: &RequestEvent);
+}
+impl<F: Fn(&RequestEvent) + Send + Sync> RequestObserver for F {
+   fn observe(&self, event: &RequestEvent) { self(event) }
+}
+
+/// Sets the observer told about every call made to Yahoo, replacing any
+/// existing observer.  The observer may replace or clear itself.
+///
+/// # Examples
+///
+/// ```
+/// use yahoo_finance::metrics;
+///
+/// metrics::set_observer(|event: &metrics::RequestEvent| {
+///    println!("{:?} {} took {:?}", event.operation, event.url, event.elapsed);
+/// });
+/// ```
+pub fn set_observer(observer: impl RequestObserver + 'static) {
+   *OBSERVER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(observer));
+}
+
+/// Removes any observer
+pub fn clear_observer() {
+   *OBSERVER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub(crate) fn notify(operation: Operation, url: &str, status: Option<u16>, bytes: u64, elapsed: Duration) {
+   // let go of the lock first, so the observer can change the observer
+   let observer = OBSERVER.read().unwrap_or_else(|e| e.into_inner()).clone();
+   if let Some(observer) = observer {
+      let url = url.split('?').next().unwrap_or_default().to_string();
+      observer.observe(&RequestEvent { operation, url, status, bytes, elapsed });
+   }
+}
@@ -0,0 +1,174 @@
+//! Each parser takes the raw bytes of a saved Yahoo response - exactly as
+//! Yahoo sent it - and returns the same types the network calls do.
+//!
+//! # Examples
+//!
+//! ``` no_run
+//! use yahoo_finance::parse;
+//!
+//! let saved = std::fs::read("aapl_chart.json").unwrap();
+//! for bar in parse::chart(&saved).unwrap() {
+//!    println!("{} closed at {:.2}", bar.timestamp, bar.close);
+//! }
+//! ```
+use std::collections::HashMap;
+use snafu::OptionExt;
+
+use crate::{ error, history, yahoo, Bar, Earnings, Financials, Insights, Profile, Result, Statistics, Summary };
+use crate::analysts::{ self, PriceTargets, RatingChange, Ratings };
+use crate::earnings::{ self, EarningsDate, Surprise };
+use crate::fundamentals::{ self, BalanceSheet, DataPoint, IncomeStatement };
+use crate::funds::Allocation;
+use crate::holders::{ self, Holder, Insiders };
+use crate::market::Mover;
+use crate::symbols::{ Kind, Recommendation };
+
+fn text(data: &[u8]) -> std::borrow::Cow<'_, str> { String::from_utf8_lossy(data) }
+
+/// Parses a chart response (`/v8/finance/chart/{symbol}`) into OHLCV bars
+pub fn chart(data: &[u8]) -> Result<Vec<Bar>> {
+   history::aggregate_bars(yahoo::parse_chart(&text(data))?)
+}
+
+/// Parses a quote page (`https://finance.yahoo.com/quote/{symbol}`) into a profile
+pub fn profile(data: &[u8]) -> Result<Profile> {
+   Profile::new(yahoo::parse_page(&text(data))?.quote_summary_store)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `assetProfile`, `quoteType` & `fundProfile` modules into a profile
+pub fn quote_summary_profile(data: &[u8]) -> Result<Profile> {
+   let summary = yahoo::parse_quote_summary(&text(data))?.context(error::MissingData { reason: "no quote summary" })?;
+   Profile::new(summary.into_profile().context(error::MissingData { reason: "no quote type" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `topHoldings` module into a fund's asset allocation
+pub fn quote_summary_allocation(data: &[u8]) -> Result<Allocation> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   Allocation::new(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `defaultKeyStatistics` & `summaryDetail` modules into the key statistics
+pub fn quote_summary_statistics(data: &[u8]) -> Result<Statistics> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   Statistics::new(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `summaryDetail` module into a summary
+pub fn quote_summary_detail(data: &[u8]) -> Result<Summary> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   Summary::new(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `earnings` & `earningsTrend` modules into the earnings
+pub fn quote_summary_earnings(data: &[u8]) -> Result<Earnings> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   Earnings::new(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `calendarEvents` or `earnings` module into the next earnings date
+pub fn quote_summary_earnings_date(data: &[u8]) -> Result<Option<EarningsDate>> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   Ok(EarningsDate::from_summary(summary.context(error::MissingData { reason: "no quote summary" })?))
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `earningsHistory` module into the EPS surprises
+pub fn quote_summary_surprises(data: &[u8]) -> Result<Vec<Surprise>> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   earnings::surprise_history(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `recommendationTrend` module into the monthly analyst ratings
+pub fn quote_summary_ratings(data: &[u8]) -> Result<Vec<Ratings>> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   analysts::ratings(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `upgradeDowngradeHistory` module into the rating changes
+pub fn quote_summary_rating_changes(data: &[u8]) -> Result<Vec<RatingChange>> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   analysts::rating_history(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `financialData` module into the analysts' price targets
+pub fn quote_summary_price_targets(data: &[u8]) -> Result<PriceTargets> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   PriceTargets::new(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `institutionOwnership` module into the institutional holders
+pub fn quote_summary_institutional_holders(data: &[u8]) -> Result<Vec<Holder>> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   holders::institutional_holders(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `insiderHolders` or `insiderTransactions` modules into the insiders
+pub fn quote_summary_insiders(data: &[u8]) -> Result<Insiders> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   holders::insider_holders(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote summary response (`/v10/finance/quoteSummary/{symbol}`)
+/// containing the `financialData` module into the financials
+pub fn quote_summary_financials(data: &[u8]) -> Result<Financials> {
+   let summary = yahoo::parse_quote_summary(&text(data))?;
+   Financials::new(summary.context(error::MissingData { reason: "no quote summary" })?)
+}
+
+/// Parses a quote type response (`/v1/finance/quoteType/{symbol}`)
+pub fn quote_type(data: &[u8]) -> Result<Kind> {
+   let quote_type = yahoo::parse_quote_type(&text(data))?;
+   Ok(Kind::from(quote_type.context(error::MissingData { reason: "no quote type" })?.kind.as_str()))
+}
+
+/// Parses a symbol validation response (`/v6/finance/quote/validate`)
+pub fn validation(data: &[u8]) -> Result<HashMap<String, bool>> {
+   yahoo::parse_validation(&text(data))
+}
+
+/// Parses a recommendations response (`/v6/finance/recommendationsbysymbol/{symbol}`)
+pub fn recommendations(data: &[u8]) -> Result<Vec<Recommendation>> {
+   let recommendations = yahoo::parse_recommendations(&text(data))?;
+   Ok(Recommendation::from_yahoo(recommendations.context(error::MissingData { reason: "no recommendations" })?))
+}
+
+/// Parses an insights response (`/ws/insights/v1/finance/insights`)
+pub fn insights(data: &[u8]) -> Result<Insights> {
+   let insights = yahoo::parse_insights(&text(data))?;
+   Ok(Insights::new(insights.context(error::MissingData { reason: "no insights" })?))
+}
+
+/// Parses a screener response (`/v1/finance/screener`) into market movers
+pub fn screener(data: &[u8]) -> Result<Vec<Mover>> {
+   Ok(yahoo::parse_screen(&text(data))?.into_iter().map(Mover::new).collect())
+}
+
+/// Parses a fundamentals timeseries response (`/ws/fundamentals-timeseries/v1/finance/timeseries/{symbol}`)
+/// into annual or quarterly income statements, with only the financial currency
+/// since the response doesn't say what the symbol trades in
+pub fn income_statement(data: &[u8]) -> Result<Vec<IncomeStatement>> {
+   fundamentals::income_statements(yahoo::parse_timeseries(&text(data))?, None)
+}
+
+/// Parses a fundamentals timeseries response (`/ws/fundamentals-timeseries/v1/finance/timeseries/{symbol}`)
+/// into annual or quarterly balance sheets, with only the financial currency
+pub fn balance_sheet(data: &[u8]) -> Result<Vec<BalanceSheet>> {
+   fundamentals::balance_sheets(yahoo::parse_timeseries(&text(data))?, None)
+}
+
+/// Parses a fundamentals timeseries response (`/ws/fundamentals-timeseries/v1/finance/timeseries/{symbol}`)
+/// into a series for each of its fundamentals, with only the financial currency
+pub fn timeseries(data: &[u8]) -> Result<HashMap<String, Vec<DataPoint>>> {
+   fundamentals::data_points(yahoo::parse_timeseries(&text(data))?, None)
+}
@@ -1,8 +1,16 @@
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
 use crate::{error, yahoo, Result};
+use crate::currency::Currencies;
+use crate::symbols::Kind;
+
+/// The quote summary modules a profile is made of
+pub(crate) const MODULES: &[&str] = &["assetProfile", "quoteType", "fundProfile", "price", "financialData"];
 
 /// Symbols which represent a company can have an address associated with them.
 /// This is usually the company headquarters.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Address {
    pub street1: Option<String>,
    pub street2: Option<String>,
@@ -25,12 +33,15 @@ impl Address {
    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Company {
    /// Optional address on file for the symbol - typically the HQ for publicly
    /// traded companies.
    pub address: Option<Address>,
 
+   /// The currencies the company trades & reports its financials in.
+   pub currencies: Currencies,
+
    /// The industry, according to Yahoo.  ie. 'Gold'
    pub industry: Option<String>,
 
@@ -48,13 +59,18 @@ pub struct Company {
 }
 impl Company {
    fn new(data: yahoo::QuoteSummaryStore) -> Result<Company> {
-      let profile = data.company_profile.expect("asdf");
+      let profile = data.company_profile.context(error::MissingData { reason: "no company profile" })?;
       let address = Some(Address::new(&profile)?);
+      let currencies = Currencies {
+         trading: data.price.and_then(|price| price.currency),
+         financial: data.financial_data.and_then(|financials| financials.financial_currency)
+      };
 
       Ok(Company {
          name: data.quote_type.name,
          summary: profile.summary,
          address,
+         currencies,
          industry: profile.industry,
          sector: profile.sector,
          website: profile.website,
@@ -62,7 +78,7 @@ impl Company {
    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Fund {
    pub name: String,
 
@@ -72,7 +88,7 @@ pub struct Fund {
 }
 impl Fund {
    fn new(data: yahoo::QuoteSummaryStore) -> Result<Fund> {
-      let profile = data.fund_profile.expect("asdf");
+      let profile = data.fund_profile.context(error::MissingData { reason: "no fund profile" })?;
 
       Ok(Fund {
          name: data.quote_type.name,
@@ -82,20 +98,35 @@ impl Fund {
    }
 }
 
-#[derive(Debug, Clone, PartialEq)]
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Profile {
    Company(Company),
    Fund(Fund)
 }
 impl Profile {
+   /// Loads the profile from Yahoo's quote summary, falling back to the data
+   /// on the quote page when the quote summary can't be loaded.  The quote
+   /// page's error is the one returned when neither can be loaded.
    pub async fn load(symbol: &str) -> Result<Profile> {
-      let data = yahoo::scrape(symbol).await?.quote_summary_store;
+      let summary = yahoo::load_quote_summary(symbol, MODULES).await
+         .and_then(|summary| Ok(summary.into_profile().context(error::MissingData { reason: "no quote type" })?));
+
+      match summary {
+         Ok(data) => Self::new(data),
+         Err(_) => Self::new(yahoo::scrape(symbol).await?.quote_summary_store)
+      }
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Profile> { crate::blocking::run(Self::load(symbol)) }
 
-      let kind = &data.quote_type.kind;
-      match kind.as_str() {
-         "EQUITY" => Ok(Self::Company(Company::new(data)?)),
-         "ETF" => Ok(Self::Fund(Fund::new(data)?)),
-         _ => (error::UnsupportedSecurity { kind }).fail().map_err(core::convert::Into::into)
+   pub(crate) fn new(data: yahoo::QuoteSummaryStore) -> Result<Profile> {
+      match Kind::from(data.quote_type.kind.as_str()) {
+         Kind::Equity => Ok(Self::Company(Company::new(data)?)),
+         Kind::Etf | Kind::MutualFund => Ok(Self::Fund(Fund::new(data)?)),
+         kind => (error::UnsupportedSecurity { kind: kind.to_string() }).fail().map_err(core::convert::Into::into)
       }
    }
 }
@@ -0,0 +1,97 @@
+use chrono::{DateTime, Utc};
+use futures::future::BoxFuture;
+use serde::{Deserialize, Serialize};
+
+use crate::{history, Bar, Result};
+
+/// A source of historical OHLCV data.
+///
+/// The crate's own `Yahoo` provider is one implementation, but any other
+/// source (a database, a mirror, another vendor) can implement this and be
+/// used wherever a provider is expected.
+pub trait Provider: Send + Sync {
+   /// A short name identifying this source - used when reporting which source served the data.
+   fn name(&self) -> &str;
+
+   /// Retrieves OHLCV data for a symbol between a start and end date.
+   fn history<'a>(&'a self, symbol: &'a str, start: DateTime<Utc>, end: DateTime<Utc>) -> BoxFuture<'a, Result<Vec<Bar>>>;
+}
+
+/// The Yahoo! finance provider.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Yahoo;
+
+impl Provider for Yahoo {
+   fn name(&self) -> &str { "yahoo" }
+
+   fn history<'a>(&'a self, symbol: &'a str, start: DateTime<Utc>, end: DateTime<Utc>) -> BoxFuture<'a, Result<Vec<Bar>>> {
+      Box::pin(history::retrieve_range(symbol, start, Some(end)))
+   }
+}
+
+/// Data along with the name of the provider that served it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Served<T> {
+   pub source: String,
+   pub data: T
+}
+
+/// A provider that tries Yahoo! first and falls back to a secondary provider
+/// when Yahoo! is rate limiting us or is unavailable.
+///
+/// Other failures - like an unknown symbol - are returned as is since the
+/// secondary provider is unlikely to do any better.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use chrono::{Duration, Utc};
+/// use yahoo_finance::provider::{FallbackProvider, Yahoo};
+///
+/// #[tokio::main]
+/// async fn main() {
+///    // a real application would use a different secondary provider
+///    let provider = FallbackProvider::new(Yahoo);
+///
+///    let now = Utc::now();
+///    let served = provider.retrieve("AAPL", now - Duration::days(30), now).await.unwrap();
+///    println!("{} bars served by {}", served.data.len(), served.source);
+/// }
+/// ```
+pub struct FallbackProvider {
+   primary: Box<dyn Provider>,
+   secondary: Box<dyn Provider>
+}
+
+impl FallbackProvider {
+   /// Creates a provider that uses Yahoo! and then the secondary provider
+   pub fn new<P: Provider + 'static>(secondary: P) -> FallbackProvider {
+      FallbackProvider { primary: Box::new(Yahoo), secondary: Box::new(secondary) }
+   }
+
+   /// Retrieves OHLCV data, reporting which provider served it.
+   pub async fn retrieve(&self, symbol: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Served<Vec<Bar>>> {
+      let provider = match self.primary.history(symbol, start, end).await {
+         Ok(data) => return Ok(Served { source: self.primary.name().to_string(), data }),
+         Err(e) if e.is_unavailable() => &self.secondary,
+         Err(e) => return Err(e)
+      };
+
+      let data = provider.history(symbol, start, end).await?;
+      Ok(Served { source: provider.name().to_string(), data })
+   }
+
+   /// Blocking version of `retrieve` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_retrieve(&self, symbol: &str, start: DateTime<Utc>, end: DateTime<Utc>) -> Result<Served<Vec<Bar>>> {
+      crate::blocking::run(self.retrieve(symbol, start, end))
+   }
+}
+
+impl Provider for FallbackProvider {
+   fn name(&self) -> &str { "fallback" }
+
+   fn history<'a>(&'a self, symbol: &'a str, start: DateTime<Utc>, end: DateTime<Utc>) -> BoxFuture<'a, Result<Vec<Bar>>> {
+      Box::pin(async move { Ok(self.retrieve(symbol, start, end).await?.data) })
+   }
+}
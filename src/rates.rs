@@ -0,0 +1,102 @@
+use futures::future;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::{ history, yahoo, Bar, Interval, Result };
+
+/// The US treasury maturities Yahoo publishes yield indices for.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Treasury {
+   /// 13 week treasury bill - '^IRX'
+   ThreeMonth,
+
+   /// 5 year treasury note - '^FVX'
+   FiveYear,
+
+   /// 10 year treasury note - '^TNX'
+   TenYear,
+
+   /// 30 year treasury bond - '^TYX'
+   ThirtyYear
+}
+impl Treasury {
+   /// All of the maturities, from shortest to longest
+   pub const ALL: [Treasury; 4] = [ Treasury::ThreeMonth, Treasury::FiveYear, Treasury::TenYear, Treasury::ThirtyYear ];
+
+   /// The Yahoo symbol for the maturity's yield index
+   pub fn symbol(&self) -> &'static str {
+      match self {
+         Treasury::ThreeMonth => "^IRX",
+         Treasury::FiveYear => "^FVX",
+         Treasury::TenYear => "^TNX",
+         Treasury::ThirtyYear => "^TYX"
+      }
+   }
+}
+impl fmt::Display for Treasury {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Treasury::ThreeMonth => f.write_str("US 3M"),
+         Treasury::FiveYear => f.write_str("US 5Y"),
+         Treasury::TenYear => f.write_str("US 10Y"),
+         Treasury::ThirtyYear => f.write_str("US 30Y")
+      }
+   }
+}
+
+/// The current yield for a treasury maturity.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Yield {
+   pub maturity: Treasury,
+
+   /// The yield as a percentage - ie. 0.68 is 0.68%
+   pub rate: f64,
+
+   /// The yield at the previous close as a percentage
+   pub previous_close: f64
+}
+
+/// Retrieves the current yield for a single treasury maturity.
+pub async fn treasury_yield(maturity: Treasury) -> Result<Yield> {
+   let meta = yahoo::load_daily(maturity.symbol(), Interval::_5d).await?.meta;
+   Ok(Yield { maturity, rate: meta.current_price as f64, previous_close: meta.previous_close as f64 })
+}
+
+/// Blocking version of `treasury_yield` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_treasury_yield(maturity: Treasury) -> Result<Yield> { crate::blocking::run(treasury_yield(maturity)) }
+
+/// Retrieves the current yields for all of the treasury maturities, from
+/// shortest to longest.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::rates;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for treasury in rates::treasury().await.unwrap() {
+///       println!("{} yields {:.3}%", treasury.maturity, treasury.rate);
+///    }
+/// }
+/// ```
+pub async fn treasury() -> Result<Vec<Yield>> {
+   future::try_join_all(Treasury::ALL.iter().map(|maturity| treasury_yield(*maturity))).await
+}
+
+/// Blocking version of `treasury` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_treasury() -> Result<Vec<Yield>> { crate::blocking::run(treasury()) }
+
+/// Retrieves the history of a treasury maturity's yield.  The OHLC values are
+/// yields as percentages.
+pub async fn treasury_history(maturity: Treasury, interval: Interval) -> Result<Vec<Bar>> {
+   history::retrieve_interval(maturity.symbol(), interval).await
+}
+
+/// Blocking version of `treasury_history` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_treasury_history(maturity: Treasury, interval: Interval) -> Result<Vec<Bar>> {
+   crate::blocking::run(treasury_history(maturity, interval))
+}
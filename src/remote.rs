@@ -0,0 +1,45 @@
+//! serde definitions for the `market_finance` types we expose, since they
+//! don't implement serde themselves.
+//!
+//! Use them with serde's `with` attribute to persist a `Bar` or
+//! `TradingSession` as part of your own types.
+//!
+//! # Examples
+//!
+//! ```
+//! use serde::{ Deserialize, Serialize };
+//! use yahoo_finance::Bar;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Saved {
+//!    symbol: String,
+//!
+//!    #[serde(with = "yahoo_finance::remote::BarDef")]
+//!    bar: Bar
+//! }
+//!
+//! let saved = Saved { symbol: "AAPL".to_string(), bar: Bar { timestamp: 1588253400000, open: 289.96, high: 294.53, low: 288.35, close: 293.8, volume: Some(45766000) } };
+//! let json = serde_json::to_string(&saved).unwrap();
+//! let loaded: Saved = serde_json::from_str(&json).unwrap();
+//! assert_eq!(saved.bar, loaded.bar);
+//! ```
+use serde::{Deserialize, Serialize};
+
+use crate::{Bar, TradingSession};
+
+/// The serde definition of a [`Bar`](../struct.Bar.html)
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "Bar")]
+pub struct BarDef {
+   pub timestamp: i64,
+   pub open: f64,
+   pub high: f64,
+   pub low: f64,
+   pub close: f64,
+   pub volume: Option<u64>
+}
+
+/// The serde definition of a [`TradingSession`](../enum.TradingSession.html)
+#[derive(Serialize, Deserialize)]
+#[serde(remote = "TradingSession")]
+pub enum TradingSessionDef { PreMarket, Regular, AfterHours, Other }
@@ -0,0 +1,54 @@
+use std::sync::{ Arc, RwLock };
+
+type Observer = Arc<dyn Fn(&SchemaMatch) + Send + Sync>;
+
+static OBSERVER: RwLock<Option<Observer>> = RwLock::new(None);
+
+/// Describes which version of a Yahoo response schema was used to read a
+/// response.
+///
+/// Yahoo changes its responses without notice, so endpoints that Yahoo has
+/// sent in more than one shape know about a list of schema versions which
+/// are tried in order.  A match on anything other than the first version
+/// means Yahoo is sending something other than what we expect and the crate
+/// may need an update.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaMatch {
+   /// The endpoint that was read.  ie. 'chart'
+   pub endpoint: &'static str,
+
+   /// The name of the schema version that matched.  ie. 'v8'
+   pub version: &'static str,
+
+   /// Whether the preferred schema version failed & an older one was used
+   pub fallback: bool
+}
+
+/// Sets a function to be notified every time a response is read, replacing
+/// any existing observer.
+///
+/// # Examples
+///
+/// ```
+/// use yahoo_finance::schema;
+///
+/// schema::set_observer(|matched| {
+///    if matched.fallback {
+///       eprintln!("Yahoo changed the {} schema - fell back to {}", matched.endpoint, matched.version);
+///    }
+/// });
+/// ```
+pub fn set_observer(observer: impl Fn(&SchemaMatch) + Send + Sync + 'static) {
+   *OBSERVER.write().unwrap_or_else(|e| e.into_inner()) = Some(Arc::new(observer));
+}
+
+/// Removes any observer
+pub fn clear_observer() {
+   *OBSERVER.write().unwrap_or_else(|e| e.into_inner()) = None;
+}
+
+pub(crate) fn notify(matched: &SchemaMatch) {
+   // let go of the lock first, so the observer can replace or clear itself
+   let observer = OBSERVER.read().unwrap_or_else(|e| e.into_inner()).clone();
+   if let Some(observer) = observer { observer(matched); }
+}
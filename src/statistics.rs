@@ -0,0 +1,128 @@
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
+use crate::{ error, yahoo, Result };
+use crate::currency::Currencies;
+
+/// The quote summary modules the statistics are made of
+const MODULES: &[&str] = &["defaultKeyStatistics", "summaryDetail", "financialData"];
+
+/// The key statistics Yahoo keeps for a symbol - valuation, earnings, risk
+/// & share ownership.
+///
+/// Statistics that don't apply are left out - see
+/// [missing figures & fractions](crate#missing-figures--fractions).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Statistics {
+   /// The currencies the symbol trades & reports its financials in
+   pub currencies: Currencies,
+
+   pub market_cap: Option<f64>,
+   pub enterprise_value: Option<f64>,
+
+   /// The price over the earnings of the last 12 months
+   pub trailing_pe: Option<f64>,
+
+   /// The price over the estimated earnings for the next fiscal year
+   pub forward_pe: Option<f64>,
+
+   /// The forward P/E over the expected earnings growth
+   pub peg_ratio: Option<f64>,
+
+   /// The earnings per share over the last 12 months
+   pub trailing_eps: Option<f64>,
+
+   /// The estimated earnings per share for the next fiscal year
+   pub forward_eps: Option<f64>,
+
+   /// How much the price moves with the market
+   pub beta: Option<f64>,
+
+   /// The book value per share
+   pub book_value: Option<f64>,
+   pub price_to_book: Option<f64>,
+   pub profit_margins: Option<f64>,
+
+   /// The growth in earnings over the same quarter a year ago
+   pub earnings_quarterly_growth: Option<f64>,
+   pub enterprise_to_revenue: Option<f64>,
+   pub enterprise_to_ebitda: Option<f64>,
+
+   pub shares_outstanding: Option<u64>,
+
+   /// The shares available for trading, ie. not held by insiders
+   pub float_shares: Option<u64>,
+   pub shares_short: Option<u64>,
+
+   /// The days it would take to cover the short interest at the average volume
+   pub short_ratio: Option<f64>,
+   pub short_percent_of_float: Option<f64>,
+   pub held_percent_insiders: Option<f64>,
+   pub held_percent_institutions: Option<f64>,
+
+   /// The change in price over the last 52 weeks
+   pub change_52_week: Option<f64>,
+
+   /// The change in the S&P 500 over the last 52 weeks, to compare against
+   pub sp500_change_52_week: Option<f64>
+}
+impl Statistics {
+   /// Loads the key statistics for a symbol.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::Statistics;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let statistics = Statistics::load("AAPL").await.unwrap();
+   ///    println!("Apple trades at {:.1} times earnings with a beta of {:.2}",
+   ///       statistics.trailing_pe.unwrap_or_default(),
+   ///       statistics.beta.unwrap_or_default());
+   /// }
+   /// ```
+   pub async fn load(symbol: &str) -> Result<Statistics> {
+      Self::new(yahoo::load_quote_summary(symbol, MODULES).await?)
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Statistics> { crate::blocking::run(Self::load(symbol)) }
+
+   pub(crate) fn new(data: yahoo::QuoteSummary) -> Result<Statistics> {
+      let currencies = Currencies::from_summary(&data);
+      let stats = data.default_key_statistics.context(error::MissingData { reason: "no key statistics" })?;
+      let (market_cap, trailing_pe) = match data.summary_detail {
+         Some(detail) => (detail.market_cap, detail.trailing_pe),
+         None => (None, None)
+      };
+
+      Ok(Statistics {
+         currencies,
+         market_cap,
+         enterprise_value: stats.enterprise_value,
+         trailing_pe,
+         forward_pe: stats.forward_pe,
+         peg_ratio: stats.peg_ratio,
+         trailing_eps: stats.trailing_eps,
+         forward_eps: stats.forward_eps,
+         beta: stats.beta,
+         book_value: stats.book_value,
+         price_to_book: stats.price_to_book,
+         profit_margins: stats.profit_margins,
+         earnings_quarterly_growth: stats.earnings_quarterly_growth,
+         enterprise_to_revenue: stats.enterprise_to_revenue,
+         enterprise_to_ebitda: stats.enterprise_to_ebitda,
+         shares_outstanding: stats.shares_outstanding,
+         float_shares: stats.float_shares,
+         shares_short: stats.shares_short,
+         short_ratio: stats.short_ratio,
+         short_percent_of_float: stats.short_percent_of_float,
+         held_percent_insiders: stats.held_percent_insiders,
+         held_percent_institutions: stats.held_percent_institutions,
+         change_52_week: stats.change_52_week,
+         sp500_change_52_week: stats.sp500_change_52_week
+      })
+   }
+}
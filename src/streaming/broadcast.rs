@@ -0,0 +1,30 @@
+use futures::{ future, stream, Stream, StreamExt };
+use std::sync::{ Arc, Weak };
+use tokio::sync::broadcast::Sender;
+
+use super::Quote;
+
+/// Shares one streaming connection between any number of consumers.  Each
+/// handle from [`subscribe`](#method.subscribe) gets every quote from then on.
+///
+/// Created with [`Streamer::broadcast`](struct.Streamer.html#method.broadcast).
+pub struct Broadcast {
+   /// Only the task feeding the quotes keeps the sender alive, so the
+   /// handles all end along with the connection.
+   sender: Weak<Sender<Quote>>
+}
+impl Broadcast {
+   pub(super) fn new(sender: &Arc<Sender<Quote>>) -> Broadcast {
+      Broadcast { sender: Arc::downgrade(sender) }
+   }
+
+   /// Creates a new handle on the quotes.  A handle that falls too far behind
+   /// skips the quotes it missed rather than holding up everybody else.  The
+   /// handle is empty once the stream has ended.
+   pub fn subscribe(&self) -> impl Stream<Item = Quote> {
+      match self.sender.upgrade() {
+         Some(sender) => sender.subscribe().filter_map(|quote| future::ready(quote.ok())).left_stream(),
+         None => stream::empty().right_stream()
+      }
+   }
+}
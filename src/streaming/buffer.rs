@@ -0,0 +1,126 @@
+use futures::stream::{ self, BoxStream, StreamExt };
+use std::collections::VecDeque;
+use std::sync::{ Arc, Mutex, MutexGuard };
+use tokio::sync::Notify;
+
+/// What to do with quotes that arrive while the buffer is full
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Overflow {
+   /// Wait for the consumer to catch up, which holds up reading from Yahoo!
+   /// until it does.  Yahoo! may hang up on a connection that falls too far
+   /// behind.
+   Block,
+
+   /// Throw away the oldest quote to make room - ie. for a ticker display
+   /// that only cares about the most recent prices
+   DropOldest,
+
+   /// Throw away the quote that just arrived
+   DropNewest
+}
+
+/// The consumer has gone away
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Closed;
+
+#[derive(Debug)]
+struct State<T> {
+   queue: VecDeque<T>,
+   sender_closed: bool,
+   receiver_closed: bool
+}
+
+#[derive(Debug)]
+struct Shared<T> {
+   state: Mutex<State<T>>,
+   capacity: Option<usize>,
+   overflow: Overflow,
+   readable: Notify,
+   writable: Notify
+}
+impl<T> Shared<T> {
+   fn lock(&self) -> MutexGuard<'_, State<T>> { self.state.lock().unwrap_or_else(|e| e.into_inner()) }
+}
+
+/// A single producer, single consumer queue holding up to `capacity` items
+/// (or any number of them when `None`) & handling the rest by `overflow`.
+pub fn channel<T>(capacity: Option<usize>, overflow: Overflow) -> (Sender<T>, Receiver<T>) {
+   let shared = Arc::new(Shared {
+      state: Mutex::new(State { queue: VecDeque::new(), sender_closed: false, receiver_closed: false }),
+      capacity: capacity.map(|capacity| capacity.max(1)),
+      overflow,
+      readable: Notify::new(),
+      writable: Notify::new()
+   });
+   (Sender(shared.clone()), Receiver(shared))
+}
+
+#[derive(Debug)]
+pub struct Sender<T>(Arc<Shared<T>>);
+impl<T> Sender<T> {
+   /// Queues up an item, failing once the receiver has gone away
+   pub async fn send(&self, item: T) -> Result<(), Closed> {
+      let mut item = Some(item);
+      loop {
+         {
+            let mut state = self.0.lock();
+            if state.receiver_closed { return Err(Closed); }
+
+            let full = self.0.capacity.is_some_and(|capacity| state.queue.len() >= capacity);
+            match (full, self.0.overflow) {
+               (true, Overflow::Block) => {},
+               (true, Overflow::DropNewest) => return Ok(()),
+               (true, Overflow::DropOldest) => { state.queue.pop_front(); },
+               (false, _) => {}
+            }
+            if !full || self.0.overflow == Overflow::DropOldest {
+               state.queue.extend(item.take());
+               drop(state);
+               self.0.readable.notify();
+               return Ok(());
+            }
+         }
+         self.0.writable.notified().await;
+      }
+   }
+}
+impl<T> Drop for Sender<T> {
+   fn drop(&mut self) {
+      self.0.lock().sender_closed = true;
+      self.0.readable.notify();
+   }
+}
+
+#[derive(Debug)]
+pub struct Receiver<T>(Arc<Shared<T>>);
+impl<T> Receiver<T> {
+   /// Waits for the next item, or `None` once the sender has gone away &
+   /// everything it sent has been read.
+   pub async fn recv(&self) -> Option<T> {
+      loop {
+         {
+            let mut state = self.0.lock();
+            if let Some(item) = state.queue.pop_front() {
+               drop(state);
+               self.0.writable.notify();
+               return Some(item);
+            }
+            if state.sender_closed { return None; }
+         }
+         self.0.readable.notified().await;
+      }
+   }
+
+   pub fn into_stream(self) -> BoxStream<'static, T> where T: Send + 'static {
+      stream::unfold(self, |receiver| async move {
+         let item = receiver.recv().await;
+         item.map(|item| (item, receiver))
+      }).boxed()
+   }
+}
+impl<T> Drop for Receiver<T> {
+   fn drop(&mut self) {
+      self.0.lock().receiver_closed = true;
+      self.0.writable.notify();
+   }
+}
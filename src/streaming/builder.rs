@@ -0,0 +1,291 @@
+use reqwest::header::{ HeaderName, HeaderValue };
+use snafu::ensure;
+use std::future::Future;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::runtime::Handle;
+
+use crate::{ error, Result, TradingSession };
+use crate::config::{ self, Endpoints, RetryPolicy };
+use super::{ Overflow, Recorder, StreamEvent, Streamer, SubscriptionEvent, TlsConnector };
+
+pub(super) type Observer = Arc<dyn Fn(&StreamEvent) + Send + Sync>;
+pub(super) type SubscriptionObserver = Arc<dyn Fn(&SubscriptionEvent) + Send + Sync>;
+
+/// How a `Streamer` connects & hands out its quotes
+#[derive(Clone, Default)]
+pub(super) struct Options {
+   /// The websocket to connect to, instead of the configured endpoint
+   pub url: Option<String>,
+
+   /// The servers to make every call against, instead of the configured ones
+   pub endpoints: Option<Endpoints>,
+
+   /// Sent with the handshake on top of the configured headers
+   pub headers: Vec<(String, String)>,
+
+   /// Sets up TLS instead of the defaults
+   pub tls: Option<TlsConnector>,
+
+   pub reconnect: RetryPolicy,
+   pub observer: Option<Observer>,
+   pub subscription_observer: Option<SubscriptionObserver>,
+   pub conflation: Option<Duration>,
+   pub watchdog: Option<Duration>,
+   pub buffer: Option<(usize, Overflow)>,
+   pub snapshot: bool,
+   pub sessions: Option<Vec<TradingSession>>,
+   pub recorder: Option<Arc<Recorder>>,
+   pub poll: Option<Duration>,
+
+   /// Runs the background tasks instead of the runtime the streamer is used from
+   pub runtime: Option<Handle>
+}
+impl Options {
+   /// Starts a background task on the streamer's runtime
+   pub fn spawn<F>(&self, task: F) where F: Future<Output = ()> + Send + 'static {
+      let task = self.scoped(task);
+      match &self.runtime {
+         Some(runtime) => { runtime.spawn(task); },
+         None => { tokio::spawn(task); }
+      }
+   }
+
+   /// Runs a task on the streamer's runtime, waiting for it to finish.  This
+   /// keeps the calls to Yahoo! off of whatever is waiting.
+   pub async fn run<F>(&self, task: F) -> F::Output where F: Future + Send + 'static, F::Output: Send + 'static {
+      let task = self.scoped(task);
+      match &self.runtime {
+         Some(runtime) => runtime.spawn(task).await.expect("the streamer's task panicked"),
+         None => task.await
+      }
+   }
+
+   /// Keeps a task on the streamer's endpoints, or else those it was started
+   /// with, once it is off on its own
+   fn scoped<F: Future>(&self, task: F) -> impl Future<Output = F::Output> {
+      config::with_endpoints(self.endpoints.clone().unwrap_or_else(config::endpoints), task)
+   }
+}
+
+/// A fluent way to set up a `Streamer` when the defaults don't quite fit.
+/// By default the streamer connects to the configured endpoint, doesn't
+/// reconnect & holds on to every quote until it is read.
+///
+/// # Examples
+///
+/// Reconnect whenever Yahoo! hangs up & keep only the latest quotes for a slow UI:
+///
+/// ```
+/// use std::time::Duration;
+/// use yahoo_finance::{ config::RetryPolicy, Overflow, Streamer };
+///
+/// let streamer = Streamer::builder(vec!["AAPL", "TSLA"])
+///    .reconnect(RetryPolicy::standard())
+///    .conflate(Duration::from_millis(250))
+///    .buffer(100, Overflow::DropOldest)
+///    .on_event(|event| println!("{:?}", event))
+///    .build().unwrap();
+/// ```
+pub struct StreamerBuilder {
+   symbols: Vec<String>,
+   options: Options,
+   record: Option<PathBuf>
+}
+impl StreamerBuilder {
+   pub(super) fn new<I, S>(symbols: I) -> StreamerBuilder where I: IntoIterator<Item = S>, S: Into<String> {
+      StreamerBuilder { symbols: symbols.into_iter().map(Into::into).collect(), options: Options::default(), record: None }
+   }
+
+   /// Connects to another websocket - ie. a relay - instead of the configured endpoint
+   pub fn url(mut self, url: &str) -> StreamerBuilder {
+      self.options.url = Some(url.to_string());
+      self
+   }
+
+   /// Makes every call the streamer makes - the websocket along with the
+   /// chart calls behind snapshots, candles & polling - against other
+   /// servers than the configured endpoints.  A `url` takes precedence for
+   /// the websocket.
+   pub fn endpoints(mut self, endpoints: Endpoints) -> StreamerBuilder {
+      self.options.endpoints = Some(endpoints);
+      self
+   }
+
+   /// Sends an extra header with the handshake, on top of those from
+   /// `config::set_header`.  Replaces a configured header of the same name.
+   pub fn header(mut self, name: &str, value: &str) -> StreamerBuilder {
+      self.options.headers.push((name.to_string(), value.to_string()));
+      self
+   }
+
+   /// Sets up the TLS for the connection, ie. to trust the CA of a proxy that
+   /// intercepts TLS.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// # #[cfg(not(feature = "rustls"))]
+   /// # fn main() {
+   /// use native_tls::{ Certificate, TlsConnector };
+   /// use yahoo_finance::Streamer;
+   ///
+   /// let ca = Certificate::from_pem(&std::fs::read("corporate-ca.pem").unwrap()).unwrap();
+   /// let tls = TlsConnector::builder().add_root_certificate(ca).build().unwrap();
+   /// let streamer = Streamer::builder(vec!["AAPL"]).tls(tls).build().unwrap();
+   /// # }
+   /// # #[cfg(feature = "rustls")]
+   /// # fn main() {}
+   /// ```
+   pub fn tls(mut self, connector: TlsConnector) -> StreamerBuilder {
+      self.options.tls = Some(connector);
+      self
+   }
+
+   /// Sets how to reconnect when Yahoo! drops the connection - which it does
+   /// nightly.  `max_attempts` counts the tries in a row that don't get any
+   /// quotes, so a connection that worked for a while always gets retried.
+   ///
+   /// The default is not to reconnect, ending the stream instead.
+   pub fn reconnect(mut self, policy: RetryPolicy) -> StreamerBuilder {
+      self.options.reconnect = policy;
+      self
+   }
+
+   /// Sends at most one quote per symbol every `interval`, always the latest,
+   /// so busy symbols don't flood slow consumers.
+   pub fn conflate(mut self, interval: Duration) -> StreamerBuilder {
+      self.options.conflation = Some(interval);
+      self
+   }
+
+   /// Watches for the connection or a symbol going quiet for `timeout`,
+   /// sending a `StreamEvent::Stale` when it does.  A quiet connection is
+   /// dropped as dead, reconnecting if the streamer does.
+   ///
+   /// Symbols are quiet outside of their trading hours, so the timeout needs
+   /// to suit the symbols & the time of day.
+   pub fn watchdog(mut self, timeout: Duration) -> StreamerBuilder {
+      self.options.watchdog = Some(timeout);
+      self
+   }
+
+   /// Holds at most `capacity` quotes for a consumer that falls behind,
+   /// dealing with the rest by `overflow`.  Without this quotes are held
+   /// until they are read, however many that is.
+   pub fn buffer(mut self, capacity: usize, overflow: Overflow) -> StreamerBuilder {
+      self.options.buffer = Some((capacity, overflow));
+      self
+   }
+
+   /// Starts the quote stream with the latest regular session quote for each
+   /// symbol, so there is something to show while the markets are quiet.
+   /// The snapshots come from the charts the streamer loads anyway, so a
+   /// symbol that can't be loaded simply waits for its first live quote.
+   pub fn snapshot(mut self) -> StreamerBuilder {
+      self.options.snapshot = true;
+      self
+   }
+
+   /// Only streams quotes from the given trading sessions, ie. to leave out
+   /// the thin pre market & after hours trading.  Applies to the quotes from
+   /// `stream` & `broadcast`, not to the raw pricing data.
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use yahoo_finance::{ Streamer, TradingSession };
+   ///
+   /// let streamer = Streamer::builder(vec!["AAPL"])
+   ///    .sessions(vec![TradingSession::Regular])
+   ///    .build().unwrap();
+   /// ```
+   pub fn sessions(mut self, sessions: Vec<TradingSession>) -> StreamerBuilder {
+      self.options.sessions = Some(sessions);
+      self
+   }
+
+   /// Records every frame received to a file, to play back later with
+   /// [`Replay`](struct.Replay.html).  The file is replaced if it exists.
+   pub fn record(mut self, path: impl Into<PathBuf>) -> StreamerBuilder {
+      self.record = Some(path.into());
+      self
+   }
+
+   /// Polls Yahoo!'s quote endpoint every `interval` instead of streaming,
+   /// for networks that block websockets.  The quotes come through the same
+   /// way, although only as often as polled & without the frames to record
+   /// or the watchdog to look after them.
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use std::time::Duration;
+   /// use yahoo_finance::Streamer;
+   ///
+   /// let streamer = Streamer::builder(vec!["AAPL"]).poll(Duration::from_secs(5)).build().unwrap();
+   /// ```
+   pub fn poll(mut self, interval: Duration) -> StreamerBuilder {
+      self.options.poll = Some(interval);
+      self
+   }
+
+   /// Runs the streamer's background tasks - & its calls to Yahoo! - on the
+   /// given tokio runtime.  The quotes can then be read from anywhere, ie. a
+   /// different runtime or executor, or one without tokio's IO & timers.
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use tokio::runtime::Runtime;
+   /// use yahoo_finance::Streamer;
+   ///
+   /// let runtime = Runtime::new().unwrap();
+   /// let streamer = Streamer::builder(vec!["AAPL"]).runtime(runtime.handle().clone()).build().unwrap();
+   /// ```
+   pub fn runtime(mut self, handle: Handle) -> StreamerBuilder {
+      self.options.runtime = Some(handle);
+      self
+   }
+
+   /// Sets a callback told about disconnects & reconnects
+   pub fn on_event(mut self, observer: impl Fn(&StreamEvent) + Send + Sync + 'static) -> StreamerBuilder {
+      self.options.observer = Some(Arc::new(observer));
+      self
+   }
+
+   /// Sets a callback told which symbols Yahoo! acknowledged or turned down
+   /// & which ones have started streaming
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use yahoo_finance::{ Streamer, SubscriptionEvent };
+   ///
+   /// let streamer = Streamer::builder(vec!["AAPL", "FUBAR"])
+   ///    .on_subscription(|event| match event {
+   ///       SubscriptionEvent::Rejected { symbols, reason } => eprintln!("{:?} turned down - {}", symbols, reason),
+   ///       SubscriptionEvent::Live { symbol } => println!("{} is streaming", symbol),
+   ///       _ => {}
+   ///    })
+   ///    .build().unwrap();
+   /// ```
+   pub fn on_subscription(mut self, observer: impl Fn(&SubscriptionEvent) + Send + Sync + 'static) -> StreamerBuilder {
+      self.options.subscription_observer = Some(Arc::new(observer));
+      self
+   }
+
+   /// Creates the streamer, failing if any of the headers can't be sent or
+   /// the recording can't be created
+   pub fn build(mut self) -> Result<Streamer> {
+      for (name, value) in &self.options.headers {
+         ensure!(
+            HeaderName::from_bytes(name.as_bytes()).is_ok() && HeaderValue::from_str(value).is_ok(),
+            error::InvalidHeader { name }
+         );
+      }
+      if let Some(path) = &self.record { self.options.recorder = Some(Arc::new(Recorder::create(path)?)); }
+      Ok(Streamer::with_options(self.symbols, self.options))
+   }
+}
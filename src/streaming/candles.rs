@@ -0,0 +1,265 @@
+use chrono::Utc;
+use futures::{ future, stream, Stream, StreamExt };
+use serde::{ Deserialize, Serialize };
+use snafu::ensure;
+use std::collections::HashMap;
+use std::mem;
+use std::time::{ Duration, Instant };
+use tokio::time;
+
+use crate::{ error, Bar, Interval, Result, Timestamped, TradingSession };
+use crate::history::SessionBar;
+use super::Quote;
+
+/// How often an aggregated stream checks for candles whose interval has ended
+const TICK: Duration = Duration::from_secs(1);
+
+/// A finished candle for a symbol
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Candle {
+   pub symbol: String,
+
+   #[serde(with = "crate::remote::TradingSessionDef")]
+   pub session: TradingSession,
+
+   /// The candle, timestamped with the start of its interval
+   #[serde(with = "crate::remote::BarDef")]
+   pub bar: Bar
+}
+impl Timestamped for Candle {
+   fn timestamp_millis(&self) -> i64 { self.bar.timestamp }
+}
+
+/// A candle still taking quotes
+#[derive(Clone, Debug)]
+struct Building {
+   session: TradingSession,
+   bar: Bar,
+
+   /// The day's volume before the candle started & as of the latest quote.
+   /// A candle picked up from the history works out the volume before it
+   /// from its first quote.
+   volume_before: Option<u64>,
+   volume: u64
+}
+
+/// Builds candles (ie. 1 or 5 minute bars) out of streamed quotes.
+///
+/// A candle is finished by the first quote for its symbol in a later
+/// interval or a different trading session, so a pre market candle never
+/// runs into the open.  When aggregating a stream, candles are also finished
+/// once the end of their interval has passed - without waiting on a quote
+/// that may be a long time coming for a quiet symbol.  Intervals line up with the clock in UTC, unless
+/// lined up with the symbol's session (see [`align`](#method.align)) or
+/// picking up from a bar out of the history.  Quotes arriving late for a
+/// candle that has already been finished are left out.
+///
+/// Yahoo! streams the day's volume, so each candle gets the volume traded
+/// since the one before it.  The very first candle for a symbol only counts
+/// the volume from its first quote on.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use yahoo_finance::{ CandleAggregator, Interval, Streamer };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let streamer = Streamer::new(vec!["AAPL", "MSFT"]);
+///    let candles = CandleAggregator::new(Interval::_1m).unwrap();
+///
+///    candles.aggregate(streamer.stream().await)
+///       .for_each(|candle| {
+///          println!("{} {:?} closed at {}", candle.symbol, candle.session, candle.bar.close);
+///          future::ready(())
+///       })
+///       .await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct CandleAggregator {
+   /// The length of each candle in milliseconds
+   width: i64,
+
+   /// Where the intervals start, per symbol.  Symbols without one line up
+   /// with the clock in UTC.
+   offsets: HashMap<String, i64>,
+
+   building: HashMap<String, Building>,
+
+   /// The start of the last finished candle, per symbol
+   finished: HashMap<String, i64>,
+
+   /// The day's volume as of the last finished candle, per symbol
+   volumes: HashMap<String, u64>
+}
+impl CandleAggregator {
+   /// Builds candles over an intraday interval
+   pub fn new(interval: Interval) -> Result<CandleAggregator> {
+      ensure!(interval.is_intraday(), error::NotIntraday { interval });
+
+      let minutes = match interval {
+         Interval::_1m => 1,
+         Interval::_2m => 2,
+         Interval::_5m => 5,
+         Interval::_15m => 15,
+         Interval::_30m => 30,
+         Interval::_60m => 60,
+         _ => 90
+      };
+      Ok(CandleAggregator { width: minutes * 60_000, offsets: HashMap::new(), building: HashMap::new(), finished: HashMap::new(), volumes: HashMap::new() })
+   }
+
+   /// Adds a quote, handing back the candle it finished - if any
+   pub fn push(&mut self, quote: &Quote) -> Option<Candle> {
+      let offset = self.offsets.get(&quote.symbol).copied().unwrap_or_default();
+      let start = quote.timestamp - (quote.timestamp - offset).rem_euclid(self.width);
+
+      let finished = match self.building.get(&quote.symbol) {
+         Some(current) if start < current.bar.timestamp => return None,
+         None if self.finished.get(&quote.symbol).is_some_and(|&until| start <= until) => return None,
+         Some(current) if current.bar.timestamp == start && mem::discriminant(&current.session) == mem::discriminant(&quote.session) => None,
+         Some(_) => self.finish(&quote.symbol),
+         None => None
+      };
+
+      match self.building.get_mut(&quote.symbol) {
+         Some(current) => {
+            current.bar.high = current.bar.high.max(quote.price);
+            current.bar.low = current.bar.low.min(quote.price);
+            current.bar.close = quote.price;
+            current.volume_before.get_or_insert(quote.volume.saturating_sub(current.bar.volume.unwrap_or_default()));
+            current.volume = quote.volume;
+         },
+         None => {
+            // the day's volume going down means a new day
+            let volume_before = match self.volumes.get(&quote.symbol) {
+               Some(&volume) if volume <= quote.volume => volume,
+               _ => quote.volume
+            };
+            let bar = Bar { timestamp: start, open: quote.price, high: quote.price, low: quote.price, close: quote.price, volume: None };
+            self.building.insert(quote.symbol.clone(), Building { session: quote.session, bar, volume_before: Some(volume_before), volume: quote.volume });
+         }
+      }
+      finished
+   }
+
+   /// Lines the intervals for a symbol up with the start of its trading
+   /// session (or any bar out of its history), in milliseconds since the
+   /// EPOCH - ie. so hourly candles for US stocks start at half past.
+   pub fn align(&mut self, symbol: &str, session_start: i64) {
+      self.offsets.insert(symbol.to_string(), session_start.rem_euclid(self.width));
+   }
+
+   /// Picks up building a candle from a bar out of the history, ie. the
+   /// latest one while it is still trading.  The symbol's intervals line up
+   /// with the bar from then on, since Yahoo! starts them with the session.
+   pub fn seed(&mut self, symbol: &str, session: TradingSession, bar: Bar) {
+      self.align(symbol, bar.timestamp);
+      self.building.insert(symbol.to_string(), Building { session, volume: 0, volume_before: None, bar });
+   }
+
+   /// Finishes the candles whose interval has ended by `now`, in milliseconds
+   /// since the EPOCH.  Quotes for those intervals coming in afterwards are
+   /// left out.
+   pub fn tick(&mut self, now: i64) -> Vec<Candle> {
+      let width = self.width;
+      let mut symbols = self.building.iter()
+         .filter(|(_, building)| building.bar.timestamp + width <= now)
+         .map(|(symbol, _)| symbol.clone())
+         .collect::<Vec<_>>();
+      symbols.sort();
+      symbols.iter().filter_map(|symbol| self.finish(symbol)).collect()
+   }
+
+   /// Finishes every candle still being built, ie. once the stream has ended
+   pub fn flush(&mut self) -> Vec<Candle> {
+      let mut symbols = self.building.keys().cloned().collect::<Vec<_>>();
+      symbols.sort();
+      symbols.iter().filter_map(|symbol| self.finish(symbol)).collect()
+   }
+
+   /// Turns a stream of quotes into a stream of finished candles.  Quotes
+   /// that couldn't be read are skipped & the candles still being built are
+   /// finished when the quotes end.
+   ///
+   /// Candles are finished as their interval ends going by the time of the
+   /// latest quote, moved along by the clock - so replayed quotes make the
+   /// same candles as live ones.
+   pub fn aggregate<S>(self, quotes: S) -> impl Stream<Item = Candle>
+      where S: Stream<Item = Result<Quote>> + Unpin {
+      let ticks = time::interval_at(time::Instant::now() + TICK, TICK);
+      let clock: Option<(i64, Instant)> = None;
+
+      stream::unfold((self, Some(quotes), Vec::new(), ticks, clock), |(mut candles, mut quotes, mut flushed, mut ticks, mut clock)| async move {
+         loop {
+            if let Some(candle) = flushed.pop() { return Some((candle, (candles, quotes, flushed, ticks, clock))); }
+
+            let source = quotes.as_mut()?;
+            tokio::select! {
+               next = source.next() => match next {
+                  Some(Ok(quote)) => {
+                     if clock.is_none_or(|(latest, _)| quote.timestamp > latest) { clock = Some((quote.timestamp, Instant::now())); }
+                     if let Some(candle) = candles.push(&quote) { return Some((candle, (candles, quotes, flushed, ticks, clock))); }
+                  },
+                  Some(Err(_)) => {},
+                  None => {
+                     quotes = None;
+                     flushed = candles.flush();
+                     flushed.reverse();
+                  }
+               },
+               _ = ticks.tick() => {
+                  let now = match clock {
+                     Some((latest, seen)) => latest + seen.elapsed().as_millis() as i64,
+                     None => Utc::now().timestamp_millis()
+                  };
+                  flushed = candles.tick(now);
+                  flushed.reverse();
+               }
+            }
+         }
+      })
+   }
+
+   /// Starts with the history loaded for the symbols before carrying on
+   /// with candles built from the quotes.  The latest bar is built on by the
+   /// quotes while it is still trading, & nothing the history already covers
+   /// comes through again.
+   pub(super) fn backfill<S>(mut self, histories: Vec<(String, Result<Vec<SessionBar>>)>, quotes: S) -> Result<impl Stream<Item = Candle>>
+      where S: Stream<Item = Result<Quote>> + Unpin {
+      let now = Utc::now().timestamp_millis();
+      let mut candles = Vec::new();
+      let mut covered = HashMap::new();
+      for (symbol, bars) in histories {
+         let mut bars = bars?;
+         if let Some(latest) = bars.pop() {
+            self.align(&symbol, latest.bar.timestamp);
+            if latest.bar.timestamp + self.width > now {
+               self.seed(&symbol, latest.session, latest.bar);
+            } else {
+               covered.insert(symbol.clone(), latest.bar.timestamp);
+               bars.push(latest);
+            }
+         }
+         candles.extend(bars.into_iter().map(|bar| Candle { symbol: symbol.clone(), session: bar.session, bar: bar.bar }));
+      }
+
+      let live = self.aggregate(quotes).filter(move |candle| future::ready(covered.get(&candle.symbol).is_none_or(|&until| candle.bar.timestamp > until)));
+      Ok(stream::iter(candles).chain(live))
+   }
+
+   fn finish(&mut self, symbol: &str) -> Option<Candle> {
+      let mut building = self.building.remove(symbol)?;
+      self.finished.insert(symbol.to_string(), building.bar.timestamp);
+      if building.volume_before.is_some() { self.volumes.insert(symbol.to_string(), building.volume); }
+
+      building.bar.volume = match building.volume_before {
+         Some(before) if building.volume > 0 => Some(building.volume.saturating_sub(before)),
+         Some(_) => None,
+         None => building.bar.volume
+      };
+      Some(Candle { symbol: symbol.to_string(), session: building.session, bar: building.bar })
+   }
+}
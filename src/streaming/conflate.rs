@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::{ self, Instant };
+
+use crate::Result;
+use super::buffer::{ Receiver, Sender };
+use super::Quote;
+
+/// Lets through at most one quote per symbol every `interval`, always keeping
+/// the latest.  The first quote for a symbol goes straight out, while later
+/// ones wait for the rest of the interval - replacing each other as they come.
+///
+/// Errors aren't held back.
+pub async fn conflate(quotes: Receiver<Result<Quote>>, interval: Duration, tx: Sender<Result<Quote>>) {
+   let mut sent: HashMap<String, Instant> = HashMap::new();
+   let mut pending: HashMap<String, Quote> = HashMap::new();
+
+   loop {
+      // the next time a held back quote is due
+      let due = pending.keys().filter_map(|symbol| sent.get(symbol)).min().map(|at| *at + interval);
+      let waiting = async {
+         match due {
+            Some(due) => time::delay_until(due).await,
+            None => futures::future::pending().await
+         }
+      };
+
+      tokio::select! {
+         quote = quotes.recv() => match quote {
+            Some(Ok(quote)) => {
+               let now = Instant::now();
+               match sent.get(&quote.symbol) {
+                  Some(at) if now < *at + interval => { pending.insert(quote.symbol.clone(), quote); },
+                  _ => {
+                     sent.insert(quote.symbol.clone(), now);
+                     if tx.send(Ok(quote)).await.is_err() { break; }
+                  }
+               }
+            },
+            Some(Err(e)) => if tx.send(Err(e)).await.is_err() { break; },
+            None => break
+         },
+         _ = waiting => {
+            let now = Instant::now();
+            let ready = pending.keys().filter(|symbol| sent.get(*symbol).is_none_or(|at| now >= *at + interval)).cloned().collect::<Vec<_>>();
+            for symbol in ready {
+               if let Some(quote) = pending.remove(&symbol) {
+                  sent.insert(symbol, now);
+                  let _ = tx.send(Ok(quote)).await;
+               }
+            }
+         }
+      }
+   }
+
+   // don't lose the latest quotes when the stream ends
+   let mut held = pending.into_iter().collect::<Vec<_>>();
+   held.sort_by_key(|(symbol, _)| sent.get(symbol).copied());
+   for (_, quote) in held { let _ = tx.send(Ok(quote)).await; }
+}
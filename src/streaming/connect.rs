@@ -0,0 +1,61 @@
+use tokio::net::TcpStream;
+use tokio_tungstenite::WebSocketStream;
+use tokio_tungstenite::tungstenite::{ error::Error, handshake::client::{ Request, Response } };
+
+#[cfg(not(feature = "rustls"))]
+use tokio::io::{ AsyncRead, AsyncWrite };
+#[cfg(not(feature = "rustls"))]
+use tokio_tungstenite::client_async_tls_with_config;
+
+#[cfg(feature = "rustls")]
+use std::sync::Arc;
+#[cfg(feature = "rustls")]
+use tokio_rustls::{ client::TlsStream, rustls::ClientConfig, webpki::DNSNameRef };
+#[cfg(feature = "rustls")]
+use tokio_tungstenite::{ client_async, stream::Stream };
+
+/// Sets up the TLS for the websocket, ie. to trust a corporate CA that
+/// intercepts TLS.  This is the `native_tls::TlsConnector` from the
+/// `native-tls` crate.
+#[cfg(not(feature = "rustls"))]
+pub type TlsConnector = tokio_tungstenite::TlsConnector;
+
+/// Sets up the TLS for the websocket, ie. to trust a corporate CA that
+/// intercepts TLS.  This is the client configuration from the `rustls` crate.
+#[cfg(feature = "rustls")]
+pub type TlsConnector = Arc<ClientConfig>;
+
+/// Opens the websocket, with the platform's TLS unless given a connector
+#[cfg(not(feature = "rustls"))]
+pub async fn connect(request: Request, tls: Option<&TlsConnector>) -> Result<(WebSocketStream<impl AsyncRead + AsyncWrite + Send + Unpin>, Response), Error> {
+   let (socket, _) = open(&request).await?;
+   client_async_tls_with_config(request, socket, None, tls.cloned()).await
+}
+
+/// Opens the websocket over rustls - since tokio-tungstenite only knows
+/// native-tls - trusting the webpki roots unless given a connector.
+#[cfg(feature = "rustls")]
+pub async fn connect(request: Request, tls: Option<&TlsConnector>) -> Result<(WebSocketStream<Stream<TcpStream, TlsStream<TcpStream>>>, Response), Error> {
+   let (socket, secure) = open(&request).await?;
+   if !secure { return client_async(request, Stream::Plain(socket)).await; }
+
+   let config = tls.cloned().unwrap_or_else(|| {
+      let mut config = ClientConfig::new();
+      config.root_store.add_server_trust_anchors(&webpki_roots::TLS_SERVER_ROOTS);
+      Arc::new(config)
+   });
+   let host = request.uri().host().unwrap_or_default();
+   let domain = DNSNameRef::try_from_ascii_str(host).map_err(|_| Error::Url("invalid host".into()))?;
+   let socket = tokio_rustls::TlsConnector::from(config).connect(domain, socket).await?;
+   client_async(request, Stream::Tls(socket)).await
+}
+
+/// Connects to the host in the request, noting whether it wants TLS
+async fn open(request: &Request) -> Result<(TcpStream, bool), Error> {
+   let uri = request.uri();
+   let host = uri.host().ok_or_else(|| Error::Url("missing host".into()))?;
+   let secure = uri.scheme_str() == Some("wss") || uri.scheme_str() == Some("https");
+   let port = uri.port_u16().unwrap_or(if secure { 443 } else { 80 });
+
+   Ok((TcpStream::connect((host, port)).await?, secure))
+}
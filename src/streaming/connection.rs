@@ -0,0 +1,172 @@
+use futures::{ future, SinkExt, StreamExt };
+use std::collections::HashSet;
+use std::sync::Arc;
+use std::time::{ Duration, Instant };
+use tokio::sync::mpsc;
+use tokio::time;
+use tokio_tungstenite::tungstenite::{ http::Request, protocol::Message };
+
+use crate::{ config, Result };
+use crate::metrics::{ self, Operation };
+use crate::yahoo::PricingData;
+use super::{ buffer::Sender, builder::Options, frame, Shared, StreamEvent, SubscriptionEvent };
+use super::watchdog::Watchdog;
+use super::connect::connect;
+
+/// How often we ping Yahoo! to keep a quiet connection open
+const PING_INTERVAL: Duration = Duration::from_secs(30);
+
+/// How a connection came to an end
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Ended {
+   /// We were asked to stop, or nobody is reading the quotes anymore
+   Stopped,
+
+   /// The connection went away - or never came up - noting whether any
+   /// quotes came through so a connection that works can be told apart from
+   /// one that fails straight away.
+   Lost { connected: bool, received: bool }
+}
+
+/// Runs a single connection to Yahoo!, sending everything it receives to
+/// `out` - after `convert`ing it, leaving out whatever `convert` turns into
+/// `None` - until the connection goes away.  Tells
+/// `notify` about a successful reconnect & about anything going quiet.
+pub async fn run<T, F, N>(shared: &Arc<Shared>, options: &Options, out: &Sender<Result<T>>, convert: &mut F, notify: &N, reconnecting: bool) -> Ended
+   where F: FnMut(PricingData) -> Option<T>, N: Fn(StreamEvent) {
+   let (tx, mut rx) = mpsc::unbounded_channel();
+
+   // the handshake goes out with the same headers as every other call, plus our own
+   //
+   // permessage-deflate is deliberately not offered - tungstenite 0.11 has no
+   // extension support & rejects any frame with RSV1 set (how compressed
+   // frames are marked) as a protocol error, so every compressed frame from
+   // Yahoo would end the connection.  Compression has to wait for a
+   // tungstenite with the `deflate` feature, which needs the move off tokio 0.2.
+   let url = options.url.clone().unwrap_or_else(|| config::endpoints().streaming);
+   let mut headers = config::headers();
+   headers.retain(|(name, _)| !options.headers.iter().any(|(own, _)| own.eq_ignore_ascii_case(name)));
+   headers.extend(options.headers.iter().cloned());
+
+   let mut request = Request::builder().uri(url.as_str());
+   for (name, value) in headers { request = request.header(name.as_str(), value.as_str()); }
+   let request = match request.body(()) {
+      Ok(request) => request,
+      Err(e) => { let _ = out.send(Err(frame::failed(e.into()))).await; return Ended::Lost { connected: false, received: false }; }
+   };
+
+   let started = Instant::now();
+   let (stream, response) = match connect(request, options.tls.as_ref()).await {
+      Ok(connection) => connection,
+      Err(e) => { let _ = out.send(Err(frame::failed(e))).await; return Ended::Lost { connected: false, received: false }; }
+   };
+   metrics::notify(Operation::Connect, &url, Some(response.status().as_u16()), 0, started.elapsed());
+   if reconnecting {
+      shared.stats.reconnected();
+      notify(StreamEvent::Reconnected);
+   }
+   let (mut sink, mut source) = stream.split();
+
+   // send the symbols we are interested in streaming
+   let subscription = shared.subs.connected(tx.clone());
+
+   // send out subscription changes, pongs & our own pings until the connection goes away
+   let sender_shared = shared.clone();
+   options.spawn(async move {
+      let mut pings = time::interval_at(time::Instant::now() + PING_INTERVAL, PING_INTERVAL);
+      loop {
+         let msg = tokio::select! {
+            _ = sender_shared.stopped() => { let _ = sink.close().await; break; },
+            _ = pings.tick() => Message::Ping(Vec::new()),
+            msg = rx.recv() => match msg {
+               Some(msg) => msg,
+               None => break
+            }
+         };
+         if sink.send(msg).await.is_err() { break; }
+      }
+   });
+
+   let mut watchdog = options.watchdog.map(|timeout| Watchdog::new(timeout, &shared.subs.symbols()));
+   let subscribed = |event: SubscriptionEvent| if let Some(observer) = &options.subscription_observer { observer(&event) };
+   let ended = async {
+      let mut received = false;
+      let mut live = HashSet::new();
+      loop {
+         let deadline = watchdog.as_ref().map(Watchdog::deadline);
+         let quiet = async {
+            match deadline {
+               Some(deadline) => time::delay_until(deadline).await,
+               None => future::pending().await
+            }
+         };
+
+         let next = tokio::select! {
+            _ = shared.stopped() => return Ended::Stopped,
+            _ = quiet => {
+               // nothing came in time - drop a silent connection, but only report quiet symbols
+               if let Some(watchdog) = &mut watchdog {
+                  let silence = watchdog.check(&shared.subs.symbols());
+                  if silence.connection {
+                     notify(StreamEvent::Stale { symbol: None });
+                     break;
+                  }
+                  for symbol in silence.symbols { notify(StreamEvent::Stale { symbol: Some(symbol) }); }
+               }
+               continue;
+            },
+            next = source.next() => next
+         };
+         let msg = match next {
+            Some(msg) => msg,
+            None => break
+         };
+
+         let msg = match msg {
+            Ok(msg) => msg,
+            Err(e) => { let _ = out.send(Err(frame::failed(e))).await; break; }
+         };
+         match &msg {
+            Message::Ping(payload) => { let _ = tx.send(Message::Pong(payload.clone())); },
+            Message::Close(_) => break,
+            _ => {}
+         }
+         if let Some(event) = frame::control(&msg) {
+            if let Some(watchdog) = &mut watchdog { watchdog.heard(None); }
+            subscribed(event);
+            continue;
+         }
+         if let Some(recorder) = &options.recorder { recorder.record(&msg); }
+         if let Some(size) = frame::size(&msg) {
+            metrics::notify(Operation::Message, &url, None, size, Duration::default());
+         }
+
+         let data = frame::decode(&msg);
+         if let Some(data) = &data { shared.stats.message(data.is_ok()); }
+         let data = match data {
+            Some(Ok(data)) => {
+               received = true;
+               if let Some(watchdog) = &mut watchdog { watchdog.heard(Some(&data.id)); }
+               if live.insert(data.id.clone()) { subscribed(SubscriptionEvent::Live { symbol: data.id.clone() }); }
+               match convert(data) {
+                  Some(converted) => Ok(converted),
+                  None => continue
+               }
+            },
+            Some(Err(e)) => Err(e),
+            None => {
+               if let Some(watchdog) = &mut watchdog { watchdog.heard(None); }
+               continue
+            }
+         };
+
+         // nobody is listening anymore
+         if out.send(data).await.is_err() { return Ended::Stopped; }
+      }
+      Ended::Lost { connected: true, received }
+   }.await;
+
+   shared.subs.disconnected(subscription);
+   if let Some(recorder) = &options.recorder { recorder.flush().await; }
+   ended
+}
@@ -0,0 +1,70 @@
+use serde_json::Value;
+use snafu::IntoError;
+use tokio_tungstenite::tungstenite::{ self, protocol::Message };
+
+use crate::{ error, Result };
+use crate::yahoo::PricingData;
+use super::SubscriptionEvent;
+
+/// Reads the pricing data out of a websocket message.  Yahoo! sends each
+/// update as base64 encoded protobuf, as either a text or a binary message.
+///
+/// Messages that don't carry data (ie. pings) are `None`.
+pub fn decode(message: &Message) -> Option<Result<PricingData>> {
+   payload(message).map(parse)
+}
+
+/// Reads what Yahoo! says about the subscriptions out of a control message,
+/// which comes as a JSON object rather than base64 encoded data - either
+/// `{"subscribed":["AAPL"]}` or `{"error":{"message":"Unknown symbol"},"symbols":["FUBAR"]}`.
+///
+/// Anything else is `None`, so it gets reported like any other frame we can't read.
+pub fn control(message: &Message) -> Option<SubscriptionEvent> {
+   let value = match message {
+      Message::Text(text) if text.trim_start().starts_with('{') => serde_json::from_str::<Value>(text).ok()?,
+      _ => return None
+   };
+
+   match (value.get("subscribed"), value.get("error"), value.get("symbols")) {
+      (Some(symbols), None, None) => Some(SubscriptionEvent::Acknowledged { symbols: symbol_list(symbols)? }),
+      (None, Some(error), Some(symbols)) => Some(SubscriptionEvent::Rejected {
+         symbols: symbol_list(symbols)?,
+         reason: error.get("message")?.as_str()?.to_string()
+      }),
+      _ => None
+   }
+}
+
+/// The symbols in a list, which must be nothing but symbols
+fn symbol_list(value: &Value) -> Option<Vec<String>> {
+   value.as_array()?.iter().map(|symbol| symbol.as_str().map(str::to_string)).collect()
+}
+
+/// The size of a message carrying data
+pub fn size(message: &Message) -> Option<u64> {
+   payload(message).map(|payload| payload.len() as u64)
+}
+
+/// The encoded data in a message, if it carries any
+pub fn payload(message: &Message) -> Option<&[u8]> {
+   match message {
+      Message::Text(text) => Some(text.as_bytes()),
+      Message::Binary(data) => Some(data.as_slice()),
+      _ => None
+   }
+}
+
+/// Reads the pricing data out of an encoded frame
+pub fn parse(text: &[u8]) -> Result<PricingData> {
+   let bytes = base64::decode(text).or_else(bad_frame)?;
+   PricingData::parse(&bytes)
+}
+
+fn bad_frame<T>(reason: impl ToString) -> Result<T> {
+   Ok(error::BadFrame { reason: reason.to_string() }.fail()?)
+}
+
+/// Turns a websocket failure into our own error
+pub fn failed(source: tungstenite::Error) -> crate::Error {
+   error::StreamFailed.into_error(source).into()
+}
@@ -0,0 +1,451 @@
+use futures::{ future, stream, Stream, StreamExt };
+use std::collections::HashMap;
+use std::sync::{ Arc, Mutex };
+use std::time::Duration;
+use tokio::sync::watch;
+
+use crate::{ history, many, yahoo, Interval, Result };
+pub use crate::yahoo::{ pricing_data, PricingData };
+
+mod broadcast;
+pub use broadcast::Broadcast;
+
+mod candles;
+pub use candles::{ Candle, CandleAggregator };
+
+mod builder;
+pub use builder::StreamerBuilder;
+use builder::Options;
+
+mod buffer;
+pub use buffer::Overflow;
+use buffer::Sender;
+
+mod connect;
+pub use connect::TlsConnector;
+
+mod conflate;
+
+mod connection;
+use connection::Ended;
+
+mod frame;
+
+mod recording;
+pub use recording::Replay;
+use recording::Recorder;
+
+mod polling;
+
+mod quote;
+pub use quote::{ OptionQuote, Quote };
+use quote::ChangeTracker;
+
+mod stats;
+pub use stats::StreamStats;
+use stats::Counters;
+
+mod subscriptions;
+pub use subscriptions::SubscriptionEvent;
+use subscriptions::Subscriptions;
+
+mod watchdog;
+
+/// Something that happened to the streaming connection
+#[derive(Debug, Clone, PartialEq)]
+pub enum StreamEvent {
+   /// Yahoo! dropped the connection
+   Disconnected,
+
+   /// About to try connecting again (starting at 1) after waiting `delay`
+   Reconnecting { attempt: u32, delay: Duration },
+
+   /// The connection is back & the symbols have been subscribed again
+   Reconnected,
+
+   /// Nothing arrived for the watchdog's timeout.  A quiet connection
+   /// (`symbol` is `None`) is dropped so it can reconnect, while a quiet
+   /// symbol is reported once until it updates again.
+   Stale { symbol: Option<String> }
+}
+
+/// What the streamer shares with its connections
+struct Shared {
+   subs: Subscriptions,
+   shutdown: watch::Sender<bool>,
+   stopped: watch::Receiver<bool>,
+   stats: Counters,
+
+   /// The latest quote for each symbol, for reading on demand
+   latest: Mutex<HashMap<String, Quote>>
+}
+impl Shared {
+   fn is_shutdown(&self) -> bool { *self.stopped.borrow() }
+
+   fn remember(&self, quote: &Quote) {
+      self.latest.lock().unwrap_or_else(|e| e.into_inner()).insert(quote.symbol.clone(), quote.clone());
+   }
+
+   /// Waits until the streamer is stopped
+   async fn stopped(&self) {
+      let mut stopped = self.stopped.clone();
+      while let Some(stop) = stopped.recv().await {
+         if stop { return; }
+      }
+      future::pending().await
+   }
+}
+
+/// Realtime price quote streamer
+///
+/// To use it:
+/// 1. Create a new streamer with `Streamer::new(vec!["AAPL"])`, or set one up with `Streamer::builder(vec!["AAPL"])`
+/// 1. Start streaming with `streamer.stream().await`
+/// 1. Change the symbols as you go with `streamer.subscribe(vec!["MSFT"])` & `streamer.unsubscribe(vec!["AAPL"])`
+pub struct Streamer {
+   shared: Arc<Shared>,
+   options: Options
+}
+impl Streamer {
+   /// Creates a streamer for any list of symbols - ie. `vec!["AAPL"]`, a
+   /// `Vec<String>` loaded from a config file or an array of `&str`.
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use yahoo_finance::Streamer;
+   ///
+   /// let watchlist: Vec<String> = vec!["AAPL".to_string(), "MSFT".to_string()];
+   /// let streamer = Streamer::new(watchlist);
+   /// let streamer = Streamer::new(["^DJI", "^IXIC"]);
+   /// ```
+   pub fn new<I, S>(symbols: I) -> Streamer where I: IntoIterator<Item = S>, S: Into<String> {
+      Streamer::with_options(symbols.into_iter().map(Into::into).collect(), Options::default())
+   }
+
+   /// Starts setting up a streamer that reconnects, limits its quotes or
+   /// connects somewhere other than the configured endpoint.
+   pub fn builder<I, S>(symbols: I) -> StreamerBuilder where I: IntoIterator<Item = S>, S: Into<String> {
+      StreamerBuilder::new(symbols)
+   }
+
+   fn with_options(symbols: Vec<String>, options: Options) -> Streamer {
+      let (shutdown, stopped) = watch::channel(false);
+      let shared = Shared { subs: Subscriptions::new(symbols), shutdown, stopped, stats: Counters::default(), latest: Mutex::default() };
+      Streamer { shared: Arc::new(shared), options }
+   }
+
+   /// Starts streaming more symbols, without reconnecting if already streaming.
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use yahoo_finance::Streamer;
+   ///
+   /// let streamer = Streamer::new(vec!["AAPL"]);
+   /// streamer.subscribe(vec!["MSFT", "GOOG"]);
+   /// streamer.unsubscribe(vec!["AAPL"]);
+   /// ```
+   pub fn subscribe<I, S>(&self, symbols: I) where I: IntoIterator<Item = S>, S: Into<String> {
+      self.shared.subs.subscribe(symbols.into_iter().map(Into::into).collect());
+   }
+
+   /// Stops streaming some symbols, without reconnecting if already streaming.
+   pub fn unsubscribe<I, S>(&self, symbols: I) where I: IntoIterator<Item = S>, S: Into<String> {
+      let symbols: Vec<String> = symbols.into_iter().map(Into::into).collect();
+      self.shared.latest.lock().unwrap_or_else(|e| e.into_inner()).retain(|symbol, _| !symbols.contains(symbol));
+      self.shared.subs.unsubscribe(symbols);
+   }
+
+   /// Loads the previous close for all of the symbols so we can provide the
+   /// change on the very first quote, along with the snapshot quotes when
+   /// asked for.  Symbols we can't load will pick up the close from the
+   /// stream itself.
+   async fn bootstrap(&self) -> (ChangeTracker, Vec<Quote>) {
+      let symbols = self.shared.subs.symbols();
+      let snapshot = self.options.snapshot;
+
+      self.options.run(async move {
+         let names: Vec<&str> = symbols.iter().map(String::as_str).collect();
+         let mut charts = many::load_many(&names, |symbol| yahoo::load_daily(symbol, Interval::_1d)).await;
+
+         let mut tracker = ChangeTracker::default();
+         let mut snapshots = Vec::new();
+         for symbol in &symbols {
+            if let Some(Ok(data)) = charts.remove(symbol) {
+               tracker.bootstrap(symbol, data.meta.previous_close as f64, data.meta.current_price as f64);
+               if snapshot { snapshots.push(Quote::from_chart(&data.meta, &mut tracker)); }
+            }
+         }
+         (tracker, snapshots)
+      }).await
+   }
+
+   /// Connects to Yahoo! & streams quotes for the symbols.  Quotes that can't
+   /// be read come through as errors without ending the stream.  Losing the
+   /// connection also comes through as an error, after which the stream
+   /// either reconnects (see [`reconnect`](#method.reconnect)) or ends.
+   ///
+   /// When set up with [`snapshot`](struct.StreamerBuilder.html#method.snapshot)
+   /// the stream starts with the latest quote for each symbol.
+   pub async fn stream(&self) -> impl Stream<Item = Result<Quote>> {
+      let (mut changes, snapshots) = self.bootstrap().await;
+      let (capacity, overflow) = self.limits();
+
+      // conflating keeps up with whatever Yahoo! sends, so only its output is limited
+      let (tx, rx) = match self.options.conflation {
+         Some(_) => buffer::channel(None, Overflow::Block),
+         None => buffer::channel(capacity, overflow)
+      };
+      let shared = self.shared.clone();
+      let sessions = self.options.sessions.clone();
+      let in_session = move |quote: &Quote| sessions.as_ref().is_none_or(|sessions| quote.in_session(sessions));
+      let snapshots: Vec<Quote> = snapshots.into_iter().filter(|quote| in_session(quote)).collect();
+      for quote in &snapshots { shared.remember(quote); }
+
+      // quotes outside the sessions are dropped before they can take up room in the buffer or conflation
+      self.supervise(tx, move |data| {
+         let quote = Quote::from_data(data, &mut changes);
+         if !in_session(&quote) { return None; }
+         shared.remember(&quote);
+         Some(quote)
+      });
+
+      let live = match self.options.conflation {
+         Some(interval) => {
+            let (conflated, output) = buffer::channel(capacity, overflow);
+            self.options.spawn(conflate::conflate(rx, interval, conflated));
+            output.into_stream()
+         },
+         None => rx.into_stream()
+      };
+      stream::iter(snapshots.into_iter().map(Ok)).chain(live)
+   }
+
+   /// Streams the pricing data exactly as Yahoo! sends it, for the fields
+   /// that don't make it into a [`Quote`](struct.Quote.html).  Reconnecting
+   /// & buffering work the same as for [`stream`](#method.stream), while
+   /// conflation is left out.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// use futures::{ future, StreamExt };
+   /// use yahoo_finance::Streamer;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL"]);
+   ///    streamer.raw_stream().await
+   ///       .for_each(|data| {
+   ///          if let Ok(data) = data { println!("{} last traded {} shares", data.id, data.last_size); }
+   ///          future::ready(())
+   ///       })
+   ///       .await;
+   /// }
+   /// ```
+   pub async fn raw_stream(&self) -> impl Stream<Item = Result<PricingData>> {
+      let (capacity, overflow) = self.limits();
+      let (tx, rx) = buffer::channel(capacity, overflow);
+      self.supervise(tx, Some);
+      rx.into_stream()
+   }
+
+   /// Streams quotes for the option contracts among the symbols, which are
+   /// subscribed to with their OCC style symbols - ie. 'AAPL240119C00190000'.
+   /// Quotes for anything other than an option are left out.  Reconnecting
+   /// & buffering work the same as for [`stream`](#method.stream), while
+   /// conflation is left out.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// use futures::{ future, StreamExt };
+   /// use yahoo_finance::Streamer;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL240119C00190000", "AAPL240119P00170000"]);
+   ///    streamer.option_stream().await
+   ///       .for_each(|quote| {
+   ///          if let Ok(option) = quote { println!("{:?} ${} is at {}", option.contract.kind, option.contract.strike, option.quote.price); }
+   ///          future::ready(())
+   ///       })
+   ///       .await;
+   /// }
+   /// ```
+   pub async fn option_stream(&self) -> impl Stream<Item = Result<OptionQuote>> {
+      let (mut changes, _) = self.bootstrap().await;
+      let (capacity, overflow) = self.limits();
+      let (tx, rx) = buffer::channel(capacity, overflow);
+      self.supervise(tx, move |data| OptionQuote::from_data(data, &mut changes));
+      rx.into_stream()
+   }
+
+   fn limits(&self) -> (Option<usize>, Overflow) {
+      match self.options.buffer {
+         Some((capacity, overflow)) => (Some(capacity), overflow),
+         None => (None, Overflow::Block)
+      }
+   }
+
+   /// Keeps a connection to Yahoo! going in the background, reconnecting
+   /// according to the policy & sending everything it gets to `tx` - apart
+   /// from whatever `convert` turns into `None`.
+   fn supervise<T, F>(&self, tx: Sender<Result<T>>, mut convert: F)
+      where T: Send + 'static, F: FnMut(PricingData) -> Option<T> + Send + 'static {
+      let shared = self.shared.clone();
+      let options = self.options.clone();
+      let policy = options.reconnect;
+      let observer = options.observer.clone();
+      let notify = move |event: StreamEvent| if let Some(observer) = &observer { observer(&event) };
+
+      self.options.spawn(async move {
+         let mut attempt = 0;
+         loop {
+            let ended = match options.poll {
+               Some(interval) => polling::run(&shared, interval, &tx, &mut convert).await,
+               None => connection::run(&shared, &options, &tx, &mut convert, &notify, attempt > 0).await
+            };
+
+            match ended {
+               Ended::Stopped => break,
+               Ended::Lost { connected, received } => {
+                  if connected { notify(StreamEvent::Disconnected); }
+                  if received { attempt = 0; }
+                  attempt += 1;
+               }
+            }
+            if attempt >= policy.max_attempts || shared.is_shutdown() { break; }
+
+            let delay = policy.backoff(attempt);
+            notify(StreamEvent::Reconnecting { attempt, delay });
+            tokio::select! {
+               _ = shared.stopped() => break,
+               _ = tokio::time::delay_for(delay) => {}
+            }
+         }
+      });
+   }
+
+   /// Streams candles for the symbols, starting with their recent history
+   /// (including the pre & post market) & carrying on with candles built
+   /// from the live quotes - without any gap or overlap in between.
+   ///
+   /// The symbols are those subscribed to when called.  Fails if the
+   /// interval isn't intraday or the history can't be loaded.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// use futures::{ future, StreamExt };
+   /// use yahoo_finance::{ Interval, Streamer };
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL"]);
+   ///    streamer.candles(Interval::_5m).await.unwrap()
+   ///       .for_each(|candle| future::ready(println!("{} closed at {}", candle.symbol, candle.bar.close)))
+   ///       .await;
+   /// }
+   /// ```
+   pub async fn candles(&self, interval: Interval) -> Result<impl Stream<Item = Candle>> {
+      let aggregator = CandleAggregator::new(interval)?;
+
+      // the quotes are already coming in while the history loads, so nothing is missed
+      let quotes = self.stream().await;
+      let symbols = self.shared.subs.symbols();
+      let histories = self.options.run(async move {
+         let names: Vec<&str> = symbols.iter().map(String::as_str).collect();
+         let mut histories = many::load_many(&names, |symbol| history::retrieve_extended(symbol, interval)).await;
+         symbols.iter().filter_map(|symbol| Some((symbol.clone(), histories.remove(symbol)?))).collect::<Vec<_>>()
+      }).await;
+      aggregator.backfill(histories, quotes)
+   }
+
+   /// Connects to Yahoo! & shares the quotes between any number of consumers,
+   /// ie. a UI, a logger & a strategy all running as their own tasks.  Up to
+   /// `capacity` quotes are kept for consumers that fall behind - a capacity
+   /// of 0 is taken as 1.
+   ///
+   /// Quotes that can't be read are left out, as are connection failures.
+   /// Use [`on_event`](#method.on_event) to hear about disconnects.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// use futures::{ future, StreamExt };
+   /// use yahoo_finance::Streamer;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL", "MSFT"]);
+   ///    let quotes = streamer.broadcast(64).await;
+   ///
+   ///    let logger = quotes.subscribe().for_each(|quote| future::ready(println!("{:?}", quote)));
+   ///    let ui = quotes.subscribe().for_each(|quote| future::ready(println!("{} is at {}", quote.symbol, quote.price)));
+   ///    future::join(logger, ui).await;
+   /// }
+   /// ```
+   pub async fn broadcast(&self, capacity: usize) -> Broadcast {
+      let (sender, _) = tokio::sync::broadcast::channel(capacity.max(1));
+      let sender = Arc::new(sender);
+      let handle = Broadcast::new(&sender);
+
+      let mut quotes = self.stream().await;
+      self.options.spawn(async move {
+         while let Some(quote) = quotes.next().await {
+            // nobody listening right now is fine - they may subscribe later
+            if let Ok(quote) = quote { let _ = sender.send(quote); }
+         }
+      });
+      handle
+   }
+
+   /// How the feed has been doing since the streamer was created
+   ///
+   /// # Examples
+   ///
+   /// ```
+   /// use yahoo_finance::Streamer;
+   ///
+   /// let streamer = Streamer::new(vec!["AAPL", "MSFT"]);
+   /// let stats = streamer.stats();
+   /// assert_eq!(2, stats.subscriptions);
+   /// assert_eq!(None, stats.last_message);
+   /// ```
+   pub fn stats(&self) -> StreamStats {
+      self.shared.stats.snapshot(self.shared.subs.symbols().len())
+   }
+
+   /// The latest quote streamed for a symbol, for reading the current price
+   /// on demand rather than from the stream.  `None` until a quote for the
+   /// symbol has been streamed, or once it has been unsubscribed.
+   ///
+   /// # Examples
+   ///
+   /// ```no_run
+   /// use futures::StreamExt;
+   /// use yahoo_finance::Streamer;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let streamer = Streamer::new(vec!["AAPL"]);
+   ///    let mut quotes = streamer.stream().await;
+   ///    tokio::spawn(async move { while quotes.next().await.is_some() {} });
+   ///
+   ///    if let Some(quote) = streamer.latest("AAPL") { println!("AAPL is at {}", quote.price); }
+   /// }
+   /// ```
+   pub fn latest(&self, symbol: &str) -> Option<Quote> {
+      self.shared.latest.lock().unwrap_or_else(|e| e.into_inner()).get(symbol).cloned()
+   }
+
+   /// Stops streaming straight away, closing the connection & ending the streams
+   pub fn stop(&mut self) {
+      let _ = self.shared.shutdown.broadcast(true);
+   }
+}
+impl From<&[&str]> for Streamer {
+   fn from(symbols: &[&str]) -> Streamer { Streamer::new(symbols.iter().copied()) }
+}
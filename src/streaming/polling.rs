@@ -0,0 +1,44 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use crate::{ yahoo, Result };
+use crate::yahoo::PricingData;
+use super::{ buffer::Sender, connection::Ended, Shared };
+
+/// Polls the quote endpoint every `interval` instead of streaming, sending
+/// each quote that has changed since the last poll to `out` - after
+/// `convert`ing it, leaving out whatever `convert` turns into `None` - until the streamer stops.  A failed poll comes through
+/// as an error & polling carries on.
+pub async fn run<T, F>(shared: &Arc<Shared>, interval: Duration, out: &Sender<Result<T>>, convert: &mut F) -> Ended
+   where F: FnMut(PricingData) -> Option<T> {
+   let mut latest = HashMap::new();
+
+   loop {
+      if shared.is_shutdown() { return Ended::Stopped; }
+
+      let symbols = shared.subs.symbols();
+      if !symbols.is_empty() {
+         match yahoo::load_quotes(&symbols.iter().map(String::as_str).collect::<Vec<_>>()).await {
+            Ok(quotes) => for quote in quotes {
+               // an unchanged quote is one we've already sent
+               let data = quote.to_pricing_data();
+               let seen = (data.time, data.price.to_bits(), data.day_volume);
+               if latest.insert(data.id.clone(), seen) == Some(seen) { continue; }
+
+               shared.stats.message(true);
+               let converted = match convert(data) {
+                  Some(converted) => converted,
+                  None => continue
+               };
+               if out.send(Ok(converted)).await.is_err() { return Ended::Stopped; }
+            },
+            Err(e) => if out.send(Err(e)).await.is_err() { return Ended::Stopped; }
+         }
+      }
+      tokio::select! {
+         _ = shared.stopped() => return Ended::Stopped,
+         _ = tokio::time::delay_for(interval) => {}
+      }
+   }
+}
@@ -0,0 +1,262 @@
+use chrono::{ TimeZone, Utc };
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::mem;
+
+use crate::{ Timestamped, TradingSession };
+use crate::symbols::{ Kind, OptionContract, OptionKind };
+use crate::yahoo::{ pricing_data::{ MarketHoursType, OptionType, QuoteType }, Meta, PricingData };
+
+/// A symbol's streamed quote at a period in time
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct Quote {
+   /// The symbol for the quote
+   pub symbol: String,
+
+   /// The kind of security the symbol is - ie. to tell indices & crypto
+   /// apart from stocks in a mixed stream.  `None` when Yahoo doesn't say.
+   pub kind: Option<Kind>,
+
+   /// The timestamp of the quote in millisecond accuracy
+   pub timestamp: i64,
+
+   /// The trading session of the quote - pre market / regular hours / after hours
+   #[serde(with = "crate::remote::TradingSessionDef")]
+   pub session: TradingSession,
+
+   /// The price of the quote
+   pub price: f64,
+
+   /// The volume (daily or transactional) of the symbol
+   pub volume: u64,
+
+   /// The change in price from the previous close - or from the regular
+   /// session close for after hours quotes.  `None` until we know the close.
+   pub change: Option<f64>,
+
+   /// The change as a percentage - ie. 1.5 is 1.5%
+   pub change_percent: Option<f64>,
+
+   /// The best bid, when Yahoo sends one
+   pub bid: Option<f64>,
+
+   /// The best ask, when Yahoo sends one
+   pub ask: Option<f64>,
+
+   /// The highest price of the day so far
+   pub day_high: Option<f64>,
+
+   /// The lowest price of the day so far
+   pub day_low: Option<f64>,
+
+   /// The market capitalization
+   pub market_cap: Option<f64>,
+
+   /// The size of the last trade, when Yahoo sends it
+   pub last_size: Option<u64>,
+
+   /// The volume over the last 24 hours, for crypto currencies
+   pub volume_24h: Option<u64>,
+
+   /// The coins in circulation, for crypto currencies
+   pub circulating_supply: Option<f64>
+}
+impl Quote {
+   /// Reads a quote out of the pricing data, working out the change as we go
+   pub(crate) fn from_data(data: PricingData, changes: &mut ChangeTracker) -> Quote {
+      let mut quote = Quote {
+         symbol: data.id.to_string(),
+         kind: convert_kind(data.quote_type()),
+         timestamp: data.time,
+         session: convert_session(data.market_hours()),
+         price: data.price as f64,
+         volume: data.day_volume as u64,
+         change: None,
+         change_percent: None,
+         bid: sent(data.bid as f64),
+         ask: sent(data.ask as f64),
+         day_high: sent(data.day_high as f64),
+         day_low: sent(data.day_low as f64),
+         market_cap: sent(data.marketcap),
+         last_size: sent_count(data.last_size),
+         volume_24h: sent_count(data.vol_24hr),
+         circulating_supply: sent(data.circulating_supply)
+      };
+      changes.apply(&mut quote, data.previous_close as f64, data.change as f64);
+      quote
+   }
+
+   /// Reads the latest regular session quote out of a chart, to have
+   /// something to show before the stream gets going
+   pub(crate) fn from_chart(meta: &Meta, changes: &mut ChangeTracker) -> Quote {
+      let mut quote = Quote {
+         symbol: meta.symbol.to_string(),
+         kind: if meta.instrument_type.is_empty() { None } else { Some(Kind::from(meta.instrument_type.as_str())) },
+         timestamp: meta.regular_market_time * 1000,
+         session: TradingSession::Regular,
+         price: meta.current_price as f64,
+         volume: meta.regular_market_volume,
+         change: None,
+         change_percent: None,
+         bid: None,
+         ask: None,
+         day_high: meta.regular_market_day_high,
+         day_low: meta.regular_market_day_low,
+         market_cap: None,
+         last_size: None,
+         volume_24h: None,
+         circulating_supply: None
+      };
+      changes.apply(&mut quote, meta.previous_close as f64, 0.0);
+      quote
+   }
+
+   /// Whether the quote is from any of the trading sessions
+   pub(crate) fn in_session(&self, sessions: &[TradingSession]) -> bool {
+      sessions.iter().any(|session| mem::discriminant(session) == mem::discriminant(&self.session))
+   }
+}
+impl Timestamped for Quote {
+   /// Gets the timestamp in millisecond accuracy
+   fn timestamp_millis(&self) -> i64 { self.timestamp }
+}
+impl From<Quote> for market_finance::Quote {
+   fn from(quote: Quote) -> Self {
+      market_finance::Quote {
+         symbol: quote.symbol,
+         timestamp: quote.timestamp,
+         session: quote.session,
+         price: quote.price,
+         volume: quote.volume
+      }
+   }
+}
+
+/// A streamed quote for an option contract
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct OptionQuote {
+   pub contract: OptionContract,
+
+   /// The number of contracts still open, when Yahoo sends it
+   pub open_interest: Option<u64>,
+
+   /// The contract's price, volume & change
+   pub quote: Quote
+}
+impl OptionQuote {
+   /// Reads an option quote out of the pricing data, going by the symbol or
+   /// else the contract details Yahoo sent.  `None` for anything that isn't
+   /// an option.
+   pub(crate) fn from_data(data: PricingData, changes: &mut ChangeTracker) -> Option<OptionQuote> {
+      let contract = match OptionContract::parse(&data.id) {
+         Ok(contract) => contract,
+         Err(_) if data.quote_type() == QuoteType::Option && !data.underlying_symbol.is_empty() => OptionContract {
+            underlying: data.underlying_symbol.to_string(),
+            expiry: Utc.timestamp_opt(data.expire_date, 0).single().unwrap_or_default().date_naive(),
+            kind: match data.options_type() {
+               OptionType::Call => OptionKind::Call,
+               OptionType::Put => OptionKind::Put
+            },
+            strike: data.strike_price as f64
+         },
+         Err(_) => return None
+      };
+      let open_interest = sent_count(data.open_interest);
+      Some(OptionQuote { contract, open_interest, quote: Quote::from_data(data, changes) })
+   }
+}
+impl Timestamped for OptionQuote {
+   fn timestamp_millis(&self) -> i64 { self.quote.timestamp }
+}
+
+fn convert_session(value: MarketHoursType) -> TradingSession {
+   match value {
+      MarketHoursType::PreMarket => TradingSession::PreMarket,
+      MarketHoursType::RegularMarket => TradingSession::Regular,
+      MarketHoursType::PostMarket => TradingSession::AfterHours,
+      _ => TradingSession::Other,
+   }
+}
+
+fn convert_kind(value: QuoteType) -> Option<Kind> {
+   let other = |kind: &str| Some(Kind::Other(kind.to_string()));
+   match value {
+      QuoteType::None => None,
+      QuoteType::Equity => Some(Kind::Equity),
+      QuoteType::Etf => Some(Kind::Etf),
+      QuoteType::Index => Some(Kind::Index),
+      QuoteType::Mutualfund => Some(Kind::MutualFund),
+      QuoteType::Moneymarket => Some(Kind::MoneyMarket),
+      QuoteType::Currency => Some(Kind::Currency),
+      QuoteType::Cryptocurrency => Some(Kind::Crypto),
+      QuoteType::Future => Some(Kind::Future),
+      QuoteType::Option => Some(Kind::Option),
+      QuoteType::Altsymbol => other("ALTSYMBOL"),
+      QuoteType::Heartbeat => other("HEARTBEAT"),
+      QuoteType::Warrant => other("WARRANT"),
+      QuoteType::Bond => other("BOND"),
+      QuoteType::Commodity => other("COMMODITY"),
+      QuoteType::Ecnquote => other("ECNQUOTE"),
+      QuoteType::Indicator => other("INDICATOR"),
+      QuoteType::Industry => other("INDUSTRY")
+   }
+}
+
+/// Yahoo! leaves out the fields it doesn't have, which then read as zero
+fn sent(value: f64) -> Option<f64> {
+   if value != 0.0 { Some(value) } else { None }
+}
+
+fn sent_count(value: i64) -> Option<u64> {
+   if value > 0 { Some(value as u64) } else { None }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct Closes {
+   /// The close of the previous regular session
+   previous: Option<f64>,
+
+   /// The last price seen during today's regular session
+   regular: Option<f64>
+}
+
+/// Keeps track of the closing prices per symbol so we can work out the
+/// change for every quote - regardless of the trading session.
+#[derive(Debug, Default)]
+pub(crate) struct ChangeTracker {
+   closes: HashMap<String, Closes>
+}
+impl ChangeTracker {
+   /// Seeds the previous close for a symbol, along with the latest regular
+   /// session price so after hours quotes have a close to go from when the
+   /// stream starts after the market closes
+   pub fn bootstrap(&mut self, symbol: &str, previous_close: f64, regular_close: f64) {
+      let closes = self.closes.entry(symbol.to_string()).or_default();
+      if previous_close > 0.0 { closes.previous = Some(previous_close); }
+      if regular_close > 0.0 { closes.regular = Some(regular_close); }
+   }
+
+   /// Fills in the change for a quote.  Yahoo's own previous close wins when
+   /// it sends one, otherwise we derive it from the change Yahoo sent during
+   /// the regular session.
+   pub fn apply(&mut self, quote: &mut Quote, previous_close: f64, change: f64) {
+      let closes = self.closes.entry(quote.symbol.clone()).or_default();
+
+      if previous_close > 0.0 {
+         closes.previous = Some(previous_close);
+      } else if closes.previous.is_none() && change != 0.0 {
+         if let TradingSession::Regular = quote.session { closes.previous = Some(quote.price - change); }
+      }
+      if let TradingSession::Regular = quote.session { closes.regular = Some(quote.price); }
+
+      let reference = match quote.session {
+         TradingSession::AfterHours => closes.regular.or(closes.previous),
+         _ => closes.previous
+      };
+      if let Some(reference) = reference {
+         let difference = quote.price - reference;
+         quote.change = Some(difference);
+         quote.change_percent = Some(difference / reference * 100.0);
+      }
+   }
+}
@@ -0,0 +1,160 @@
+use chrono::Utc;
+use futures::{ stream, Stream, StreamExt };
+use snafu::ResultExt;
+use std::fs::{ self, File };
+use std::io::{ BufWriter, Write };
+use std::path::Path;
+use std::sync::mpsc::{ self, RecvTimeoutError };
+use std::thread;
+use std::time::Duration;
+use tokio::sync::oneshot;
+use tokio::time::{ self, Instant };
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+use crate::{ error, Result };
+use crate::yahoo::PricingData;
+use super::{ frame, quote::ChangeTracker, Quote };
+
+/// How often the recording is flushed to disk
+const FLUSH_EVERY: Duration = Duration::from_secs(1);
+
+/// The slowest a recording can be played back
+const MIN_SPEED: f64 = 0.001;
+
+enum Entry {
+   Frame(String),
+   Flush(oneshot::Sender<()>)
+}
+
+/// Writes the frames a streamer receives to a file, one per line along with
+/// when it arrived: `<milliseconds since the epoch>\t<frame>`
+///
+/// The writing happens on a thread of its own so the stream never waits on
+/// the disk.
+pub struct Recorder {
+   entries: mpsc::Sender<Entry>
+}
+impl Recorder {
+   pub fn create(path: &Path) -> Result<Recorder> {
+      let file = File::create(path).context(error::RecordingFailed { path: path.display().to_string() })?;
+      let (entries, queued) = mpsc::channel();
+      thread::spawn(move || write(BufWriter::new(file), queued));
+      Ok(Recorder { entries })
+   }
+
+   /// Records a frame.  A recording that can't be written to doesn't get to
+   /// stop the stream, so failures are left out.
+   pub fn record(&self, message: &Message) {
+      if let Some(payload) = frame::payload(message) {
+         let _ = self.entries.send(Entry::Frame(format!("{}\t{}", Utc::now().timestamp_millis(), String::from_utf8_lossy(payload))));
+      }
+   }
+
+   /// Waits for everything recorded so far to be written out
+   pub async fn flush(&self) {
+      let (done, flushed) = oneshot::channel();
+      if self.entries.send(Entry::Flush(done)).is_ok() { let _ = flushed.await; }
+   }
+}
+
+/// Writes the queued frames, flushing them every `FLUSH_EVERY` & when asked
+/// to, until the recorder goes away
+fn write(mut file: BufWriter<File>, queued: mpsc::Receiver<Entry>) {
+   let mut flushed = std::time::Instant::now();
+   loop {
+      match queued.recv_timeout(FLUSH_EVERY) {
+         Ok(Entry::Frame(line)) => { let _ = writeln!(file, "{}", line); },
+         Ok(Entry::Flush(done)) => {
+            let _ = file.flush();
+            let _ = done.send(());
+         },
+         Err(RecvTimeoutError::Timeout) => {},
+         Err(RecvTimeoutError::Disconnected) => { let _ = file.flush(); return; }
+      }
+
+      if flushed.elapsed() >= FLUSH_EVERY {
+         let _ = file.flush();
+         flushed = std::time::Instant::now();
+      }
+   }
+}
+
+/// Plays back the frames recorded by a streamer (see
+/// [`StreamerBuilder::record`](struct.StreamerBuilder.html#method.record)),
+/// so a strategy can be tested against real data with the same results every time.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use futures::{ future, StreamExt };
+/// use yahoo_finance::Replay;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    // ten times faster than it happened
+///    let replay = Replay::open("monday.rec").unwrap().speed(10.0);
+///
+///    replay.stream()
+///       .for_each(|quote| future::ready(if let Ok(quote) = quote { println!("{} is at {}", quote.symbol, quote.price) }))
+///       .await;
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Replay {
+   frames: Vec<(i64, String)>,
+   speed: f64
+}
+impl Replay {
+   /// Loads a recording
+   pub fn open(path: impl AsRef<Path>) -> Result<Replay> {
+      let path = path.as_ref();
+      let text = fs::read_to_string(path).context(error::RecordingFailed { path: path.display().to_string() })?;
+
+      // a line we can't read becomes a bad frame when it's played
+      let frames = text.lines()
+         .filter(|line| !line.is_empty())
+         .map(|line| match line.split_once('\t') {
+            Some((time, frame)) => (time.parse().unwrap_or_default(), frame.to_string()),
+            None => (0, line.to_string())
+         })
+         .collect();
+      Ok(Replay { frames, speed: 1.0 })
+   }
+
+   /// Plays the recording `speed` times faster than it was recorded, ie. 2.0
+   /// for double speed.  `f64::INFINITY` plays it without waiting at all.
+   /// Speeds below 0.001 (along with NaN) play at 0.001.
+   pub fn speed(mut self, speed: f64) -> Replay {
+      self.speed = if speed >= MIN_SPEED { speed } else { MIN_SPEED };
+      self
+   }
+
+   /// Plays the recording back as quotes, just like `Streamer::stream`
+   pub fn stream(self) -> impl Stream<Item = Result<Quote>> {
+      let mut changes = ChangeTracker::default();
+      self.play(move |data| Quote::from_data(data, &mut changes))
+   }
+
+   /// Plays the recording back as the pricing data, just like `Streamer::raw_stream`
+   pub fn raw_stream(self) -> impl Stream<Item = Result<PricingData>> {
+      self.play(|data| data)
+   }
+
+   fn play<T, F>(self, mut convert: F) -> impl Stream<Item = Result<T>>
+      where T: Send + 'static, F: FnMut(PricingData) -> T + Send + 'static {
+      let first = self.frames.iter().map(|(time, _)| *time).find(|time| *time > 0).unwrap_or_default();
+      let speed = self.speed;
+
+      // the clock starts with the first frame, not when the stream is set up
+      let frames = stream::unfold((self.frames.into_iter(), None), move |(mut frames, started)| async move {
+         let (time, frame) = frames.next()?;
+         let started = started.unwrap_or_else(Instant::now);
+
+         // keep to the recorded gaps between frames, sped up as asked
+         let offset = Duration::try_from_secs_f64((time - first).max(0) as f64 / 1000.0 / speed).unwrap_or_default();
+         if let Some(at) = started.checked_add(offset) { time::delay_until(at).await; }
+         Some((frame::parse(frame.as_bytes()), (frames, Some(started))))
+      });
+      frames.map(move |data| data.map(&mut convert)).boxed()
+   }
+}
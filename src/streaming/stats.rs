@@ -0,0 +1,53 @@
+use chrono::{ DateTime, Utc };
+use serde::{ Deserialize, Serialize };
+use std::sync::Mutex;
+use std::sync::atomic::{ AtomicU64, Ordering };
+
+/// How the feed has been doing, ie. for monitoring to alert on
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct StreamStats {
+   /// Data messages received from Yahoo!, including those that couldn't be read
+   pub messages: u64,
+
+   /// Messages that couldn't be read
+   pub parse_errors: u64,
+
+   /// Times the connection came back after being lost
+   pub reconnects: u64,
+
+   /// The number of symbols subscribed to
+   pub subscriptions: usize,
+
+   /// When the last data message arrived
+   pub last_message: Option<DateTime<Utc>>
+}
+
+/// The running counts behind `StreamStats`, updated by the connections
+#[derive(Debug, Default)]
+pub struct Counters {
+   messages: AtomicU64,
+   parse_errors: AtomicU64,
+   reconnects: AtomicU64,
+   last_message: Mutex<Option<DateTime<Utc>>>
+}
+impl Counters {
+   pub fn message(&self, parsed: bool) {
+      self.messages.fetch_add(1, Ordering::Relaxed);
+      if !parsed { self.parse_errors.fetch_add(1, Ordering::Relaxed); }
+      *self.last_message.lock().unwrap_or_else(|e| e.into_inner()) = Some(Utc::now());
+   }
+
+   pub fn reconnected(&self) {
+      self.reconnects.fetch_add(1, Ordering::Relaxed);
+   }
+
+   pub fn snapshot(&self, subscriptions: usize) -> StreamStats {
+      StreamStats {
+         messages: self.messages.load(Ordering::Relaxed),
+         parse_errors: self.parse_errors.load(Ordering::Relaxed),
+         reconnects: self.reconnects.load(Ordering::Relaxed),
+         subscriptions,
+         last_message: *self.last_message.lock().unwrap_or_else(|e| e.into_inner())
+      }
+   }
+}
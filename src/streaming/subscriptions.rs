@@ -0,0 +1,92 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::sync::{ Mutex, MutexGuard };
+use std::sync::atomic::{ AtomicUsize, Ordering };
+use tokio::sync::mpsc::UnboundedSender as Sender;
+use tokio_tungstenite::tungstenite::protocol::Message;
+
+/// What we ask Yahoo! for, ie. `{"subscribe":["AAPL"]}`
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum Request<'a> {
+   Subscribe(&'a [String]),
+   Unsubscribe(&'a [String])
+}
+impl Request<'_> {
+   fn message(&self) -> Option<Message> {
+      serde_json::to_string(self).ok().map(Message::Text)
+   }
+}
+
+/// What Yahoo! made of the symbols subscribed to
+#[derive(Debug, Clone, PartialEq)]
+pub enum SubscriptionEvent {
+   /// Yahoo! confirmed the subscription to the symbols
+   Acknowledged { symbols: Vec<String> },
+
+   /// Yahoo! turned down the symbols - ie. an unknown symbol or too many of
+   /// them.  `symbols` is empty when Yahoo! doesn't say which.
+   Rejected { symbols: Vec<String>, reason: String },
+
+   /// The first update for a symbol came through on the connection, so it
+   /// is streaming
+   Live { symbol: String }
+}
+
+/// The symbols being streamed, shared between the streamer & every
+/// connection currently open so changes reach Yahoo! straight away and
+/// survive reconnects.
+#[derive(Debug, Default)]
+pub struct Subscriptions {
+   symbols: Mutex<Vec<String>>,
+
+   /// Sends messages out on each open connection, by connection id
+   live: Mutex<HashMap<usize, Sender<Message>>>,
+
+   next_id: AtomicUsize
+}
+impl Subscriptions {
+   pub fn new(symbols: Vec<String>) -> Subscriptions {
+      Subscriptions { symbols: Mutex::new(symbols), ..Subscriptions::default() }
+   }
+
+   pub fn symbols(&self) -> Vec<String> { lock(&self.symbols).clone() }
+
+   pub fn subscribe(&self, symbols: Vec<String>) {
+      let live = lock(&self.live);
+      let mut current = lock(&self.symbols);
+      for symbol in &symbols {
+         if !current.contains(symbol) { current.push(symbol.clone()); }
+      }
+      send(live.values(), Request::Subscribe(&symbols));
+   }
+
+   pub fn unsubscribe(&self, symbols: Vec<String>) {
+      let live = lock(&self.live);
+      lock(&self.symbols).retain(|symbol| !symbols.contains(symbol));
+      send(live.values(), Request::Unsubscribe(&symbols));
+   }
+
+   /// Sends the current subscription out on a newly opened connection,
+   /// which then gets any later changes until it is `disconnected` with the
+   /// id handed back.
+   pub fn connected(&self, sender: Sender<Message>) -> usize {
+      let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+      let mut live = lock(&self.live);
+      send(std::iter::once(&sender), Request::Subscribe(&lock(&self.symbols)));
+      live.insert(id, sender);
+      id
+   }
+
+   pub fn disconnected(&self, id: usize) {
+      lock(&self.live).remove(&id);
+   }
+}
+
+fn send<'a>(live: impl Iterator<Item = &'a Sender<Message>>, request: Request) {
+   if let Some(message) = request.message() {
+      for sender in live { let _ = sender.send(message.clone()); }
+   }
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> { mutex.lock().unwrap_or_else(|e| e.into_inner()) }
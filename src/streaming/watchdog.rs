@@ -0,0 +1,60 @@
+use std::collections::HashMap;
+use std::time::Duration;
+use tokio::time::Instant;
+
+/// Keeps track of when we last heard from the connection & from each symbol
+#[derive(Debug)]
+pub struct Watchdog {
+   timeout: Duration,
+   last: Instant,
+
+   /// When each symbol last updated & whether it has been reported as stale since
+   symbols: HashMap<String, (Instant, bool)>
+}
+
+/// What the watchdog found gone quiet
+#[derive(Debug, Default)]
+pub struct Silence {
+   pub connection: bool,
+   pub symbols: Vec<String>
+}
+
+impl Watchdog {
+   pub fn new(timeout: Duration, subscribed: &[String]) -> Watchdog {
+      let last = Instant::now();
+      Watchdog { timeout, last, symbols: subscribed.iter().map(|symbol| (symbol.clone(), (last, false))).collect() }
+   }
+
+   /// Notes a message, which may be an update for a symbol
+   pub fn heard(&mut self, symbol: Option<&str>) {
+      self.last = Instant::now();
+      if let Some(symbol) = symbol { self.symbols.insert(symbol.to_string(), (self.last, false)); }
+   }
+
+   /// When something is next due to go stale
+   pub fn deadline(&self) -> Instant {
+      let symbols = self.symbols.values().filter(|(_, reported)| !reported).map(|(seen, _)| *seen);
+      symbols.fold(self.last, |earliest, seen| earliest.min(seen)) + self.timeout
+   }
+
+   /// Finds whatever has gone quiet for the subscribed symbols, reporting
+   /// each symbol once until it updates again.
+   pub fn check(&mut self, subscribed: &[String]) -> Silence {
+      let now = Instant::now();
+      if now >= self.last + self.timeout { return Silence { connection: true, symbols: Vec::new() }; }
+
+      // symbols we haven't heard about yet count from when they were subscribed
+      self.symbols.retain(|symbol, _| subscribed.contains(symbol));
+      for symbol in subscribed { self.symbols.entry(symbol.clone()).or_insert((now, false)); }
+
+      let mut silence = Silence::default();
+      for (symbol, (seen, reported)) in self.symbols.iter_mut() {
+         if !*reported && now >= *seen + self.timeout {
+            *reported = true;
+            silence.symbols.push(symbol.clone());
+         }
+      }
+      silence.symbols.sort();
+      silence
+   }
+}
@@ -0,0 +1,114 @@
+use chrono::{ NaiveDate, TimeZone, Utc };
+use serde::{Deserialize, Serialize};
+use snafu::OptionExt;
+
+use crate::{ error, yahoo, Result };
+use crate::currency::Currencies;
+
+/// The quote summary modules the summary is made of
+const MODULES: &[&str] = &["summaryDetail", "financialData"];
+
+/// The overview Yahoo shows at the top of a quote page - the day's trading,
+/// the 52 week range, volumes & dividends.
+///
+/// Parts that don't apply (ie. dividends for a company that doesn't pay
+/// any) are left out - see [missing figures & fractions](crate#missing-figures--fractions).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Summary {
+   /// The currencies the symbol trades & reports its financials in - the
+   /// prices are in the trading one
+   pub currencies: Currencies,
+
+   pub previous_close: Option<f64>,
+   pub open: Option<f64>,
+   pub day_low: Option<f64>,
+   pub day_high: Option<f64>,
+   pub fifty_two_week_low: Option<f64>,
+   pub fifty_two_week_high: Option<f64>,
+   pub fifty_day_average: Option<f64>,
+   pub two_hundred_day_average: Option<f64>,
+
+   pub bid: Option<f64>,
+   pub bid_size: Option<u64>,
+   pub ask: Option<f64>,
+   pub ask_size: Option<u64>,
+
+   /// The day's volume so far
+   pub volume: Option<u64>,
+
+   /// The average daily volume over the last 3 months
+   pub average_volume: Option<u64>,
+
+   /// The average daily volume over the last 10 days
+   pub average_volume_10_day: Option<u64>,
+
+   pub market_cap: Option<f64>,
+
+   /// The dividends expected over the next year, per share
+   pub dividend_rate: Option<f64>,
+   pub dividend_yield: Option<f64>,
+
+   /// The first day the shares trade without the next dividend
+   pub ex_dividend_date: Option<NaiveDate>,
+
+   /// The fraction of earnings paid out as dividends
+   pub payout_ratio: Option<f64>,
+
+   /// The average dividend yield over the last 5 years, as a percentage -
+   /// ie. 0.8 is 0.8%
+   pub five_year_average_dividend_yield: Option<f64>
+}
+impl Summary {
+   /// Loads the summary for a symbol.
+   ///
+   /// # Examples
+   ///
+   /// ``` no_run
+   /// use yahoo_finance::Summary;
+   ///
+   /// #[tokio::main]
+   /// async fn main() {
+   ///    let summary = Summary::load("AAPL").await.unwrap();
+   ///    println!("Apple has traded between {:.2} & {:.2} over the last year",
+   ///       summary.fifty_two_week_low.unwrap_or_default(),
+   ///       summary.fifty_two_week_high.unwrap_or_default());
+   /// }
+   /// ```
+   pub async fn load(symbol: &str) -> Result<Summary> {
+      Self::new(yahoo::load_quote_summary(symbol, MODULES).await?)
+   }
+
+   /// Blocking version of `load` for code that doesn't run tokio.  This must not be called from async code.
+   #[cfg(feature = "blocking")]
+   pub fn blocking_load(symbol: &str) -> Result<Summary> { crate::blocking::run(Self::load(symbol)) }
+
+   pub(crate) fn new(data: yahoo::QuoteSummary) -> Result<Summary> {
+      let currencies = Currencies::from_summary(&data);
+      let detail = data.summary_detail.context(error::MissingData { reason: "no summary detail" })?;
+
+      Ok(Summary {
+         currencies,
+         previous_close: detail.previous_close,
+         open: detail.open,
+         day_low: detail.day_low,
+         day_high: detail.day_high,
+         fifty_two_week_low: detail.fifty_two_week_low,
+         fifty_two_week_high: detail.fifty_two_week_high,
+         fifty_day_average: detail.fifty_day_average,
+         two_hundred_day_average: detail.two_hundred_day_average,
+         bid: detail.bid,
+         bid_size: detail.bid_size,
+         ask: detail.ask,
+         ask_size: detail.ask_size,
+         volume: detail.volume,
+         average_volume: detail.average_volume,
+         average_volume_10_day: detail.average_volume_10days,
+         market_cap: detail.market_cap,
+         dividend_rate: detail.dividend_rate,
+         dividend_yield: detail.dividend_yield,
+         ex_dividend_date: detail.ex_dividend_date.and_then(|date| Utc.timestamp_opt(date, 0).single()).map(|date| date.date_naive()),
+         payout_ratio: detail.payout_ratio,
+         five_year_average_dividend_yield: detail.five_year_avg_dividend_yield
+      })
+   }
+}
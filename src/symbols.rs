@@ -0,0 +1,252 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use snafu::{ ensure, OptionExt };
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::{ error, yahoo, Result };
+
+/// The most symbols we'll ask Yahoo to validate in a single call
+const VALIDATION_BATCH: usize = 100;
+
+/// The kind of security a symbol represents, according to Yahoo.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum Kind {
+   /// Common stock - ie. 'AAPL'
+   Equity,
+
+   /// Exchange traded funds - ie. 'QQQ'
+   Etf,
+
+   /// Market indices - ie. '^DJI'
+   Index,
+
+   /// Mutual funds - ie. 'VFIAX'
+   MutualFund,
+
+   /// Money market funds
+   MoneyMarket,
+
+   /// Currency pairs - ie. 'EURUSD=X'
+   Currency,
+
+   /// Crypto currencies - ie. 'BTC-USD'
+   Crypto,
+
+   /// Futures contracts - ie. 'CL=F'
+   Future,
+
+   /// Option contracts
+   Option,
+
+   /// Anything else Yahoo reports, holding the raw Yahoo type
+   Other(String)
+}
+impl From<&str> for Kind {
+   fn from(kind: &str) -> Kind {
+      match kind {
+         "EQUITY" => Kind::Equity,
+         "ETF" => Kind::Etf,
+         "INDEX" => Kind::Index,
+         "MUTUALFUND" => Kind::MutualFund,
+         "MONEYMARKET" => Kind::MoneyMarket,
+         "CURRENCY" => Kind::Currency,
+         "CRYPTOCURRENCY" => Kind::Crypto,
+         "FUTURE" => Kind::Future,
+         "OPTION" => Kind::Option,
+         other => Kind::Other(other.to_string())
+      }
+   }
+}
+impl fmt::Display for Kind {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self {
+         Kind::Equity => f.write_str("EQUITY"),
+         Kind::Etf => f.write_str("ETF"),
+         Kind::Index => f.write_str("INDEX"),
+         Kind::MutualFund => f.write_str("MUTUALFUND"),
+         Kind::MoneyMarket => f.write_str("MONEYMARKET"),
+         Kind::Currency => f.write_str("CURRENCY"),
+         Kind::Crypto => f.write_str("CRYPTOCURRENCY"),
+         Kind::Future => f.write_str("FUTURE"),
+         Kind::Option => f.write_str("OPTION"),
+         Kind::Other(kind) => f.write_str(kind)
+      }
+   }
+}
+
+/// A symbol Yahoo considers related to another symbol.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Recommendation {
+   /// The recommended symbol
+   pub symbol: String,
+
+   /// How similar Yahoo thinks the symbol is - higher is more similar
+   pub score: f64
+}
+impl Recommendation {
+   /// Converts Yahoo's recommendations, ordered from most to least similar
+   pub(crate) fn from_yahoo(data: yahoo::Recommendations) -> Vec<Recommendation> {
+      let mut result: Vec<Recommendation> = data.recommended_symbols
+         .into_iter()
+         .map(|r| Recommendation { symbol: r.symbol, score: r.score })
+         .collect();
+
+      result.sort_by(|a, b| b.score.partial_cmp(&a.score).unwrap_or(std::cmp::Ordering::Equal));
+      result
+   }
+}
+
+/// Whether an option contract is the right to buy or to sell
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OptionKind {
+   Call,
+   Put
+}
+
+/// An option contract, as spelled out by its OCC style symbol - ie.
+/// 'AAPL240119C00190000' is the AAPL $190 call expiring on Jan 19, 2024.
+///
+/// # Examples
+///
+/// ```
+/// use yahoo_finance::symbols::{ OptionContract, OptionKind };
+///
+/// let contract = OptionContract::parse("AAPL240119C00190000").unwrap();
+/// assert_eq!("AAPL", contract.underlying);
+/// assert_eq!(OptionKind::Call, contract.kind);
+/// assert_eq!(190.0, contract.strike);
+/// assert_eq!("AAPL240119C00190000", contract.to_string());
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OptionContract {
+   /// The symbol the option is on
+   pub underlying: String,
+
+   pub expiry: NaiveDate,
+   pub kind: OptionKind,
+   pub strike: f64
+}
+impl OptionContract {
+   /// Reads a contract out of its symbol - the underlying symbol followed by
+   /// the expiry as YYMMDD, 'C' or 'P' & the strike in thousandths of a
+   /// dollar padded to 8 digits.
+   pub fn parse(symbol: &str) -> Result<OptionContract> {
+      let invalid = || error::InvalidOptionSymbol { symbol: symbol.to_string() };
+
+      let split = symbol.len().checked_sub(15).filter(|&split| split > 0 && symbol.is_char_boundary(split)).context(invalid())?;
+      let (underlying, details) = symbol.split_at(split);
+      ensure!(details.is_ascii(), invalid());
+
+      let expiry = NaiveDate::parse_from_str(&details[..6], "%y%m%d").ok().context(invalid())?;
+      let kind = match &details[6..7] {
+         "C" => OptionKind::Call,
+         "P" => OptionKind::Put,
+         _ => invalid().fail()?
+      };
+      ensure!(details[7..].bytes().all(|b| b.is_ascii_digit()), invalid());
+      let strike = details[7..].parse::<u64>().ok().context(invalid())?;
+      Ok(OptionContract { underlying: underlying.to_string(), expiry, kind, strike: strike as f64 / 1000.0 })
+   }
+}
+impl fmt::Display for OptionContract {
+   /// Writes out the contract's symbol
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      let kind = match self.kind { OptionKind::Call => 'C', OptionKind::Put => 'P' };
+      write!(f, "{}{}{}{:08}", self.underlying, self.expiry.format("%y%m%d"), kind, (self.strike * 1000.0).round() as u64)
+   }
+}
+
+/// Looks up the kind of security a symbol represents.
+///
+/// This uses Yahoo's lightweight quote type data so it is much cheaper than
+/// loading a full `Profile`.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::symbols::{ self, Kind };
+///
+/// #[tokio::main]
+/// async fn main() {
+///    match symbols::kind("BTC-USD").await {
+///       Ok(Kind::Crypto) => println!("Bitcoin is a crypto currency"),
+///       Ok(kind) => println!("Bitcoin is a {}?", kind),
+///       Err(e) => println!("Failed to call Yahoo: {:?}", e)
+///    }
+/// }
+/// ```
+pub async fn kind(symbol: &str) -> Result<Kind> {
+   let data = yahoo::load_quote_type(symbol).await?;
+   Ok(Kind::from(data.kind.as_str()))
+}
+
+/// Blocking version of `kind` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_kind(symbol: &str) -> Result<Kind> { crate::blocking::run(kind(symbol)) }
+
+/// Checks which symbols Yahoo recognizes.
+///
+/// The result maps every requested symbol, as given, to whether or not
+/// Yahoo knows about it - handy for pruning dead tickers before downloading
+/// history.  Symbols are matched regardless of case.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::symbols;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    let valid = symbols::validate_many(&["AAPL", "FUBAR"]).await.unwrap();
+///    for (symbol, known) in &valid {
+///       println!("{} is {}", symbol, if *known { "valid" } else { "unknown" });
+///    }
+/// }
+/// ```
+pub async fn validate_many(symbols: &[&str]) -> Result<HashMap<String, bool>> {
+   let mut result = HashMap::new();
+   for batch in symbols.chunks(VALIDATION_BATCH) {
+      // Yahoo answers with the symbols uppercased
+      let validated: HashMap<String, bool> = yahoo::load_validation(batch).await?.into_iter()
+         .map(|(symbol, known)| (symbol.to_uppercase(), known))
+         .collect();
+
+      // anything Yahoo doesn't mention is not something it recognizes
+      for symbol in batch {
+         let known = validated.get(&symbol.to_uppercase()).copied().unwrap_or(false);
+         result.insert(symbol.to_string(), known);
+      }
+   }
+   Ok(result)
+}
+
+/// Blocking version of `validate_many` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_validate_many(symbols: &[&str]) -> Result<HashMap<String, bool>> { crate::blocking::run(validate_many(symbols)) }
+
+/// Retrieves the symbols Yahoo recommends for people interested in a symbol,
+/// ordered from most to least similar.
+///
+/// These are 'people also watch' style recommendations and have nothing to
+/// do with analyst buy / sell recommendations.
+///
+/// # Examples
+///
+/// ``` no_run
+/// use yahoo_finance::symbols;
+///
+/// #[tokio::main]
+/// async fn main() {
+///    for related in symbols::recommended("AAPL").await.unwrap() {
+///       println!("{} ({:.3})", related.symbol, related.score);
+///    }
+/// }
+/// ```
+pub async fn recommended(symbol: &str) -> Result<Vec<Recommendation>> {
+   Ok(Recommendation::from_yahoo(yahoo::load_recommendations(symbol).await?))
+}
+
+/// Blocking version of `recommended` for code that doesn't run tokio.  This must not be called from async code.
+#[cfg(feature = "blocking")]
+pub fn blocking_recommended(symbol: &str) -> Result<Vec<Recommendation>> { crate::blocking::run(recommended(symbol)) }
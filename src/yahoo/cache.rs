@@ -0,0 +1,28 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Response bodies keyed by URL, along with when they were loaded
+static RESPONSES: Mutex<Option<HashMap<String, (Instant, String)>>> = Mutex::new(None);
+
+/// Gets a cached response that is no older than `ttl`
+pub fn lookup(url: &str, ttl: Duration) -> Option<String> {
+   let responses = RESPONSES.lock().unwrap_or_else(|e| e.into_inner());
+   match responses.as_ref()?.get(url) {
+      Some((loaded, body)) if loaded.elapsed() < ttl => Some(body.clone()),
+      _ => None
+   }
+}
+
+/// Caches a response, dropping anything that has expired
+pub fn store(url: &str, body: &str, ttl: Duration) {
+   let mut responses = RESPONSES.lock().unwrap_or_else(|e| e.into_inner());
+   let responses = responses.get_or_insert_with(HashMap::new);
+
+   responses.retain(|_, (loaded, _)| loaded.elapsed() < ttl);
+   responses.insert(url.to_string(), (Instant::now(), body.to_string()));
+}
+
+pub fn clear_cache() {
+   *RESPONSES.lock().unwrap_or_else(|e| e.into_inner()) = None;
+}
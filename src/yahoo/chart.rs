@@ -1,24 +1,63 @@
+// `OHLCV` keeps the name the chart data has always gone by
+#![allow(clippy::upper_case_acronyms)]
+
 use chrono::serde::ts_seconds;
 use chrono::{DateTime, Utc};
-use reqwest::Url;
 use serde::Deserialize;
+use std::collections::HashMap;
 use snafu::{ ensure, OptionExt, ResultExt };
-use std::env;
 
 use crate::{error, Interval, Result};
+use super::{ http, QuerySpec };
+
+/// Helper function to build up the main query
+pub fn chart_query(symbol: &str) -> QuerySpec {
+   QuerySpec::query("/v8/finance/chart").segment(symbol)
+}
+
+ez_serde!(Period { timezone: String, start: i64, end: i64, gmtoffset: i64 });
+ez_serde!(CurrentPeriods { pre: Period, regular: Period, post: Period });
+ez_serde!(ExtendedPeriods {
+   #[serde(default)]
+   pre: Vec<Vec<Period>>,
 
-const BASE_URL: &'static str = "https://query1.finance.yahoo.com/v8/finance/chart/";
+   #[serde(default)]
+   regular: Vec<Vec<Period>>,
 
-/// Helper function to build up the main query URL
-fn build_query(symbol: &str) -> Result<Url> {
-   let base = env::var("TEST_URL").unwrap_or(BASE_URL.to_string());
-   Ok(Url::parse(&base).context(error::InternalURL { url: &base })?
-      .join(symbol).context(error::InternalURL { url: symbol })?)
+   #[serde(default)]
+   post: Vec<Vec<Period>>
+});
+
+/// Yahoo only breaks the trading periods out by session when asked for pre & post market data
+#[allow(dead_code)]
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+pub enum TradingPeriods {
+   Extended(ExtendedPeriods),
+   Regular(Vec<Vec<Period>>)
 }
 
 ez_serde!(Meta {
    symbol: String,
 
+   #[serde(default)]
+   currency: Option<String>,
+
+   #[serde(default)]
+   exchange_name: String,
+
+   #[serde(default)]
+   instrument_type: String,
+
+   #[serde(default)]
+   gmtoffset: i64,
+
+   #[serde(default)]
+   timezone: String,
+
+   #[serde(default)]
+   exchange_timezone_name: String,
+
    #[serde(with = "ts_seconds")]
    first_trade_date: DateTime<Utc>,
 
@@ -26,7 +65,25 @@ ez_serde!(Meta {
    current_price: f32,
 
    #[serde(rename = "chartPreviousClose")]
-   previous_close: f32
+   previous_close: f32,
+
+   #[serde(default)]
+   regular_market_time: i64,
+
+   #[serde(default)]
+   regular_market_volume: u64,
+
+   #[serde(default)]
+   regular_market_day_high: Option<f64>,
+
+   #[serde(default)]
+   regular_market_day_low: Option<f64>,
+
+   #[serde(default)]
+   current_trading_period: Option<CurrentPeriods>,
+
+   #[serde(default)]
+   trading_periods: Option<TradingPeriods>
 });
 
 ez_serde!(OHLCV {
@@ -46,11 +103,38 @@ ez_serde!(OHLCV {
    volumes: Vec<Option<u64>>
 });
 
-ez_serde!(Indicators { #[serde(rename = "quote", default)] quotes: Vec<OHLCV> });
+ez_serde!(AdjClose { #[serde(rename = "adjclose", default)] values: Vec<Option<f64>> });
+
+ez_serde!(Indicators {
+   #[serde(rename = "quote", default)]
+   quotes: Vec<OHLCV>,
+
+   #[serde(rename = "adjclose", default)]
+   adjusted_closes: Vec<AdjClose>
+});
+
+ez_serde!(DividendEvent { amount: f64, date: i64 });
+ez_serde!(CapitalGainEvent { amount: f64, date: i64 });
+ez_serde!(SplitEvent { date: i64, numerator: f64, denominator: f64 });
+
+// events are keyed by their timestamp
+ez_serde!(CorporateEvents {
+   #[serde(default)]
+   dividends: HashMap<String, DividendEvent>,
+
+   #[serde(default)]
+   splits: HashMap<String, SplitEvent>,
+
+   #[serde(default)]
+   capital_gains: HashMap<String, CapitalGainEvent>
+});
 
 ez_serde!(Data {
    meta: Meta,
 
+   #[serde(default)]
+   events: Option<CorporateEvents>,
+
    #[serde(rename = "timestamp", default)]
    timestamps: Vec<i64>,
 
@@ -61,19 +145,11 @@ ez_serde!(Error {code: String, description: String });
 ez_serde!(Chart { result: Option<Vec<Data>>, error: Option<Error> });
 ez_serde!(Response { chart: Chart });
 
-async fn load(url: &Url) -> Result<Data> {
-   // make the call - we do not really expect this to fail.
-   // ie - we won't 404 if the symbol doesn't exist
-   let response = reqwest::get(url.clone()).await.context(error::RequestFailed)?;
-   ensure!(
-      response.status().is_success(),
-      error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
-   );
-
-   let data = response.text().await.context(error::UnexpectedErrorRead { url: url.to_string() })?;
-   let chart = serde_json::from_str::<Response>(&data).context(error::BadData)?.chart;
+/// Reads a chart response
+pub fn parse_chart(data: &str) -> Result<Data> {
+   let chart = serde_json::from_str::<Response>(data).context(error::BadData)?.chart;
 
-   if !chart.result.is_some() {
+   if chart.result.is_none() {
       // no result so we'd better have an error
       let err = chart.error.context(error::InternalLogic{ reason: "error block exists without values"})?;
       error::ChartFailed{ code: err.code, description: err.description }.fail()?;
@@ -81,25 +157,30 @@ async fn load(url: &Url) -> Result<Data> {
 
    // we have a result to process
    let result = chart.result.context(error::UnexpectedErrorYahoo)?;
-   ensure!(result.len() > 0, error::UnexpectedErrorYahoo);
+   ensure!(!result.is_empty(), error::UnexpectedErrorYahoo);
    Ok(result[0].clone())
 }
 
+/// Loads a chart, failing over to the second query host when the first one is unavailable
+pub async fn load_chart(query: &QuerySpec) -> Result<Data> {
+   let body = match http::get(query).await {
+      Err(e) if e.is_unavailable() => match query.fallback() {
+         Some(fallback) => http::get(&fallback).await?,
+         None => return Err(e)
+      },
+      result => result?
+   };
+   parse_chart(&body)
+}
+
 pub async fn load_daily(symbol: &str, period: Interval) -> Result<Data> {
-   let mut lookup = build_query(symbol)?;
-   lookup.query_pairs_mut()
-      .append_pair("range", &period.to_string())
-      .append_pair("interval", "1d");
+   load_bars(symbol, period, Interval::_1d).await
+}
 
-   load(&lookup).await
+pub async fn load_bars(symbol: &str, period: Interval, bar_size: impl ToString) -> Result<Data> {
+   load_chart(&chart_query(symbol).range(period).interval(bar_size)).await
 }
 
 pub async fn load_daily_range(symbol: &str, start: i64, end: i64) -> Result<Data> {
-   let mut lookup = build_query(symbol)?;
-   lookup.query_pairs_mut()
-      .append_pair("period1", &start.to_string())
-      .append_pair("period2", &end.to_string())
-      .append_pair("interval", "1d");
-
-   load(&lookup).await
+   load_chart(&chart_query(symbol).start(start).end(end).interval(Interval::_1d)).await
 }
@@ -0,0 +1,117 @@
+use reqwest::header::{ HeaderMap, COOKIE, LOCATION, SET_COOKIE };
+use reqwest::redirect::Policy;
+use reqwest::Url;
+use snafu::{ ensure, OptionExt, ResultExt };
+use std::sync::RwLock;
+
+use crate::{ error, Result };
+use crate::metrics::Operation;
+use super::http;
+
+/// The most redirects we follow through the consent pages before giving up
+const MAX_REDIRECTS: usize = 5;
+
+/// The hosts Yahoo! serves its consent pages from
+const CONSENT_HOSTS: &[&str] = &["consent.", "guce."];
+
+/// The paths of the consent pages, for when they're served from Yahoo!'s other hosts
+const CONSENT_PATHS: &[&str] = &["/consent", "/collectConsent", "/copyConsent", "/v2/collectConsent", "/v2/copyConsent"];
+
+static COOKIES: RwLock<Option<String>> = RwLock::new(None);
+
+/// Whether Yahoo! sent us to its cookie consent page rather than what we asked for.
+/// This happens to calls made from the EU.
+pub fn is_consent_page(url: &Url) -> bool {
+   let host = url.host_str().unwrap_or_default();
+   let path = url.path().trim_end_matches('/');
+   CONSENT_HOSTS.iter().any(|prefix| host.starts_with(prefix)) || CONSENT_PATHS.contains(&path)
+}
+
+/// The cookies showing that we already agreed to Yahoo!'s consent page
+pub fn cookies() -> Option<String> {
+   COOKIES.read().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+/// Agrees to the consent page at `url`, keeping the cookies Yahoo! hands out
+/// so that the following calls go through.
+pub async fn accept(url: &str) -> Result<()> {
+   let mut url = Url::parse(url).context(error::InternalURL { url })?;
+   let client = http::client_builder()?.redirect(Policy::none()).build().context(error::RequestFailed)?;
+   let mut cookies = Vec::new();
+
+   // load the consent form & agree to it
+   let response = http::execute(Operation::Get, client.get(url.clone())).await?;
+   collect(&response.headers, &mut cookies);
+   let (action, mut fields) = parse_form(&response.body).context(error::ConsentFailed { url: url.as_str() })?;
+   fields.push(("agree".to_string(), "agree".to_string()));
+
+   url = url.join(&action).context(error::InternalURL { url: &action })?;
+   let mut response = http::execute(Operation::Post, client.post(url.clone()).header(COOKIE, cookies.join("; ")).form(&fields)).await?;
+   collect(&response.headers, &mut cookies);
+
+   // Yahoo! hands out the rest of the cookies while redirecting back
+   for _ in 0..MAX_REDIRECTS {
+      let location = match response.headers.get(LOCATION).and_then(|value| value.to_str().ok()) {
+         Some(location) => url.join(location).context(error::InternalURL { url: location })?,
+         None => break
+      };
+      if !is_consent_page(&location) { break; }
+
+      url = location;
+      response = http::execute(Operation::Get, client.get(url.clone()).header(COOKIE, cookies.join("; "))).await?;
+      collect(&response.headers, &mut cookies);
+   }
+
+   ensure!(!cookies.is_empty(), error::ConsentFailed { url: url.as_str() });
+   *COOKIES.write().unwrap_or_else(|e| e.into_inner()) = Some(cookies.join("; "));
+   Ok(())
+}
+
+/// Keeps the `name=value` part of each cookie being set, replacing older values
+fn collect(headers: &HeaderMap, cookies: &mut Vec<String>) {
+   let values = headers.get_all(SET_COOKIE).iter()
+      .filter_map(|value| value.to_str().ok())
+      .filter_map(|value| value.split(';').next())
+      .map(str::trim)
+      .filter(|value| value.contains('='));
+
+   for value in values {
+      let name = value.split('=').next().unwrap_or_default();
+      cookies.retain(|cookie| cookie.split('=').next() != Some(name));
+      cookies.push(value.to_string());
+   }
+}
+
+/// Finds where the consent form is sent & the hidden fields that go with it
+fn parse_form(page: &str) -> Option<(String, Vec<(String, String)>)> {
+   let start = page.find("<form")?;
+   let form = &page[start..];
+   let form = &form[..form.find("</form>").unwrap_or(form.len())];
+
+   let action = attribute(&form[..form.find('>')?], "action").unwrap_or_default();
+   let fields = form.split("<input").skip(1)
+      .filter_map(|input| input.split('>').next())
+      .filter(|input| attribute(input, "type").is_some_and(|kind| kind.eq_ignore_ascii_case("hidden")))
+      .filter_map(|input| Some((attribute(input, "name")?, attribute(input, "value").unwrap_or_default())))
+      .collect();
+   Some((action, fields))
+}
+
+/// The value of an HTML attribute within a tag
+fn attribute(tag: &str, name: &str) -> Option<String> {
+   let pattern = format!("{}=", name);
+   let mut rest = tag;
+   while let Some(index) = rest.find(&pattern) {
+      // skip matches in the middle of other attribute names, ie. `data-name=`
+      let whole = index == 0 || rest[..index].ends_with(char::is_whitespace);
+      rest = &rest[index + pattern.len()..];
+      if !whole { continue; }
+
+      let quote = rest.chars().next()?;
+      return match quote {
+         '"' | '\'' => rest[1..].split(quote).next().map(|value| value.replace("&amp;", "&")),
+         _ => rest.split(|c: char| c.is_whitespace() || c == '/').next().map(str::to_string)
+      };
+   }
+   None
+}
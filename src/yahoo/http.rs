@@ -0,0 +1,215 @@
+use chrono::{ DateTime, Utc };
+use reqwest::header::{ HeaderMap, HeaderName, HeaderValue, CONTENT_TYPE, COOKIE, RETRY_AFTER };
+use reqwest::{ RequestBuilder, StatusCode, Url };
+use std::future::Future;
+use std::sync::Mutex;
+use std::time::{ Duration, Instant };
+use snafu::{ ensure, ResultExt };
+
+use crate::{ config, error, Result };
+use crate::metrics::{ self, Operation };
+use super::{ cache, consent, interstitial, session, QuerySpec };
+
+/// How much of a failed response's body is kept in the error
+const SNIPPET_LENGTH: usize = 200;
+
+/// The headers & proxy a client was built with
+type ClientSettings = (Vec<(String, String)>, Option<String>);
+
+/// The client every call shares, along with the settings it was built with
+static CLIENT: Mutex<Option<(ClientSettings, reqwest::Client)>> = Mutex::new(None);
+
+/// A client that sends the configured headers with every call, through the
+/// configured proxy.  The client - and with it the connection pool - is
+/// shared by every call, and is only built again once the headers or the
+/// proxy change.
+pub fn client() -> Result<reqwest::Client> {
+   let settings = (config::headers(), config::proxy());
+   let mut cached = CLIENT.lock().unwrap_or_else(|e| e.into_inner());
+   if let Some((built_with, client)) = cached.as_ref() {
+      if *built_with == settings { return Ok(client.clone()); }
+   }
+
+   let client = client_builder()?.build().context(error::RequestFailed)?;
+   *cached = Some((settings, client.clone()));
+   Ok(client)
+}
+
+/// The starting point for clients that need more settings than the usual ones
+pub fn client_builder() -> Result<reqwest::ClientBuilder> {
+   let mut headers = HeaderMap::new();
+   for (name, value) in config::headers() {
+      // headers are checked when they're configured, so these don't fail
+      if let (Ok(name), Ok(value)) = (HeaderName::from_bytes(name.as_bytes()), HeaderValue::from_str(&value)) {
+         headers.insert(name, value);
+      }
+   }
+
+   let mut builder = reqwest::Client::builder().default_headers(headers);
+   if let Some(proxy) = config::proxy() {
+      builder = builder.proxy(reqwest::Proxy::all(&proxy).context(error::RequestFailed)?);
+   }
+   Ok(builder)
+}
+
+/// A response that has been read in full
+pub struct Fetched {
+   /// Where the response came from, after any redirects
+   pub url: Url,
+   pub status: StatusCode,
+   pub headers: HeaderMap,
+   pub body: String
+}
+
+/// Sends a request & reads the response whatever its status, telling the
+/// request observer how it went
+pub async fn execute(operation: Operation, request: RequestBuilder) -> Result<Fetched> {
+   // make the call - we do not really expect this to fail.
+   // ie - we won't 404 if the symbol doesn't exist
+   let started = Instant::now();
+   let response = request.send().await
+      .inspect_err(|e| metrics::notify(operation, e.url().map(Url::as_str).unwrap_or_default(), None, 0, started.elapsed()))
+      .context(error::RequestFailed)?;
+
+   let url = response.url().clone();
+   let status = response.status();
+   let headers = response.headers().clone();
+   let body = response.text().await;
+
+   // the body as read, since chunked responses don't say how long they are up front
+   metrics::notify(operation, url.as_str(), Some(status.as_u16()), body.as_ref().map_or(0, |body| body.len() as u64), started.elapsed());
+   let body = body.context(error::UnexpectedErrorRead { url: url.as_str() })?;
+   Ok(Fetched { url, status, headers, body })
+}
+
+/// Sends a request, returning the body of a successful response
+async fn send(operation: Operation, request: RequestBuilder) -> Result<String> {
+   let Fetched { url, status, headers, body } = execute(operation, request).await?;
+   let status = status.as_u16();
+
+   ensure!(!consent::is_consent_page(&url), error::ConsentRequired { url: url.as_str() });
+   ensure!(status != 429, error::CallThrottled { url: url.as_str(), retry_after: retry_after(&headers) });
+   ensure!((200..300).contains(&status), error::CallFailed { url: url.as_str(), status, body: snippet(&body) });
+   interstitial::check(url.as_str(), &body)?;
+   Ok(body)
+}
+
+/// The start of a response body, small enough to keep in an error.  Yahoo!
+/// usually says what went wrong in there.
+pub fn snippet(body: &str) -> String {
+   let body = body.split_whitespace().collect::<Vec<_>>().join(" ");
+   match body.char_indices().nth(SNIPPET_LENGTH) {
+      Some((end, _)) => format!("{}...", &body[..end]),
+      None => body
+   }
+}
+
+/// Reads the `Retry-After` header, which is either a number of seconds or a date
+fn retry_after(headers: &HeaderMap) -> Option<Duration> {
+   let value = headers.get(RETRY_AFTER)?.to_str().ok()?.trim();
+   if let Ok(seconds) = value.parse() { return Some(Duration::from_secs(seconds)); }
+
+   let date = DateTime::parse_from_rfc2822(value).ok()?;
+   Some((date.with_timezone(&Utc) - Utc::now()).to_std().unwrap_or_default())
+}
+
+/// Sends a request, retrying temporary failures according to the retry policy.
+/// When Yahoo! says how long to wait, we wait that long instead - unless it
+/// is longer than the policy's `max_backoff`, in which case the throttling
+/// is handed back so the caller can decide what to do.
+async fn send_with_retries(operation: Operation, request: impl Fn() -> RequestBuilder) -> Result<String> {
+   let policy = config::retry_policy();
+
+   let mut attempt = 1;
+   loop {
+      match send(operation, request()).await {
+         Err(e) if attempt < policy.max_attempts && e.is_retryable() && e.retry_after().is_none_or(|wait| wait <= policy.max_backoff) => {
+            tokio::time::delay_for(e.retry_after().unwrap_or_else(|| policy.backoff(attempt))).await;
+            attempt += 1;
+         },
+         result => return result
+      }
+   }
+}
+
+/// Makes a GET call to Yahoo!, returning the body of a successful response.
+///
+/// Calls that need a session get the cookie & crumb attached, with the session
+/// being refreshed once if Yahoo! turns it down.  When Yahoo! asks for cookie
+/// consent first we agree to it and try again.  Responses come from the cache
+/// when it's turned on.
+pub async fn get(query: &QuerySpec) -> Result<String> {
+   match get_once(query).await {
+      Err(e) => match e.consent_page() {
+         Some(page) => {
+            consent::accept(&page).await?;
+            get_once(query).await
+         },
+         None => Err(e)
+      },
+      result => result
+   }
+}
+
+async fn get_once(query: &QuerySpec) -> Result<String> {
+   with_session(query, |url, cookie| async move { get_with(url, cookie.as_deref()).await }).await
+}
+
+/// Makes a call with the session cookie & crumb when the query needs them,
+/// refreshing the session once if Yahoo! turns it down
+async fn with_session<F, Fut>(query: &QuerySpec, call: F) -> Result<String>
+where
+   F: Fn(Url, Option<String>) -> Fut,
+   Fut: Future<Output = Result<String>>
+{
+   if !query.is_authenticated() { return call(query.url()?, None).await; }
+
+   let call = &call;
+   let attempt = || async move {
+      let credentials = session::credentials().await?;
+      call(query.clone().crumb(&credentials.crumb).url()?, Some(credentials.cookie)).await
+   };
+   match attempt().await {
+      Err(e) if e.is_unauthorized() => {
+         session::invalidate();
+         attempt().await
+      },
+      result => result
+   }
+}
+
+/// The cookies to send - consent cookies go along with everything, session cookies only where asked for
+fn cookies(session: Option<&str>) -> String {
+   consent::cookies().into_iter().chain(session.map(str::to_string)).collect::<Vec<_>>().join("; ")
+}
+
+fn with_cookies(request: RequestBuilder, cookies: &str) -> RequestBuilder {
+   if cookies.is_empty() { request } else { request.header(COOKIE, cookies) }
+}
+
+async fn get_with(url: Url, cookie: Option<&str>) -> Result<String> {
+   let ttl = config::cache_ttl();
+   if let Some(body) = ttl.and_then(|ttl| cache::lookup(url.as_str(), ttl)) { return Ok(body); }
+
+   let cookies = cookies(cookie);
+   let client = client()?;
+   let body = send_with_retries(Operation::Get, || with_cookies(client.get(url.clone()), &cookies)).await?;
+
+   if let Some(ttl) = ttl { cache::store(url.as_str(), &body, ttl); }
+   Ok(body)
+}
+
+/// Makes a POST call to Yahoo! with a JSON body, returning the body of a
+/// successful response.  Calls that need a session get it the same way as `get`.
+pub async fn post(query: &QuerySpec, body: &serde_json::Value) -> Result<String> {
+   with_session(query, |url, cookie| async move {
+      let cookies = cookies(cookie.as_deref());
+      let client = client()?;
+      send_with_retries(Operation::Post, || {
+         let request = client.post(url.clone())
+            .header(CONTENT_TYPE, "application/json")
+            .body(body.to_string());
+         with_cookies(request, &cookies)
+      }).await
+   }).await
+}
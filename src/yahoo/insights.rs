@@ -0,0 +1,61 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Result };
+use super::{ http, QuerySpec };
+
+ez_serde!(Outlook {
+   state_description: Option<String>,
+   direction: Option<String>,
+   score: Option<u32>,
+   score_description: Option<String>
+});
+
+ez_serde!(TechnicalEvents {
+   provider: Option<String>,
+   short_term_outlook: Option<Outlook>,
+   intermediate_term_outlook: Option<Outlook>,
+   long_term_outlook: Option<Outlook>
+});
+
+ez_serde!(KeyTechnicals {
+   provider: Option<String>,
+   support: Option<f64>,
+   resistance: Option<f64>,
+   stop_loss: Option<f64>
+});
+
+ez_serde!(Valuation {
+   provider: Option<String>,
+   description: Option<String>,
+   discount: Option<String>,
+   relative_value: Option<String>
+});
+
+ez_serde!(InstrumentInfo {
+   technical_events: Option<TechnicalEvents>,
+   key_technicals: Option<KeyTechnicals>,
+   valuation: Option<Valuation>
+});
+
+ez_serde!(Insights { symbol: String, instrument_info: Option<InstrumentInfo> });
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Finance { result: Option<Insights>, error: Option<Error> });
+ez_serde!(Response { finance: Finance });
+
+/// Reads an insights response - there are no insights for unknown symbols
+pub fn parse_insights(data: &str) -> Result<Option<Insights>> {
+   let finance = serde_json::from_str::<Response>(data).context(error::BadData)?.finance;
+
+   if let Some(err) = finance.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   Ok(finance.result)
+}
+
+pub async fn load_insights(symbol: &str) -> Result<Insights> {
+   let query = QuerySpec::query("/ws/insights/v1/finance/insights").symbol(symbol);
+   Ok(parse_insights(&http::get(&query).await?)?.context(error::SymbolNotFound { symbol })?)
+}
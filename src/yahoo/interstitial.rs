@@ -0,0 +1,23 @@
+use snafu::ensure;
+
+use crate::{ error, Result };
+use super::web_scraper::DATA_VAR;
+
+/// Phrases from the page Yahoo! serves while it's down for maintenance
+const MAINTENANCE: &[&str] = &["will be right back", "temporarily unavailable", "down for maintenance"];
+
+/// Phrases from the captcha & rate limit pages Yahoo! serves when it blocks us
+const BLOCKED: &[&str] = &["captcha", "unusual traffic", "too many requests"];
+
+/// Fails when Yahoo! answered with a maintenance or block page instead of
+/// what we asked for.  These come back with a 200 status, so the status
+/// alone doesn't tell us anything went wrong.
+pub fn check(url: &str, body: &str) -> Result<()> {
+   // JSON responses & real quote pages are never one of these
+   if !body.trim_start().starts_with('<') || body.contains(DATA_VAR) { return Ok(()); }
+
+   let page = body.to_lowercase();
+   ensure!(!BLOCKED.iter().any(|phrase| page.contains(phrase)), error::Blocked { url });
+   ensure!(!MAINTENANCE.iter().any(|phrase| page.contains(phrase)), error::ServiceUnavailable { url });
+   Ok(())
+}
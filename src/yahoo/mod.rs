@@ -1,8 +1,44 @@
+mod cache;
+pub use cache::clear_cache;
+mod consent;
+mod http;
+mod interstitial;
+mod query;
+pub use query::QuerySpec;
+mod raw;
+pub use raw::Raw;
+mod schema;
+mod session;
+
 mod chart;
-pub use chart::{load_daily, load_daily_range, Data};
+pub use chart::{chart_query, load_bars, load_chart, load_daily, load_daily_range, parse_chart, CorporateEvents, Data, Meta, Period, TradingPeriods};
+
+mod insights;
+pub use insights::{load_insights, parse_insights, Insights, Outlook};
+
+mod recommendations;
+pub use recommendations::{load_recommendations, parse_recommendations, Recommendations};
 
 mod realtime;
-pub use realtime::{PricingData, PricingData_MarketHoursType};
+pub use realtime::{pricing_data, PricingData};
+
+mod quote;
+pub use quote::load_quotes;
+
+mod quote_summary;
+pub use quote_summary::{load_quote_summary, parse_quote_summary, FinancialData, QuoteSummary, Trend};
+
+mod quote_type;
+pub use quote_type::{load_quote_type, parse_quote_type};
+
+mod screener;
+pub use screener::{criteria, load_screen, parse_screen, ScreenerQuote};
+
+mod timeseries;
+pub use timeseries::{load_timeseries, parse_timeseries, Reported};
+
+mod validation;
+pub use validation::{load_validation, parse_validation};
 
 mod web_scraper;
-pub use web_scraper::{scrape, QuoteSummaryStore, CompanyProfile};
+pub use web_scraper::{parse_page, scrape, QuoteSummaryStore, CompanyProfile};
@@ -0,0 +1,159 @@
+use reqwest::Url;
+use snafu::ResultExt;
+use std::fmt;
+
+use crate::{ config, error, Interval, Result };
+
+/// The Yahoo! hosts we make calls against
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Host {
+   /// The JSON query API
+   Query,
+
+   /// The second copy of the JSON query API
+   QueryFallback,
+
+   /// The finance.yahoo.com website
+   Web,
+
+   /// The page that hands out session cookies
+   Cookie
+}
+impl Host {
+   fn base(&self) -> String {
+      let endpoints = config::endpoints();
+      match self {
+         Host::Query => endpoints.query,
+         Host::QueryFallback => endpoints.query_fallback.unwrap_or(endpoints.query),
+         Host::Web => endpoints.web,
+         Host::Cookie => endpoints.cookie
+      }
+   }
+}
+
+/// A typed description of a call to Yahoo!, shared by all of the endpoints
+/// so that every URL is built the same way.
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuerySpec {
+   host: Host,
+   path: Vec<String>,
+   params: Vec<(&'static str, String)>,
+   authenticated: bool
+}
+impl QuerySpec {
+   fn new(host: Host, path: &str) -> QuerySpec {
+      let path = path.split('/').filter(|segment| !segment.is_empty()).map(str::to_string).collect();
+      QuerySpec { host, path, params: Vec::new(), authenticated: false }
+   }
+
+   /// A call against the JSON query API.  ie. `/v8/finance/chart`
+   pub fn query(path: &str) -> QuerySpec { QuerySpec::new(Host::Query, path) }
+
+   /// A call against the finance.yahoo.com website.  ie. `/quote`
+   pub fn web(path: &str) -> QuerySpec { QuerySpec::new(Host::Web, path) }
+
+   /// The call that starts a new session by handing out a cookie
+   pub fn cookie() -> QuerySpec { QuerySpec::new(Host::Cookie, "/") }
+
+   /// The same call against the second copy of the query API, if there is one to fail over to
+   pub fn fallback(&self) -> Option<QuerySpec> {
+      if self.host != Host::Query || config::endpoints().query_fallback.is_none() { return None; }
+      Some(QuerySpec { host: Host::QueryFallback, ..self.clone() })
+   }
+
+   /// Marks the call as needing the session cookie & crumb
+   pub fn authenticated(mut self) -> QuerySpec {
+      self.authenticated = true;
+      self
+   }
+
+   /// Whether the call needs the session cookie & crumb
+   pub fn is_authenticated(&self) -> bool { self.authenticated }
+
+   /// Adds a single segment to the end of the path, encoding whatever
+   /// can't go in a URL as is.  ie. a symbol like `^DJI`
+   pub fn segment(mut self, segment: &str) -> QuerySpec {
+      self.path.push(segment.to_string());
+      self
+   }
+
+   fn param(mut self, name: &'static str, value: impl ToString) -> QuerySpec {
+      self.params.push((name, value.to_string()));
+      self
+   }
+
+   /// The amount of time to load data for.  ie. `6mo`
+   pub fn range(self, range: Interval) -> QuerySpec { self.param("range", range) }
+
+   /// The size of each bar of data.  ie. `1d`
+   pub fn interval(self, interval: impl ToString) -> QuerySpec { self.param("interval", interval) }
+
+   /// The start of a date range in seconds since the EPOCH
+   pub fn start(self, start: i64) -> QuerySpec { self.param("period1", start) }
+
+   /// The end of a date range in seconds since the EPOCH
+   pub fn end(self, end: i64) -> QuerySpec { self.param("period2", end) }
+
+   /// The corporate events to include.  ie. `div|split`
+   pub fn events(self, events: &[&str]) -> QuerySpec { self.param("events", events.join("|")) }
+
+   /// Whether to include pre & post market data
+   pub fn include_pre_post(self, include: bool) -> QuerySpec { self.param("includePrePost", include) }
+
+   /// The quote summary modules to load.  ie. `assetProfile,quoteType`
+   pub fn modules(self, modules: &[&str]) -> QuerySpec { self.param("modules", modules.join(",")) }
+
+   /// A single symbol passed as a parameter
+   pub fn symbol(self, symbol: &str) -> QuerySpec { self.param("symbol", symbol) }
+
+   /// Many symbols passed as a single parameter
+   pub fn symbols(self, symbols: &[&str]) -> QuerySpec { self.param("symbols", symbols.join(",")) }
+
+   /// The fundamentals to load.  ie. `annualTotalRevenue,annualNetIncome`
+   pub fn types(self, types: &[&str]) -> QuerySpec { self.param("type", types.join(",")) }
+
+   /// The symbol passed as the `p` parameter on website pages
+   pub fn page_symbol(self, symbol: &str) -> QuerySpec { self.param("p", symbol) }
+
+   /// The session crumb that goes along with the cookie
+   pub fn crumb(self, crumb: &str) -> QuerySpec { self.param("crumb", crumb) }
+
+   /// Builds the full URL for the call
+   pub fn url(&self) -> Result<Url> {
+      let base = self.host.base();
+      let mut url = Url::parse(&base).context(error::InternalURL { url: &base })?;
+
+      // the path goes after whatever path the base has, so mirrors can live under a prefix
+      url.path_segments_mut()
+         .map_err(|_| url::ParseError::RelativeUrlWithCannotBeABaseBase)
+         .context(error::InternalURL { url: &base })?
+         .pop_if_empty()
+         .extend(&self.path);
+
+      // `Url` leaves the `^` of index symbols alone, which isn't valid in a URL
+      if url.path().contains('^') {
+         let path = url.path().replace('^', "%5E");
+         url.set_path(&path);
+      }
+
+      // the session cookie is the same everywhere, everything else can be localized
+      let locale = config::locale().filter(|_| self.host != Host::Cookie);
+      if !self.params.is_empty() || locale.is_some() {
+         let mut pairs = url.query_pairs_mut();
+         for (name, value) in &self.params { pairs.append_pair(name, value); }
+         if let Some(locale) = locale {
+            pairs.append_pair("lang", &locale.lang);
+            pairs.append_pair("region", &locale.region);
+         }
+      }
+      Ok(url)
+   }
+}
+impl fmt::Display for QuerySpec {
+   fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+      match self.url() {
+         Ok(url) => write!(f, "{}", url),
+         Err(_) => write!(f, "{:?}/{}", self.host, self.path.join("/"))
+      }
+   }
+}
@@ -0,0 +1,100 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Result };
+use super::{ http, pricing_data::{ MarketHoursType, QuoteType }, PricingData, QuerySpec };
+
+ez_serde!(MarketQuote {
+   symbol: String,
+
+   #[serde(default)]
+   quote_type: String,
+
+   #[serde(default)]
+   market_state: String,
+
+   regular_market_price: Option<f64>,
+   regular_market_time: Option<i64>,
+   regular_market_change: Option<f64>,
+   regular_market_change_percent: Option<f64>,
+   regular_market_previous_close: Option<f64>,
+   regular_market_volume: Option<u64>,
+   regular_market_day_high: Option<f64>,
+   regular_market_day_low: Option<f64>,
+
+   pre_market_price: Option<f64>,
+   pre_market_time: Option<i64>,
+   pre_market_change: Option<f64>,
+   pre_market_change_percent: Option<f64>,
+
+   post_market_price: Option<f64>,
+   post_market_time: Option<i64>,
+   post_market_change: Option<f64>,
+   post_market_change_percent: Option<f64>,
+
+   bid: Option<f64>,
+   ask: Option<f64>,
+   market_cap: Option<f64>,
+
+   // crypto currencies only
+   volume_24_hr: Option<u64>,
+   circulating_supply: Option<f64>
+});
+impl MarketQuote {
+   /// Turns the quote into the pricing data Yahoo! would have streamed for
+   /// it - using the pre or post market price when that is the latest.
+   pub fn to_pricing_data(&self) -> PricingData {
+      let regular = (self.regular_market_price, self.regular_market_time, self.regular_market_change, self.regular_market_change_percent);
+      let extended = match self.market_state.as_str() {
+         "PRE" | "PREPRE" => Some((MarketHoursType::PreMarket, self.pre_market_price, self.pre_market_time, self.pre_market_change, self.pre_market_change_percent)),
+         "POST" | "POSTPOST" => Some((MarketHoursType::PostMarket, self.post_market_price, self.post_market_time, self.post_market_change, self.post_market_change_percent)),
+         _ => None
+      };
+      let (hours, (price, time, change, change_percent)) = match extended {
+         Some((hours, Some(price), time, change, change_percent)) => (hours, (Some(price), time, change, change_percent)),
+         _ => (MarketHoursType::RegularMarket, regular)
+      };
+
+      // anything Yahoo! leaves out is zero, just like in the stream
+      let mut data = PricingData {
+         id: self.symbol.clone(),
+         price: price.unwrap_or_default() as f32,
+         time: time.unwrap_or_default() * 1000,
+         change: change.unwrap_or_default() as f32,
+         change_percent: change_percent.unwrap_or_default() as f32,
+         previous_close: self.regular_market_previous_close.unwrap_or_default() as f32,
+         day_volume: self.regular_market_volume.unwrap_or_default() as i64,
+         day_high: self.regular_market_day_high.unwrap_or_default() as f32,
+         day_low: self.regular_market_day_low.unwrap_or_default() as f32,
+         bid: self.bid.unwrap_or_default() as f32,
+         ask: self.ask.unwrap_or_default() as f32,
+         marketcap: self.market_cap.unwrap_or_default(),
+         vol_24hr: self.volume_24_hr.unwrap_or_default() as i64,
+         circulating_supply: self.circulating_supply.unwrap_or_default(),
+         ..PricingData::default()
+      };
+      data.set_quote_type(QuoteType::from_str_name(&self.quote_type).unwrap_or(QuoteType::None));
+      data.set_market_hours(hours);
+      data
+   }
+}
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Results { result: Option<Vec<MarketQuote>>, error: Option<Error> });
+ez_serde!(Response { quote_response: Results });
+
+/// Reads a quote response
+pub fn parse_quotes(data: &str) -> Result<Vec<MarketQuote>> {
+   let results = serde_json::from_str::<Response>(data).context(error::BadData)?.quote_response;
+
+   if let Some(err) = results.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+   Ok(results.result.context(error::UnexpectedErrorYahoo)?)
+}
+
+/// Loads the latest quotes for the symbols in a single call
+pub async fn load_quotes(symbols: &[&str]) -> Result<Vec<MarketQuote>> {
+   let query = QuerySpec::query("/v7/finance/quote").symbols(symbols).authenticated();
+   parse_quotes(&http::get(&query).await?)
+}
@@ -0,0 +1,304 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+use std::collections::HashMap;
+
+use crate::{ error, Result };
+use super::{ http, raw, raw::Raw, QuerySpec };
+use super::web_scraper::{ CompanyProfile, FundProfile, QuoteSummaryStore, QuoteType };
+
+ez_serde!(BondHoldings {
+   #[serde(default, deserialize_with = "raw::value")] maturity: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] duration: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] credit_quality: Option<f64>
+});
+
+ez_serde!(TopHoldings {
+   #[serde(default, deserialize_with = "raw::value")] stock_position: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] bond_position: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] cash_position: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] other_position: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] preferred_position: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] convertible_position: Option<f64>,
+   bond_holdings: Option<BondHoldings>,
+   #[serde(default)] bond_ratings: Vec<HashMap<String, Raw<f64>>>,
+   #[serde(default)] sector_weightings: Vec<HashMap<String, Raw<f64>>>
+});
+
+ez_serde!(Price { currency: Option<String> });
+
+ez_serde!(FinancialData {
+   financial_currency: Option<String>,
+   recommendation_key: Option<String>,
+   #[serde(default, deserialize_with = "raw::value")] current_price: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] target_high_price: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] target_low_price: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] target_mean_price: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] target_median_price: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] recommendation_mean: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] number_of_analyst_opinions: Option<u32>,
+   #[serde(default, deserialize_with = "raw::value")] total_cash: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] total_cash_per_share: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] total_debt: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] debt_to_equity: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] total_revenue: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] revenue_per_share: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] revenue_growth: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] earnings_growth: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] gross_profits: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] ebitda: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] gross_margins: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] operating_margins: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] ebitda_margins: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] profit_margins: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] free_cashflow: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] operating_cashflow: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] return_on_assets: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] return_on_equity: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] current_ratio: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] quick_ratio: Option<f64>
+});
+
+ez_serde!(KeyStatistics {
+   #[serde(default, deserialize_with = "raw::value")] enterprise_value: Option<f64>,
+   #[serde(rename = "forwardPE", default, deserialize_with = "raw::value")] forward_pe: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] peg_ratio: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] trailing_eps: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] forward_eps: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] beta: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] book_value: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] price_to_book: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] profit_margins: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] earnings_quarterly_growth: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] enterprise_to_revenue: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] enterprise_to_ebitda: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] shares_outstanding: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] float_shares: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] shares_short: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] short_ratio: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] short_percent_of_float: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] held_percent_insiders: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] held_percent_institutions: Option<f64>,
+   #[serde(rename = "52WeekChange", default, deserialize_with = "raw::value")] change_52_week: Option<f64>,
+   #[serde(rename = "SandP52WeekChange", default, deserialize_with = "raw::value")] sp500_change_52_week: Option<f64>
+});
+
+ez_serde!(SummaryDetail {
+   #[serde(default, deserialize_with = "raw::value")] previous_close: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] open: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] day_low: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] day_high: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] fifty_two_week_low: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] fifty_two_week_high: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] fifty_day_average: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] two_hundred_day_average: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] volume: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] average_volume: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] average_volume_10days: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] bid: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] ask: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] bid_size: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] ask_size: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] market_cap: Option<f64>,
+   #[serde(rename = "trailingPE", default, deserialize_with = "raw::value")] trailing_pe: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] dividend_rate: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] dividend_yield: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] ex_dividend_date: Option<i64>,
+   #[serde(default, deserialize_with = "raw::value")] payout_ratio: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] five_year_avg_dividend_yield: Option<f64>,
+   currency: Option<String>
+});
+
+ez_serde!(EarningsQuarter {
+   date: String,
+   #[serde(default, deserialize_with = "raw::value")] actual: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] estimate: Option<f64>
+});
+
+ez_serde!(EarningsChart {
+   #[serde(default)] quarterly: Vec<EarningsQuarter>,
+   #[serde(default)] earnings_date: Vec<Raw<i64>>,
+   is_earnings_date_estimate: Option<bool>
+});
+
+ez_serde!(YearlyFinancials {
+   date: i32,
+   #[serde(default, deserialize_with = "raw::value")] revenue: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] earnings: Option<f64>
+});
+
+ez_serde!(QuarterlyFinancials {
+   date: String,
+   #[serde(default, deserialize_with = "raw::value")] revenue: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] earnings: Option<f64>
+});
+
+ez_serde!(FinancialsChart {
+   #[serde(default)] yearly: Vec<YearlyFinancials>,
+   #[serde(default)] quarterly: Vec<QuarterlyFinancials>
+});
+
+ez_serde!(EarningsModule {
+   earnings_chart: Option<EarningsChart>,
+   financials_chart: Option<FinancialsChart>,
+   financial_currency: Option<String>
+});
+
+ez_serde!(TrendEstimate {
+   #[serde(default, deserialize_with = "raw::value")] avg: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] low: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] high: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] year_ago_eps: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] year_ago_revenue: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] number_of_analysts: Option<u32>,
+   #[serde(default, deserialize_with = "raw::value")] growth: Option<f64>
+});
+
+ez_serde!(Trend {
+   period: String,
+   end_date: Option<String>,
+   #[serde(default, deserialize_with = "raw::value")] growth: Option<f64>,
+   earnings_estimate: Option<TrendEstimate>,
+   revenue_estimate: Option<TrendEstimate>
+});
+
+ez_serde!(EarningsTrend { #[serde(default)] trend: Vec<Trend> });
+
+ez_serde!(EarningsSurprise {
+   period: Option<String>,
+   #[serde(default, deserialize_with = "raw::value")] quarter: Option<i64>,
+   #[serde(default, deserialize_with = "raw::value")] eps_actual: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] eps_estimate: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] eps_difference: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] surprise_percent: Option<f64>
+});
+
+ez_serde!(EarningsHistory { #[serde(default)] history: Vec<EarningsSurprise> });
+
+ez_serde!(RecommendationCounts {
+   period: String,
+   #[serde(default)] strong_buy: u32,
+   #[serde(default)] buy: u32,
+   #[serde(default)] hold: u32,
+   #[serde(default)] sell: u32,
+   #[serde(default)] strong_sell: u32
+});
+
+ez_serde!(RecommendationTrend { #[serde(default)] trend: Vec<RecommendationCounts> });
+
+ez_serde!(GradeChange {
+   epoch_grade_date: i64,
+   firm: String,
+   to_grade: Option<String>,
+   from_grade: Option<String>,
+   action: Option<String>
+});
+
+ez_serde!(UpgradeDowngradeHistory { #[serde(default)] history: Vec<GradeChange> });
+
+ez_serde!(Ownership {
+   organization: String,
+   #[serde(default, deserialize_with = "raw::value")] report_date: Option<i64>,
+   #[serde(default, deserialize_with = "raw::value")] pct_held: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] position: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] value: Option<f64>,
+   #[serde(default, deserialize_with = "raw::value")] pct_change: Option<f64>
+});
+
+ez_serde!(OwnershipList { #[serde(default)] ownership_list: Vec<Ownership> });
+
+ez_serde!(InsiderHolder {
+   name: String,
+   relation: Option<String>,
+   transaction_description: Option<String>,
+   #[serde(default, deserialize_with = "raw::value")] latest_trans_date: Option<i64>,
+   #[serde(default, deserialize_with = "raw::value")] position_direct: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] position_direct_date: Option<i64>,
+   #[serde(default, deserialize_with = "raw::value")] position_indirect: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] position_indirect_date: Option<i64>
+});
+
+ez_serde!(InsiderHolders { #[serde(default)] holders: Vec<InsiderHolder> });
+
+ez_serde!(InsiderTransaction {
+   filer_name: String,
+   filer_relation: Option<String>,
+   transaction_text: Option<String>,
+   ownership: Option<String>,
+   #[serde(default, deserialize_with = "raw::value")] start_date: Option<i64>,
+   #[serde(default, deserialize_with = "raw::value")] shares: Option<u64>,
+   #[serde(default, deserialize_with = "raw::value")] value: Option<f64>
+});
+
+ez_serde!(InsiderTransactions { #[serde(default)] transactions: Vec<InsiderTransaction> });
+
+ez_serde!(CalendarEarnings {
+   #[serde(default)] earnings_date: Vec<Raw<i64>>,
+   is_earnings_date_estimate: Option<bool>
+});
+
+ez_serde!(CalendarEvents { earnings: Option<CalendarEarnings> });
+
+ez_serde!(SummaryQuoteType {
+   long_name: Option<String>,
+   short_name: Option<String>,
+   #[serde(default)] quote_type: String
+});
+
+ez_serde!(QuoteSummary {
+   top_holdings: Option<TopHoldings>,
+   quote_type: Option<SummaryQuoteType>,
+   asset_profile: Option<CompanyProfile>,
+   fund_profile: Option<FundProfile>,
+   price: Option<Price>,
+   financial_data: Option<FinancialData>,
+   default_key_statistics: Option<KeyStatistics>,
+   summary_detail: Option<SummaryDetail>,
+   earnings: Option<EarningsModule>,
+   earnings_trend: Option<EarningsTrend>,
+   calendar_events: Option<CalendarEvents>,
+   earnings_history: Option<EarningsHistory>,
+   recommendation_trend: Option<RecommendationTrend>,
+   upgrade_downgrade_history: Option<UpgradeDowngradeHistory>,
+   institution_ownership: Option<OwnershipList>,
+   insider_holders: Option<InsiderHolders>,
+   insider_transactions: Option<InsiderTransactions>
+});
+impl QuoteSummary {
+   /// Puts the profile modules in the same shape as the quote page's data,
+   /// falling back to the short name for listings without a long name.
+   /// `None` without the `quoteType` module.
+   pub fn into_profile(self) -> Option<QuoteSummaryStore> {
+      let quote_type = self.quote_type?;
+      let name = quote_type.long_name.or(quote_type.short_name).unwrap_or_default();
+
+      Some(QuoteSummaryStore {
+         financial_data: self.financial_data,
+         fund_profile: self.fund_profile,
+         price: self.price,
+         company_profile: self.asset_profile,
+         quote_type: QuoteType { name, kind: quote_type.quote_type }
+      })
+   }
+}
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Results { result: Option<Vec<QuoteSummary>>, error: Option<Error> });
+ez_serde!(Response { quote_summary: Results });
+
+/// Reads a quote summary response - there is no summary for unknown symbols
+pub fn parse_quote_summary(data: &str) -> Result<Option<QuoteSummary>> {
+   let results = serde_json::from_str::<Response>(data).context(error::BadData)?.quote_summary;
+
+   if let Some(err) = results.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let result = results.result.context(error::UnexpectedErrorYahoo)?;
+   Ok(result.into_iter().next())
+}
+
+/// Loads the requested quote summary modules for a symbol.  ie. `["topHoldings"]`
+pub async fn load_quote_summary(symbol: &str, modules: &[&str]) -> Result<QuoteSummary> {
+   let query = QuerySpec::query("/v10/finance/quoteSummary").segment(symbol).modules(modules).authenticated();
+   Ok(parse_quote_summary(&http::get(&query).await?)?.context(error::SymbolNotFound { symbol })?)
+}
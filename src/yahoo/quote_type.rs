@@ -0,0 +1,39 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Result };
+use super::{ http, QuerySpec };
+
+ez_serde!(QuoteType {
+   symbol: String,
+
+   #[serde(rename = "quoteType")]
+   kind: String,
+
+   exchange: Option<String>,
+
+   short_name: Option<String>,
+
+   long_name: Option<String>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Results { result: Option<Vec<QuoteType>>, error: Option<Error> });
+ez_serde!(Response { #[serde(rename = "quoteType")] quote_type: Results });
+
+/// Reads a quote type response - there is no quote type for unknown symbols
+pub fn parse_quote_type(data: &str) -> Result<Option<QuoteType>> {
+   let results = serde_json::from_str::<Response>(data).context(error::BadData)?.quote_type;
+
+   if let Some(err) = results.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let result = results.result.context(error::UnexpectedErrorYahoo)?;
+   Ok(result.into_iter().next())
+}
+
+pub async fn load_quote_type(symbol: &str) -> Result<QuoteType> {
+   let query = QuerySpec::query("/v1/finance/quoteType").segment(symbol);
+   Ok(parse_quote_type(&http::get(&query).await?)?.context(error::SymbolNotFound { symbol })?)
+}
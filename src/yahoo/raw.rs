@@ -0,0 +1,14 @@
+use serde::{ Deserialize, Deserializer };
+
+/// Yahoo wraps most numbers as `{ "raw": 1.23, "fmt": "1.23" }` and sends an
+/// empty `{}` when there is no value.
+#[derive(Clone, Deserialize)]
+pub struct Raw<T> { pub raw: Option<T> }
+
+/// Deserializes a Yahoo `{ raw, fmt }` object into just the raw value.
+///
+/// Use with `#[serde(default, deserialize_with = "raw::value")]`
+pub fn value<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where D: Deserializer<'de>, T: Deserialize<'de> {
+   Ok(Option::<Raw<T>>::deserialize(deserializer)?.and_then(|value| value.raw))
+}
@@ -0,0 +1,29 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Result };
+use super::{ http, QuerySpec };
+
+ez_serde!(RecommendedSymbol { symbol: String, score: f64 });
+ez_serde!(Recommendations { symbol: String, recommended_symbols: Vec<RecommendedSymbol> });
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Finance { result: Option<Vec<Recommendations>>, error: Option<Error> });
+ez_serde!(Response { finance: Finance });
+
+/// Reads a recommendations response - there are no recommendations for unknown symbols
+pub fn parse_recommendations(data: &str) -> Result<Option<Recommendations>> {
+   let finance = serde_json::from_str::<Response>(data).context(error::BadData)?.finance;
+
+   if let Some(err) = finance.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let result = finance.result.context(error::UnexpectedErrorYahoo)?;
+   Ok(result.into_iter().next())
+}
+
+pub async fn load_recommendations(symbol: &str) -> Result<Recommendations> {
+   let query = QuerySpec::query("/v6/finance/recommendationsbysymbol").segment(symbol);
+   Ok(parse_recommendations(&http::get(&query).await?)?.context(error::SymbolNotFound { symbol })?)
+}
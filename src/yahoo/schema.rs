@@ -0,0 +1,42 @@
+use snafu::ResultExt;
+
+use crate::{ error, Result };
+use crate::schema::{ notify, SchemaMatch };
+
+/// A single version of an endpoint's response schema
+pub type Version<T> = (&'static str, fn(&str) -> serde_json::Result<T>);
+
+/// The known versions of an endpoint's response schema, newest first.
+pub struct Schema<T: 'static> {
+   pub endpoint: &'static str,
+   pub versions: &'static [Version<T>]
+}
+impl<T> Schema<T> {
+   /// Reads a response using the first schema version that matches.
+   ///
+   /// If none match, the error from the newest version is reported since it
+   /// is the one Yahoo should be sending.
+   pub fn parse(&self, data: &str) -> Result<T> {
+      let mut first_error = None;
+
+      for (i, (version, parse)) in self.versions.iter().enumerate() {
+         match parse(data) {
+            Ok(value) => {
+               notify(&SchemaMatch { endpoint: self.endpoint, version, fallback: i > 0 });
+               return Ok(value);
+            },
+            Err(e) => { first_error.get_or_insert(e); }
+         }
+      }
+
+      match first_error {
+         Some(e) => Err(e).context(error::BadData)?,
+         None => error::InternalLogic { reason: format!("no schema versions for {}", self.endpoint) }.fail()?
+      }
+   }
+}
+
+/// Parses the JSON as-is into the response type
+pub fn json<T: serde::de::DeserializeOwned>(data: &str) -> serde_json::Result<T> {
+   serde_json::from_str(data)
+}
@@ -0,0 +1,60 @@
+use serde::Deserialize;
+use serde_json::{ json, Value };
+use snafu::{ OptionExt, ResultExt };
+
+use crate::{ error, Result };
+use super::{ http, QuerySpec };
+
+ez_serde!(ScreenerQuote {
+   symbol: String,
+   short_name: Option<String>,
+   exchange: Option<String>,
+   currency: Option<String>,
+   regular_market_price: Option<f64>,
+   regular_market_change: Option<f64>,
+   regular_market_change_percent: Option<f64>,
+   regular_market_volume: Option<u64>,
+   pre_market_price: Option<f64>,
+   pre_market_change: Option<f64>,
+   pre_market_change_percent: Option<f64>,
+   post_market_price: Option<f64>,
+   post_market_change: Option<f64>,
+   post_market_change_percent: Option<f64>
+});
+
+ez_serde!(Screen { #[serde(default)] quotes: Vec<ScreenerQuote> });
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Finance { result: Option<Vec<Screen>>, error: Option<Error> });
+ez_serde!(Response { finance: Finance });
+
+/// A single screener criteria - ie. `("gt", "dayvolume", 15000)`
+pub fn criteria(operator: &str, field: &str, value: Value) -> Value {
+   json!({ "operator": operator, "operands": [ field, value ] })
+}
+
+/// Runs a custom screen of equities matching all of the criteria
+pub async fn load_screen(criteria: Vec<Value>, sort_field: &str, ascending: bool, count: u32) -> Result<Vec<ScreenerQuote>> {
+   let screen = json!({
+      "offset": 0,
+      "size": count,
+      "sortField": sort_field,
+      "sortType": if ascending { "ASC" } else { "DESC" },
+      "quoteType": "EQUITY",
+      "query": { "operator": "AND", "operands": criteria }
+   });
+
+   parse_screen(&http::post(&QuerySpec::query("/v1/finance/screener").authenticated(), &screen).await?)
+}
+
+/// Reads a screener response
+pub fn parse_screen(data: &str) -> Result<Vec<ScreenerQuote>> {
+   let finance = serde_json::from_str::<Response>(data).context(error::BadData)?.finance;
+
+   if let Some(err) = finance.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let result = finance.result.context(error::UnexpectedErrorYahoo)?;
+   Ok(result.into_iter().flat_map(|screen| screen.quotes).collect())
+}
@@ -0,0 +1,73 @@
+use futures::lock::Mutex;
+use reqwest::header::{ COOKIE, SET_COOKIE };
+use snafu::ensure;
+use std::sync::RwLock;
+
+use crate::{ config, error, Result };
+use crate::metrics::Operation;
+use super::{ http, QuerySpec };
+
+/// The cookie & crumb that Yahoo! expects on its authenticated endpoints
+#[derive(Debug, Clone, PartialEq)]
+pub struct Credentials {
+   pub cookie: String,
+   pub crumb: String
+}
+
+/// The sessions we have, by the server that handed out the cookie
+static CREDENTIALS: RwLock<Vec<(String, Credentials)>> = RwLock::new(Vec::new());
+
+/// Held while fetching a session, so calls that need one at the same time share a single fetch
+static FETCHING: Mutex<()> = Mutex::new(());
+
+fn current(server: &str) -> Option<Credentials> {
+   CREDENTIALS.read().unwrap_or_else(|e| e.into_inner()).iter()
+      .find(|(from, _)| from == server)
+      .map(|(_, credentials)| credentials.clone())
+}
+
+/// The current session credentials, fetching them from Yahoo! if we don't have any yet
+pub async fn credentials() -> Result<Credentials> {
+   let server = config::endpoints().cookie;
+   if let Some(credentials) = current(&server) { return Ok(credentials); }
+
+   // another call may have fetched them while we waited our turn
+   let _fetching = FETCHING.lock().await;
+   if let Some(credentials) = current(&server) { return Ok(credentials); }
+
+   let credentials = fetch().await?;
+   let mut sessions = CREDENTIALS.write().unwrap_or_else(|e| e.into_inner());
+   sessions.retain(|(from, _)| *from != server);
+   sessions.push((server, credentials.clone()));
+   Ok(credentials)
+}
+
+/// Forgets the current session so that the next call starts a fresh one
+pub fn invalidate() {
+   let server = config::endpoints().cookie;
+   CREDENTIALS.write().unwrap_or_else(|e| e.into_inner()).retain(|(from, _)| *from != server);
+}
+
+async fn fetch() -> Result<Credentials> {
+   let client = http::client()?;
+
+   // the cookie page answers with an error status, but it still hands out the cookie
+   let response = http::execute(Operation::Get, client.get(QuerySpec::cookie().url()?)).await?;
+   let cookie = response.headers.get_all(SET_COOKIE).iter()
+      .filter_map(|value| value.to_str().ok())
+      .filter_map(|value| value.split(';').next())
+      .map(str::trim)
+      .filter(|value| !value.is_empty())
+      .collect::<Vec<_>>()
+      .join("; ");
+   ensure!(!cookie.is_empty(), error::MissingData { reason: "no session cookie" });
+
+   // the crumb has to be requested with the cookie it belongs to
+   let url = QuerySpec::query("/v1/test/getcrumb").url()?;
+   let response = http::execute(Operation::Get, client.get(url.clone()).header(COOKIE, &cookie)).await?;
+   ensure!(response.status.is_success(), error::CallFailed { url: url.to_string(), status: response.status.as_u16(), body: http::snippet(&response.body) });
+   let crumb = response.body.trim().to_string();
+   ensure!(!crumb.is_empty() && !crumb.contains('<'), error::MissingData { reason: "no session crumb" });
+
+   Ok(Credentials { cookie, crumb })
+}
@@ -0,0 +1,57 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+use std::collections::HashMap;
+
+use crate::{ error, Result };
+use super::{ http, raw, QuerySpec };
+
+ez_serde!(Reported {
+   as_of_date: String,
+   period_type: Option<String>,
+   currency_code: Option<String>,
+   #[serde(default, deserialize_with = "raw::value")] reported_value: Option<f64>
+});
+
+ez_serde!(Series {
+   timestamp: Option<Vec<i64>>,
+   #[serde(flatten)] values: HashMap<String, serde_json::Value>
+});
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Results { result: Option<Vec<Series>>, error: Option<Error> });
+ez_serde!(Response { timeseries: Results });
+
+/// Reads a fundamentals timeseries response into the values reported for
+/// each type, ie. `annualTotalRevenue`.  Types Yahoo has nothing for are
+/// left out.
+pub fn parse_timeseries(data: &str) -> Result<HashMap<String, Vec<Reported>>> {
+   let results = serde_json::from_str::<Response>(data).context(error::BadData)?.timeseries;
+
+   if let Some(err) = results.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   let mut timeseries = HashMap::new();
+   for series in results.result.context(error::UnexpectedErrorYahoo)? {
+      for (kind, values) in series.values {
+         if kind == "meta" { continue; }
+
+         // periods Yahoo has no value for come through as nulls
+         let values: Vec<Option<Reported>> = serde_json::from_value(values).context(error::BadData)?;
+         timeseries.insert(kind, values.into_iter().flatten().collect());
+      }
+   }
+   Ok(timeseries)
+}
+
+/// Loads the requested fundamentals for a symbol between two times in
+/// seconds since the EPOCH.  ie. `["annualTotalRevenue"]`
+pub async fn load_timeseries(symbol: &str, types: &[&str], start: i64, end: i64) -> Result<HashMap<String, Vec<Reported>>> {
+   let query = QuerySpec::query("/ws/fundamentals-timeseries/v1/finance/timeseries").segment(symbol)
+      .symbol(symbol)
+      .types(types)
+      .start(start)
+      .end(end)
+      .authenticated();
+   parse_timeseries(&http::get(&query).await?)
+}
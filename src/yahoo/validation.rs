@@ -0,0 +1,28 @@
+use serde::Deserialize;
+use snafu::{ OptionExt, ResultExt };
+use std::collections::HashMap;
+
+use crate::{ error, Result };
+use super::{ http, QuerySpec };
+
+ez_serde!(Error { code: String, description: String });
+ez_serde!(Results { result: Option<Vec<HashMap<String, bool>>>, error: Option<Error> });
+ez_serde!(Response { symbols_validation: Results });
+
+pub async fn load_validation(symbols: &[&str]) -> Result<HashMap<String, bool>> {
+   let query = QuerySpec::query("/v6/finance/quote/validate").symbols(symbols);
+   parse_validation(&http::get(&query).await?)
+}
+
+/// Reads a symbol validation response
+pub fn parse_validation(data: &str) -> Result<HashMap<String, bool>> {
+   let results = serde_json::from_str::<Response>(data).context(error::BadData)?.symbols_validation;
+
+   if let Some(err) = results.error {
+      error::ApiFailed { code: err.code, description: err.description }.fail()?;
+   }
+
+   // Yahoo returns all of the symbols in a single map
+   let result = results.result.context(error::UnexpectedErrorYahoo)?;
+   Ok(result.into_iter().flatten().collect())
+}
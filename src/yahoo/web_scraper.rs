@@ -1,14 +1,12 @@
-use reqwest::Url;
 use serde::Deserialize;
-use snafu::{ ensure, OptionExt, ResultExt };
-use std::env;
+use snafu::OptionExt;
 use std::io::{ BufRead, Cursor };
 
 use crate::{ error, Result };
+use super::{ http, schema, schema::Schema, QuerySpec };
+use super::quote_summary::{ FinancialData, Price };
 
-const DATA_VAR: &'static str = "root.App.main";
-
-const BASE_URL: &'static str = "https://finance.yahoo.com";
+pub(super) const DATA_VAR: &str = "root.App.main";
 
 ez_serde!(QuoteType {
    #[serde(rename = "longName")] name: String,
@@ -40,7 +38,9 @@ ez_serde!(FundProfile {
 });
 
 ez_serde!(QuoteSummaryStore {
+   #[serde(rename = "financialData")] financial_data: Option<FinancialData>,
    #[serde(rename = "fundProfile")] fund_profile: Option<FundProfile>,
+   price: Option<Price>,
    #[serde(rename = "summaryProfile")] company_profile: Option<CompanyProfile>,
    #[serde(rename = "quoteType")] quote_type: QuoteType
 });
@@ -49,34 +49,46 @@ ez_serde!(Dispatcher { stores: Stores });
 ez_serde!(Context { dispatcher: Dispatcher });
 ez_serde!(Response { context: Context });
 
-pub async fn scrape<'a>(symbol: &'a str) -> Result<Stores> {
-   // construct the lookup URL - encoding it so we're safe
-   let base = format!("{}/quote/{}", env::var("TEST_URL").unwrap_or(BASE_URL.to_string()), symbol);
-
-   let mut url = Url::parse(base.as_str()).context(error::InternalURL { url: base })?;
-   url.query_pairs_mut().append_pair("p", symbol);
+const SCHEMA: Schema<Response> = Schema {
+   endpoint: "web_scraper",
+   versions: &[ ("long-name", schema::json), ("short-name", short_name) ]
+};
+
+/// Some listings have no `longName` so we fall back to the `shortName`
+fn short_name(data: &str) -> serde_json::Result<Response> {
+   let mut value = serde_json::from_str::<serde_json::Value>(data)?;
+   let quote_type = value
+      .pointer_mut("/context/dispatcher/stores/QuoteSummaryStore/quoteType")
+      .and_then(|quote_type| quote_type.as_object_mut());
+
+   if let Some(quote_type) = quote_type {
+      if quote_type.get("longName").is_none_or(|name| name.is_null()) {
+         let short_name = quote_type.get("shortName").cloned().unwrap_or_default();
+         quote_type.insert("longName".to_string(), short_name);
+      }
+   }
+   serde_json::from_value(value)
+}
 
-   // make the call - we do not really expect this to fail.
-   // ie - we won't 404 if the symbol doesn't exist
-   let response = reqwest::get(url.clone()).await.context(error::RequestFailed)?;
-   ensure!(
-      response.status().is_success(),
-      error::CallFailed{ url: response.url().to_string(), status: response.status().as_u16() }
-   );
+pub async fn scrape(symbol: &str) -> Result<Stores> {
+   let query = QuerySpec::web("/quote").segment(symbol).page_symbol(symbol);
+   parse_page(&http::get(&query).await?)
+}
 
-   let line = Cursor::new(response.text().await.context(error::UnexpectedErrorRead { url: url.clone().to_string() })?)
+/// Reads the data out of a quote page
+pub fn parse_page(page: &str) -> Result<Stores> {
+   let line = Cursor::new(page)
       .lines()
       .map(|line| line.unwrap())
-      .filter(|line| line.trim().starts_with(DATA_VAR))
-      .next()
+      .find(|line| line.trim().starts_with(DATA_VAR))
       .context(error::MissingData { reason: "no quote data" })?;
    
    let data = line
       .trim()
       .trim_start_matches(DATA_VAR)
-      .trim_start_matches(|c| c == ' ' || c == '=')
+      .trim_start_matches([' ', '='])
       .trim_end_matches(';');
 
-   let response = serde_json::from_str::<Response>(data).context(error::BadData)?;
+   let response = SCHEMA.parse(data)?;
    Ok(response.context.dispatcher.stores)
 }
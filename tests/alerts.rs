@@ -0,0 +1,72 @@
+use futures::{stream, StreamExt};
+use tokio_test::block_on;
+use yahoo_finance::{Quote, TradingSession};
+use yahoo_finance::alerts::{Alerts, Condition};
+
+fn quote(symbol: &str, price: f64, volume: u64) -> Quote {
+   Quote {
+      symbol: symbol.to_string(),
+      kind: None,
+      timestamp: 1_600_000_000_000,
+      session: TradingSession::Regular,
+      price,
+      volume,
+      change: Some(price - 100.0),
+      change_percent: Some(price - 100.0),
+      bid: None,
+      ask: None,
+      day_high: None,
+      day_low: None,
+      market_cap: None,
+      last_size: None,
+      volume_24h: None,
+      circulating_supply: None
+   }
+}
+
+#[test]
+fn alerts_price_crosses() {
+   //! Ensure that crossing a level alerts once per crossing & only for the symbol
+
+   // GIVEN - alerts on either side of 105
+   let mut alerts = Alerts::new().add("AAPL", Condition::CrossesAbove(105.0)).add("AAPL", Condition::CrossesBelow(105.0));
+
+   // WHEN - the price goes up through it, stays up, comes back down & another symbol goes through it
+   let prices = [100.0, 106.0, 107.0, 104.0];
+   let fired = prices.iter().map(|&price| alerts.push(&quote("AAPL", price, 0)).into_iter().map(|alert| alert.condition).collect::<Vec<_>>()).collect::<Vec<_>>();
+   let other = alerts.push(&quote("MSFT", 110.0, 0));
+
+   // THEN - each crossing alerted once
+   assert_eq!(vec![vec![], vec![Condition::CrossesAbove(105.0)], vec![], vec![Condition::CrossesBelow(105.0)]], fired);
+   assert!(other.is_empty());
+}
+
+#[test]
+fn alerts_percent_move() {
+   //! Ensure that a percentage move alerts again only after dropping back under it
+
+   // GIVEN - an alert on a 5% move
+   let mut alerts = Alerts::new().add("AAPL", Condition::PercentMove(5.0));
+
+   // WHEN - the price moves 6% up, 7% up, back to 2% & then 5% down
+   let fired = [106.0, 107.0, 102.0, 95.0].iter().map(|&price| alerts.push(&quote("AAPL", price, 0)).len()).collect::<Vec<_>>();
+
+   // THEN - both moves alerted
+   assert_eq!(vec![1, 0, 0, 1], fired);
+}
+
+#[test]
+fn alerts_volume_spike() {
+   //! Ensure that a spike in volume between quotes is caught from a stream
+
+   // GIVEN - a day's volume going up 100 at a time, then 500 at once & starting over on a new day
+   let volumes = [1000, 1100, 1200, 1300, 1800, 50, 2000];
+   let quotes = stream::iter(volumes.iter().map(|&volume| Ok(quote("AAPL", 100.0, volume))).collect::<Vec<_>>());
+
+   // WHEN - we watch for 3 times the average of the last 3 quotes
+   let alerts = Alerts::new().add("AAPL", Condition::VolumeSpike { multiple: 3.0, window: 3 });
+   let fired = block_on(alerts.watch(quotes).collect::<Vec<_>>());
+
+   // THEN - only the spike alerted, with the new day's volume not counting
+   assert_eq!(vec![1800], fired.iter().map(|alert| alert.quote.volume).collect::<Vec<_>>());
+}
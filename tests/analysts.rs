@@ -0,0 +1,125 @@
+use chrono::NaiveDate;
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::analysts::{self, Action};
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str, modules: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/analysts_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules={modules}&crumb=crumb", symbol=symbol, modules=modules).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_recommendation_trend() {
+   //! Ensure that we can load the monthly analyst ratings
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_trend", symbol, "recommendationTrend").unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(analysts::recommendation_trend(symbol)).unwrap();
+
+   // THEN - we get the ratings for each month, oldest first
+   assert_eq!(vec![3, 2, 1, 0], result.iter().map(|month| month.months_ago).collect::<Vec<_>>());
+   let current = &result[3];
+   assert_eq!((11, 21, 6, 0, 0), (current.strong_buy, current.buy, current.hold, current.sell, current.strong_sell));
+   assert_eq!(38, current.total());
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn load_recommendation_trend_not_found() {
+   //! Ensure that we gracefully fail for unknown symbols
+
+   // GIVEN - an error response for an unknown symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _m = base_mock("not_found", symbol, "recommendationTrend").unwrap().create();
+
+   // WHEN - we load the data
+   block_on(analysts::recommendation_trend(symbol)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn load_rating_changes() {
+   //! Ensure that we can load the upgrade / downgrade history
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_grades", symbol, "upgradeDowngradeHistory").unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(analysts::rating_changes(symbol)).unwrap();
+
+   // THEN - we get every change, oldest first
+   assert_eq!(6, result.len());
+   assert_eq!((NaiveDate::from_ymd_opt(2023, 10, 1).unwrap(), "HSBC"), (result[0].date, result[0].firm.as_str()));
+   assert_eq!(vec![Action::Upgrade, Action::Reiterate, Action::Initiate, Action::Downgrade, Action::Downgrade, Action::Maintain],
+      result.iter().map(|change| change.action.clone()).collect::<Vec<_>>());
+
+   let barclays = &result[4];
+   assert_eq!((Some("Equal-Weight".to_string()), Some("Underweight".to_string())), (barclays.from_grade.clone(), barclays.to_grade.clone()));
+
+   // AND - new coverage has no grade before it
+   assert_eq!(None, result[2].from_grade);
+}
+
+#[test]
+fn load_price_targets() {
+   //! Ensure that we can load the analysts' price targets
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_targets", symbol, "financialData").unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(analysts::price_targets(symbol)).unwrap();
+
+   // THEN - we get the consensus
+   assert_eq!((Some(250.0), Some(159.0), Some(201.34), Some(200.0)), (result.high, result.low, result.mean, result.median));
+   assert_eq!(Some(38), result.analysts);
+   assert!((result.upside().unwrap() - 0.0278).abs() < 0.0001);
+}
+
+#[test]
+fn load_price_targets_many() {
+   //! Ensure that each symbol gets its own price targets
+
+   // GIVEN - a stock with coverage, one without & an unknown symbol
+   let _s = session_mocks();
+   let _a = base_mock("aapl_targets", "AAPL", "financialData").unwrap().create();
+   let _t = base_mock("no_coverage", "TINY", "financialData").unwrap().create();
+   let _f = base_mock("not_found", "FUBAR", "financialData").unwrap().create();
+
+   // WHEN - we load them together
+   let result = block_on(analysts::price_targets_many(&["AAPL", "TINY", "FUBAR"]));
+
+   // THEN - the failure doesn't stop the others from loading
+   assert_eq!(3, result.len());
+   assert_eq!(Some(201.34), result["AAPL"].as_ref().unwrap().mean);
+   assert_eq!(None, result["TINY"].as_ref().unwrap().upside());
+   assert!(result["FUBAR"].is_err());
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "arrow")]
+
+use yahoo_finance::{history, Bar};
+use yahoo_finance::history::Split;
+
+#[test]
+fn bars_to_parquet() {
+   //! Ensure that bars convert to Arrow & can be written as Parquet
+
+   // GIVEN - some bars, one without volume
+   let bars = vec![
+      Bar { timestamp: 1588253400000, open: 289.96, high: 294.53, low: 288.35, close: 293.8, volume: Some(45766000) },
+      Bar { timestamp: 1588339800000, open: 286.25, high: 299.0, low: 285.85, close: 289.07, volume: None }
+   ];
+
+   // WHEN - we convert them & write them out
+   let batch = history::bars_to_record_batch(&bars).unwrap();
+   let mut output = Vec::new();
+   history::write_parquet(&batch, &mut output).unwrap();
+
+   // THEN - we get every column & a parquet file
+   assert_eq!(2, batch.num_rows());
+   assert_eq!(6, batch.num_columns());
+   assert_eq!(1, batch.column(5).null_count());
+   assert_eq!(b"PAR1", &output[..4]);
+}
+
+#[test]
+fn splits_to_record_batch() {
+   //! Ensure that splits convert to Arrow
+
+   // GIVEN - a split
+   let splits = vec![ Split { timestamp: 1598880600000, numerator: 4.0, denominator: 1.0 } ];
+
+   // WHEN - we convert it
+   let batch = history::splits_to_record_batch(&splits).unwrap();
+
+   // THEN - we get the ratio column
+   assert_eq!(1, batch.num_rows());
+   assert_eq!("ratio", batch.schema().field(3).name());
+}
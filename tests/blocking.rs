@@ -0,0 +1,38 @@
+#![cfg(feature = "blocking")]
+
+use mockito::mock;
+use yahoo_finance::history;
+use yahoo_finance::config::{self, Endpoints};
+
+#[test]
+fn blocking_retrieve() {
+   //! Ensure that history can be loaded without a runtime of our own
+
+   // GIVEN - a valid symbol
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let _m = mock("GET", "/v8/finance/chart/AAPL?range=6mo&interval=1d")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we load the history without a runtime
+   let result = history::blocking::retrieve("AAPL").unwrap();
+
+   // THEN - we get the bars
+   assert_eq!(5, result.len());
+}
+
+#[test]
+fn blocking_in_async() {
+   //! Ensure that a blocking call from async code fails rather than panicking
+
+   // GIVEN - we are running on a runtime
+   let mut runtime = tokio::runtime::Runtime::new().unwrap();
+
+   // WHEN - we make a blocking call on it
+   let result = runtime.block_on(async { history::blocking::retrieve("AAPL") });
+
+   // THEN - we are told to use the async version
+   assert!(result.unwrap_err().to_string().contains("async version"));
+}
@@ -0,0 +1,46 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::commodities::{self, Commodity};
+use yahoo_finance::config::{self, Endpoints};
+
+fn base_mock(test_name: &str, path: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/commodities_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", path)
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn quote_front_month() {
+   //! Ensure that we can load the front month price for a commodity
+
+   // GIVEN - a valid response for gold futures
+   let _m = base_mock("gold", "/v8/finance/chart/GC=F?range=5d&interval=1d").unwrap().create();
+
+   // WHEN - we load the quote
+   let result = block_on(commodities::quote(Commodity::Gold)).unwrap();
+
+   // THEN - we get the front month price
+   assert_eq!("GC=F", result.symbol);
+   assert!((result.price - 1934.5).abs() < 1e-6);
+}
+
+#[test]
+fn contract_symbols() {
+   //! Ensure that specific contracts use Yahoo's naming
+
+   assert_eq!(Some("CLZ20.NYM".to_string()), Commodity::WTI.contract(12, 2020));
+   assert_eq!(Some("GCF21.CMX".to_string()), Commodity::Gold.contract(1, 2021));
+   assert_eq!(None, Commodity::Corn.contract(13, 2021));
+}
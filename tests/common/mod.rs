@@ -0,0 +1,9 @@
+use mockito::{mock, Mock};
+
+/// Hands out the session cookie & crumb that calls like the quote summary need
+pub fn session_mocks() -> Vec<Mock> {
+   vec![
+      mock("GET", "/").with_header("set-cookie", "A3=session; Domain=.yahoo.com; Path=/").with_status(404).create(),
+      mock("GET", "/v1/test/getcrumb").match_header("cookie", "A3=session").with_body("crumb").with_status(200).create()
+   ]
+}
@@ -0,0 +1,187 @@
+use mockito::{mock, Matcher};
+use std::time::Duration;
+use tokio_test::block_on;
+use yahoo_finance::{config, history};
+use yahoo_finance::config::{Endpoints, Locale};
+
+// Settings are shared by the whole process, so each test changes them only
+// after creating its mocks - which holds the mock server's lock & keeps the
+// tests from seeing each other's settings.
+
+#[test]
+fn cached_responses() {
+   //! Ensure that repeated calls are served from the cache until it's cleared
+
+   // GIVEN - caching is turned on
+   let m = mock("GET", "/v8/finance/chart/AAPL?range=6mo&interval=1d")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .expect(2)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_cache_ttl(Duration::from_secs(60));
+
+   // WHEN - we make the same call twice, clear the cache & call again
+   let first = block_on(history::retrieve("AAPL")).unwrap();
+   let second = block_on(history::retrieve("AAPL")).unwrap();
+   config::clear_cache();
+   block_on(history::retrieve("AAPL")).unwrap();
+   config::disable_cache();
+
+   // THEN - Yahoo was only called once before the cache was cleared
+   m.assert();
+   assert_eq!(first.len(), second.len());
+}
+
+#[test]
+fn custom_headers() {
+   //! Ensure that the configured User-Agent & headers are sent to Yahoo
+
+   // GIVEN - a custom User-Agent & an extra header
+   let m = mock("GET", "/v8/finance/chart/MSFT?range=6mo&interval=1d")
+      .match_header("user-agent", "yahoo-finance-tests/1.0")
+      .match_header("x-extra", "second")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_user_agent("yahoo-finance-tests/1.0").unwrap();
+   config::set_header("X-Extra", "first").unwrap();
+   config::set_header("x-extra", "second").unwrap();
+
+   // WHEN - we make a call
+   let result = block_on(history::retrieve("MSFT"));
+   config::clear_headers();
+
+   // THEN - the call went out with the headers
+   m.assert();
+   assert!(result.is_ok());
+}
+
+#[test]
+fn changed_headers() {
+   //! Ensure that a header changed between calls is sent with the next call
+
+   // GIVEN - a call made with one value for a header
+   let first = mock("GET", "/v8/finance/chart/MSFT?range=6mo&interval=1d")
+      .match_header("x-extra", "first")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+   let second = mock("GET", "/v8/finance/chart/MSFT?range=6mo&interval=1d")
+      .match_header("x-extra", "second")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_header("X-Extra", "first").unwrap();
+   block_on(history::retrieve("MSFT")).unwrap();
+
+   // WHEN - we change the header & call again
+   config::set_header("X-Extra", "second").unwrap();
+   let result = block_on(history::retrieve("MSFT"));
+   config::clear_headers();
+
+   // THEN - each call went out with the header as it was at the time
+   first.assert();
+   second.assert();
+   assert!(result.is_ok());
+}
+
+#[test]
+fn invalid_header() {
+   //! Ensure that headers which can't be sent are turned down up front
+
+   // GIVEN / WHEN - a header with a line break in it
+   let result = config::set_header("X-Extra", "bad\nvalue");
+
+   // THEN - it's rejected
+   assert!(result.unwrap_err().to_string().contains("X-Extra"));
+}
+
+#[test]
+fn proxied_calls() {
+   //! Ensure that calls go through the configured proxy
+
+   // GIVEN - a proxy in front of a host we can't reach directly
+   let m = mock("GET", Matcher::Regex("/v8/finance/chart/PRXY".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   config::set_endpoints(Endpoints::at("http://yahoo.invalid"));
+   config::set_proxy(&mockito::server_url()).unwrap();
+
+   // WHEN - we make a call
+   let result = block_on(history::retrieve("PRXY"));
+   config::clear_proxy();
+
+   // THEN - the proxy answered it
+   m.assert();
+   assert!(result.is_ok());
+}
+
+#[test]
+fn invalid_proxy() {
+   //! Ensure that proxies we can't use are turned down up front
+
+   // GIVEN / WHEN - a proxy that isn't a URL
+   let result = config::set_proxy("not a proxy");
+
+   // THEN - it's rejected
+   assert!(result.unwrap_err().to_string().contains("not a proxy"));
+}
+
+#[test]
+fn localized_calls() {
+   //! Ensure that calls ask for the configured language & region
+
+   // GIVEN - a German locale
+   let m = mock("GET", "/v8/finance/chart/SAP.DE?range=6mo&interval=1d&lang=de-DE&region=DE")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_locale(Locale::new("de-DE", "DE"));
+
+   // WHEN - we make a call
+   let result = block_on(history::retrieve("SAP.DE"));
+   config::clear_locale();
+
+   // THEN - the call was localized
+   m.assert();
+   assert!(result.is_ok());
+}
+
+#[test]
+fn scoped_endpoints() {
+   //! Ensure that calls within `with_endpoints` go to those endpoints without changing anyone else's
+
+   // GIVEN - a process pointed at a host we can't reach & a mock server for just our calls
+   let m = mock("GET", "/v8/finance/chart/SCPD?range=6mo&interval=1d")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   config::set_endpoints(Endpoints::at("http://yahoo.invalid"));
+
+   // WHEN - we make the same call within & outside of the scope
+   let scoped = block_on(config::with_endpoints(Endpoints::at(&mockito::server_url()), history::retrieve("SCPD")));
+   let unscoped = block_on(history::retrieve("SCPD"));
+
+   // THEN - only the scoped call reached the mock server
+   m.assert();
+   assert!(scoped.is_ok());
+   assert!(unscoped.is_err());
+}
@@ -0,0 +1,72 @@
+use mockito::{mock, Matcher};
+use tokio_test::block_on;
+use yahoo_finance::Profile;
+use yahoo_finance::config::{self, Endpoints};
+
+#[test]
+fn agrees_to_consent() {
+   //! Ensure that we agree to Yahoo's cookie consent page & carry on with the call
+
+   // GIVEN - a quote page that needs consent first, like from the EU
+   let page = std::fs::read_to_string("tests/profile_data/aapl.html").unwrap();
+   let quote = mock("GET", "/quote/AAPL?p=AAPL")
+      .match_header("cookie", "A1=agreed; A3=session")
+      .with_header("content-type", "text/html")
+      .with_body(&page)
+      .expect(1)
+      .create();
+   let _redirect = mock("GET", "/quote/AAPL?p=AAPL")
+      .match_header("cookie", Matcher::Missing)
+      .with_header("location", "/consent?sessionId=abc")
+      .with_status(302)
+      .create();
+   let _form = mock("GET", "/consent?sessionId=abc")
+      .with_header("content-type", "text/html")
+      .with_body(r#"<html><body><form method="post" class="consent-form" action="">
+         <input type="hidden" name="csrfToken" value="token">
+         <input type="hidden" name="sessionId" value="abc">
+         <button type="submit" name="agree" value="agree">Accept all</button>
+      </form></body></html>"#)
+      .create();
+   let agreed = mock("POST", "/consent?sessionId=abc")
+      .match_body("csrfToken=token&sessionId=abc&agree=agree")
+      .with_header("set-cookie", "A1=agreed; Domain=.yahoo.com")
+      .with_header("location", "/copyConsent?sessionId=abc")
+      .with_status(302)
+      .expect(1)
+      .create();
+   let _copy = mock("GET", "/copyConsent?sessionId=abc")
+      .match_header("cookie", "A1=agreed")
+      .with_header("set-cookie", "A3=session; Domain=.yahoo.com")
+      .with_header("location", "/quote/AAPL?p=AAPL")
+      .with_status(302)
+      .create();
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // WHEN - we load the profile
+   let result = block_on(Profile::load("AAPL")).unwrap();
+
+   // THEN - we agreed once & got the profile
+   agreed.assert();
+   quote.assert();
+   assert!(matches!(result, Profile::Company(_)));
+}
+
+#[test]
+fn consent_in_symbol() {
+   //! Ensure that a symbol with 'consent' in it isn't mistaken for the consent page
+
+   // GIVEN - the quote page for a symbol named like the consent page
+   let page = std::fs::read_to_string("tests/profile_data/aapl.html").unwrap();
+   let _quote = mock("GET", "/quote/CONSENT?p=CONSENT")
+      .with_header("content-type", "text/html")
+      .with_body(&page)
+      .create();
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // WHEN - we load the profile
+   let result = block_on(Profile::load("CONSENT"));
+
+   // THEN - we got the profile
+   assert!(matches!(result, Ok(Profile::Company(_))), "{:?}", result.err());
+}
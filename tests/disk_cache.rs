@@ -0,0 +1,81 @@
+#![cfg(feature = "disk-cache")]
+
+use chrono::{TimeZone, Utc};
+use mockito::{mock, Matcher};
+use std::env;
+use tokio_test::block_on;
+use yahoo_finance::history::DiskCache;
+use yahoo_finance::config::{self, Endpoints};
+
+#[test]
+fn second_load_uses_cache() {
+   //! Ensure that Yahoo is only called once for the same dates
+
+   // GIVEN - an empty cache & Yahoo data
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let directory = env::temp_dir().join(format!("yahoo-finance-cache-{}", std::process::id()));
+   let _ = std::fs::remove_dir_all(&directory);
+
+   let m = mock("GET", Matcher::Regex(r"^/v8/finance/chart/AAPL\?period1=\d+&period2=\d+&interval=1d$".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .expect(1)
+      .create();
+
+   // WHEN - we load the same dates twice
+   let cache = DiskCache::new(&directory);
+   let start = Utc.timestamp_opt(1587945600, 0).unwrap();
+   let end = Utc.timestamp_opt(1588377600, 0).unwrap();
+   let first = block_on(cache.retrieve_range("AAPL", start, Some(end))).unwrap();
+   let second = block_on(cache.retrieve_range("AAPL", start, Some(end))).unwrap();
+
+   // THEN - Yahoo was only called once & both loads match
+   m.assert();
+   assert_eq!(5, first.len());
+   assert_eq!(5, second.len());
+   assert!((first[4].close - second[4].close).abs() < 0.001);
+
+   // ... with no temporary files left behind
+   let files: Vec<_> = std::fs::read_dir(&directory).unwrap().map(|entry| entry.unwrap().file_name()).collect();
+   assert_eq!(vec!["AAPL.csv"], files);
+
+   let _ = std::fs::remove_dir_all(&directory);
+}
+
+#[test]
+fn empty_load_covers_only_its_dates() {
+   //! Ensure that a load with no bars only marks the dates it asked for as cached
+
+   // GIVEN - a cache holding an earlier load of dates Yahoo has no bars for
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let directory = env::temp_dir().join(format!("yahoo-finance-cache-empty-{}", std::process::id()));
+   let _ = std::fs::remove_dir_all(&directory);
+
+   let (start, first_end, second_end, third_end) = (1587945600, 1588032000, 1588118400, 1588204800);
+   let chart = |from: i64, to: i64| mock("GET", format!("/v8/finance/chart/AAPL?period1={}&period2={}&interval=1d", from, to).as_str())
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/no_quote_data.json").unwrap())
+      .with_status(200)
+      .expect(1)
+      .create();
+   let first = chart(start, first_end);
+   let second = chart(first_end, second_end);
+   let third = chart(second_end, third_end);
+
+   let cache = DiskCache::new(&directory);
+   let at = |timestamp: i64| Utc.timestamp_opt(timestamp, 0).unwrap();
+   block_on(cache.retrieve_range("AAPL", at(start), Some(at(first_end)))).unwrap();
+
+   // WHEN - we load up to later dates that are still in the past, one after the other
+   block_on(cache.retrieve_range("AAPL", at(start), Some(at(second_end)))).unwrap();
+   let result = block_on(cache.retrieve_range("AAPL", at(start), Some(at(third_end)))).unwrap();
+
+   // THEN - each load asked for just the dates after the one before
+   first.assert();
+   second.assert();
+   third.assert();
+   assert!(result.is_empty());
+
+   let _ = std::fs::remove_dir_all(&directory);
+}
@@ -0,0 +1,170 @@
+use chrono::NaiveDate;
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::{earnings, Earnings};
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str, modules: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/earnings_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules={modules}&crumb=crumb", symbol=symbol, modules=modules).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+const EARNINGS: &str = "earnings%2CearningsTrend";
+const CALENDAR: &str = "calendarEvents%2Cearnings";
+const HISTORY: &str = "earningsHistory";
+
+#[test]
+fn load_earnings() {
+   //! Ensure that we can load the earnings history & estimates for a company
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl", symbol, EARNINGS).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Earnings::load(symbol)).unwrap();
+
+   // THEN - we get the actual vs estimated EPS, oldest first
+   assert_eq!(4, result.history.len());
+   let latest = &result.history[3];
+   assert_eq!(("4Q2023", Some(2.18), Some(2.1)), (latest.quarter.as_str(), latest.actual, latest.estimate));
+   assert!((latest.surprise().unwrap() - 0.038).abs() < 0.001);
+   assert_eq!(NaiveDate::from_ymd_opt(2024, 5, 2), result.next_earnings_date);
+
+   // AND - the revenue & earnings by year & quarter
+   assert_eq!(("2023", Some(383_285_000_000.0), Some(96_995_000_000.0)), (result.yearly[3].period.as_str(), result.yearly[3].revenue, result.yearly[3].earnings));
+   assert_eq!(("1Q2023", Some(94_836_000_000.0)), (result.quarterly[0].period.as_str(), result.quarterly[0].revenue));
+   assert_eq!(Some("USD".to_string()), result.currency);
+
+   // AND - the forward estimates, with nothing but growth for the long term
+   assert_eq!(4, result.estimates.len());
+   let next = &result.estimates[1];
+   assert_eq!(("+1q", NaiveDate::from_ymd_opt(2024, 6, 30)), (next.period.as_str(), next.end_date));
+   assert_eq!((Some(1.31), Some(1.24), Some(1.39), Some(27)), (next.eps_average, next.eps_low, next.eps_high, next.eps_analysts));
+   assert_eq!(Some(84_000_000_000.0), next.revenue_average);
+   let long_term = &result.estimates[3];
+   assert_eq!((None, Some(0.1165), None), (long_term.end_date, long_term.growth, long_term.eps_average));
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn load_earnings_not_found() {
+   //! Ensure that we gracefully fail for unknown symbols
+
+   // GIVEN - an error response for an unknown symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _m = base_mock("not_found", symbol, EARNINGS).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(Earnings::load(symbol)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn next_earnings_date() {
+   //! Ensure that we can load the window the next earnings are expected in
+
+   // GIVEN - a company that hasn't set its earnings date yet
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_calendar", symbol, CALENDAR).unwrap().create();
+
+   // WHEN - we load the next date
+   let result = block_on(earnings::next_date(symbol)).unwrap().unwrap();
+
+   // THEN - we get Yahoo's estimated window
+   assert_eq!(NaiveDate::from_ymd_opt(2024, 4, 25).unwrap(), result.earliest);
+   assert_eq!(NaiveDate::from_ymd_opt(2024, 4, 29).unwrap(), result.latest);
+   assert!(result.estimated);
+}
+
+#[test]
+fn next_earnings_date_from_earnings() {
+   //! Ensure that the earnings module is used without the calendar events
+
+   // GIVEN - a response with only the earnings module
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl", symbol, CALENDAR).unwrap().create();
+
+   // WHEN - we load the next date
+   let result = block_on(earnings::next_date(symbol)).unwrap().unwrap();
+
+   // THEN - we get the date from the earnings chart
+   assert_eq!((NaiveDate::from_ymd_opt(2024, 5, 2).unwrap(), NaiveDate::from_ymd_opt(2024, 5, 2).unwrap()), (result.earliest, result.latest));
+}
+
+#[test]
+fn next_earnings_date_none() {
+   //! Ensure that there is no date for symbols without earnings
+
+   // GIVEN - a fund, which has no earnings dates
+   let symbol = "SPY";
+   let _s = session_mocks();
+   let _m = base_mock("spy_calendar", symbol, CALENDAR).unwrap().create();
+
+   // WHEN - we load the next date
+   let result = block_on(earnings::next_date(symbol)).unwrap();
+
+   // THEN - there is none
+   assert_eq!(None, result);
+}
+
+#[test]
+fn load_surprises() {
+   //! Ensure that we can load the EPS surprises for the last few quarters
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_history", symbol, HISTORY).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(earnings::surprises(symbol)).unwrap();
+
+   // THEN - we get a surprise per quarter, oldest first
+   assert_eq!(4, result.len());
+   let latest = &result[3];
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 12, 31).unwrap(), latest.quarter);
+   assert_eq!(Some("-1q".to_string()), latest.period);
+   assert_eq!((Some(2.1), Some(2.18), Some(0.08), Some(0.038)), (latest.estimate, latest.actual, latest.difference, latest.surprise));
+
+   // AND - quarters without an estimate have no surprise
+   assert_eq!((Some(1.46), None, None), (result[2].actual, result[2].estimate, result[2].surprise));
+}
+
+#[test]
+#[should_panic(expected = "MissingData")]
+fn load_surprises_without_history() {
+   //! Ensure that symbols without an earnings history fail
+
+   // GIVEN - a response without the earnings history
+   let symbol = "SPY";
+   let _s = session_mocks();
+   let _m = base_mock("spy_calendar", symbol, HISTORY).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(earnings::surprises(symbol)).unwrap();
+
+   // THEN - we get an error
+}
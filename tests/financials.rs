@@ -0,0 +1,83 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::Financials;
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/financials_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules=financialData%2Cprice&crumb=crumb", symbol=symbol).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_financials() {
+   //! Ensure that we can load the financial data for a company analysts cover
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Financials::load(symbol)).unwrap();
+
+   // THEN - we get the analyst opinions, the balance sheet figures & the margins
+   assert_eq!((Some(250.0), Some(159.0), Some(201.34)), (result.targets.high, result.targets.low, result.targets.mean));
+   assert_eq!((Some(2.2), Some("buy".to_string()), Some(38)), (result.recommendation_mean, result.recommendation_key, result.targets.analysts));
+   assert_eq!((Some(61_555_000_320.0), Some(111_088_001_024.0)), (result.total_cash, result.total_debt));
+   assert_eq!(Some(383_285_002_240.0), result.total_revenue);
+   assert_eq!((Some(0.44131), Some(0.30134), Some(0.25305998)), (result.gross_margins, result.operating_margins, result.profit_margins));
+   assert_eq!(Some(82_179_997_696.0), result.free_cash_flow);
+   assert_eq!((Some("USD".to_string()), Some("USD".to_string())), (result.currencies.trading, result.currencies.financial));
+}
+
+#[test]
+fn load_financials_without_coverage() {
+   //! Ensure that figures Yahoo leaves empty come back as missing
+
+   // GIVEN - a response for a stock no analysts cover
+   let symbol = "TINY";
+   let _s = session_mocks();
+   let _m = base_mock("no_coverage", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Financials::load(symbol)).unwrap();
+
+   // THEN - there are no targets, but the rest is still there
+   assert_eq!((None, None, None), (result.targets.high, result.targets.low, result.targets.mean));
+   assert_eq!((None, None), (result.recommendation_mean, result.targets.analysts));
+   assert_eq!((Some(1_250_000.0), None), (result.total_cash, result.total_debt));
+   assert_eq!((Some(-0.35), None), (result.profit_margins, result.free_cash_flow));
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn load_financials_not_found() {
+   //! Ensure that we gracefully fail for unknown symbols
+
+   // GIVEN - an error response for an unknown symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _m = base_mock("not_found", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(Financials::load(symbol)).unwrap();
+
+   // THEN - we get an error
+}
@@ -0,0 +1,200 @@
+use chrono::{NaiveDate, TimeZone, Utc};
+use mockito::{mock, Matcher, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::fundamentals::{self, Period};
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn price_mock(test_name: &str, symbol: &str) -> Mock {
+   // Serve up the quote summary with the currency the symbol trades in
+   mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules=price&crumb=crumb", symbol=symbol).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string(format!("tests/fundamentals_data/{}.json", test_name)).unwrap())
+      .with_status(200)
+      .create()
+}
+
+fn base_mock(test_name: &str, symbol: &str, types: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/fundamentals_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   let path = format!(r"^/ws/fundamentals-timeseries/v1/finance/timeseries/{symbol}\?symbol={symbol}&type={types}&period1=\d+&period2=\d+&crumb=crumb$", symbol=symbol, types=types);
+   Ok(mock("GET", Matcher::Regex(path))
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+const INCOME_TYPES: &str = "annualTotalRevenue%2CannualCostOfRevenue%2CannualGrossProfit%2CannualOperatingExpense%2CannualOperatingIncome%2CannualPretaxIncome%2CannualTaxProvision%2CannualNetIncome%2CannualEBITDA%2CannualBasicEPS%2CannualDilutedEPS";
+
+#[test]
+fn load_income_statement() {
+   //! Ensure that we can load a company's income statements
+
+   // GIVEN - a valid response for a company
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _p = price_mock("aapl_price", symbol);
+   let _m = base_mock("aapl_income", symbol, INCOME_TYPES).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(fundamentals::income_statement(symbol, Period::Annual)).unwrap();
+
+   // THEN - we get a statement per fiscal year, oldest first
+   assert_eq!(4, result.len());
+   let latest = &result[3];
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 9, 30).unwrap(), latest.date);
+   assert_eq!((Some("USD".to_string()), Some("USD".to_string())), (latest.currencies.trading.clone(), latest.currencies.financial.clone()));
+   assert_eq!((Some(383_285_000_000.0), Some(214_137_000_000.0)), (latest.total_revenue, latest.cost_of_revenue));
+   assert_eq!((Some(114_301_000_000.0), Some(96_995_000_000.0)), (latest.operating_income, latest.net_income));
+   assert_eq!((Some(6.16), Some(6.13)), (latest.basic_eps, latest.diluted_eps));
+
+   // AND - values Yahoo doesn't have are left out
+   assert_eq!(NaiveDate::from_ymd_opt(2020, 9, 30).unwrap(), result[0].date);
+   assert_eq!((None, Some(57_411_000_000.0)), (result[0].ebitda, result[0].net_income));
+}
+
+#[test]
+#[should_panic(expected = "SymbolNotFound")]
+fn load_income_statement_unknown() {
+   //! Ensure that we gracefully fail for symbols Yahoo doesn't know
+
+   // GIVEN - a response with nothing but the types we asked for & no quote for the symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _p = price_mock("not_found", symbol);
+   let _m = base_mock("unknown_income", symbol, INCOME_TYPES).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(fundamentals::income_statement(symbol, Period::Annual)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+#[should_panic(expected = "SymbolNotFound")]
+fn load_income_statement_none() {
+   //! Ensure that we gracefully fail for symbols Yahoo has no statements for
+
+   // GIVEN - a quote for the symbol but nothing but the types we asked for
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _p = price_mock("aapl_price", symbol);
+   let _m = base_mock("unknown_income", symbol, INCOME_TYPES).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(fundamentals::income_statement(symbol, Period::Annual)).unwrap();
+
+   // THEN - we get an error
+}
+
+const BALANCE_TYPES: &str = "annualTotalAssets%2CannualCurrentAssets%2CannualTotalLiabilitiesNetMinorityInterest%2CannualCurrentLiabilities%2CannualStockholdersEquity%2CannualCashAndCashEquivalents%2CannualCashCashEquivalentsAndShortTermInvestments%2CannualTotalDebt%2CannualLongTermDebt%2CannualNetDebt%2CannualWorkingCapital%2CannualOrdinarySharesNumber";
+
+#[test]
+fn load_balance_sheet() {
+   //! Ensure that we can load a company's balance sheets
+
+   // GIVEN - a valid response for a company
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _p = price_mock("aapl_price", symbol);
+   let _m = base_mock("aapl_balance", symbol, BALANCE_TYPES).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(fundamentals::balance_sheet(symbol, Period::Annual)).unwrap();
+
+   // THEN - we get a balance sheet per fiscal year, oldest first
+   assert_eq!(4, result.len());
+   let latest = &result[3];
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 9, 30).unwrap(), latest.date);
+   assert_eq!((Some(352_583_000_000.0), Some(290_437_000_000.0)), (latest.total_assets, latest.total_liabilities));
+   assert_eq!(Some(62_146_000_000.0), latest.stockholders_equity);
+   assert_eq!((Some(29_965_000_000.0), Some(123_930_000_000.0)), (latest.cash, latest.total_debt));
+   assert_eq!(Some(-1_742_000_000.0), latest.working_capital);
+   assert_eq!(Some(15_550_061_000), latest.shares_outstanding);
+
+   // AND - values Yahoo doesn't have are left out
+   assert_eq!((None, Some(98_667_000_000.0)), (result[0].total_debt, result[0].long_term_debt));
+}
+
+#[test]
+fn load_quarterly_income_statement() {
+   //! Ensure that we can load a company's income statements by quarter
+
+   // GIVEN - a valid response for a company's quarters
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _p = price_mock("aapl_price", symbol);
+   let _m = base_mock("aapl_quarterly_income", symbol, &INCOME_TYPES.replace("annual", "quarterly")).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(fundamentals::income_statement(symbol, Period::Quarterly)).unwrap();
+
+   // THEN - we get a statement per fiscal quarter, oldest first
+   assert_eq!(5, result.len());
+   let latest = &result[4];
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 12, 30).unwrap(), latest.date);
+   assert_eq!((Some(119_575_000_000.0), Some(33_916_000_000.0)), (latest.total_revenue, latest.net_income));
+   assert_eq!(Some(2.18), latest.diluted_eps);
+}
+
+#[test]
+fn load_timeseries() {
+   //! Ensure that we can load any fundamentals over a date range
+
+   // GIVEN - a valid response for the fundamentals we ask for
+   let symbol = "AAPL";
+   let _m = mock("GET", Matcher::Regex(r"^/ws/fundamentals-timeseries/v1/finance/timeseries/AAPL\?symbol=AAPL&type=annualTotalRevenue%2CtrailingPegRatio%2CannualFooBar&period1=1609459200&period2=1706745600&crumb=crumb$".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read("tests/fundamentals_data/aapl_timeseries.json").unwrap())
+      .with_status(200)
+      .create();
+   let _s = session_mocks();
+   let _p = price_mock("aapl_price", symbol);
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // WHEN - we load the data
+   let start = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+   let end = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+   let result = block_on(fundamentals::timeseries(symbol, &["annualTotalRevenue", "trailingPegRatio", "annualFooBar"], start, Some(end))).unwrap();
+
+   // THEN - we get a dated series for each of them, without the gaps
+   let revenue = &result["annualTotalRevenue"];
+   assert_eq!(2, revenue.len());
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 9, 30).unwrap(), revenue[1].date);
+   assert_eq!((383_285_000_000.0, Some("USD".to_string())), (revenue[1].value, revenue[1].currencies.financial.clone()));
+   assert_eq!(Some("USD".to_string()), revenue[1].currencies.trading);
+
+   let peg = &result["trailingPegRatio"];
+   assert_eq!(vec![2.0412, 2.0837, 2.1164], peg.iter().map(|point| point.value).collect::<Vec<_>>());
+   assert_eq!(Some("TTM".to_string()), peg[0].period_type);
+
+   // AND - the fundamentals Yahoo has nothing for are left out
+   assert!(!result.contains_key("annualFooBar"));
+}
+
+#[test]
+#[should_panic(expected = "InvalidStartDate")]
+fn load_timeseries_backwards() {
+   //! Ensure that a start after the end is refused
+
+   // GIVEN - a start date after the end date
+   let start = Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap();
+   let end = Utc.with_ymd_and_hms(2021, 1, 1, 0, 0, 0).unwrap();
+
+   // WHEN - we load the data
+   block_on(fundamentals::timeseries("AAPL", &["annualTotalRevenue"], start, Some(end))).unwrap();
+
+   // THEN - we get an error
+}
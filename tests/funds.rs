@@ -0,0 +1,66 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::funds::Allocation;
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/funds_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules=topHoldings&crumb=crumb", symbol=symbol).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_allocation() {
+   //! Ensure that we can load the asset allocation for a fund
+
+   // GIVEN - a valid response for a balanced fund
+   let symbol = "VBIAX";
+   let _s = session_mocks();
+   let _m = base_mock("vbiax", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Allocation::load(symbol)).unwrap();
+
+   // THEN - we get the allocation, ratings & bond statistics
+   assert_eq!(Some(0.6001), result.stocks);
+   assert_eq!(Some(0.3952), result.bonds);
+   assert_eq!(Some(&0.6772), result.bond_ratings.get("aaa"));
+   assert!(!result.bond_ratings.contains_key("other"));
+   assert_eq!(Some(&0.2475), result.sector_weightings.get("technology"));
+
+   let bonds = result.bond_statistics.unwrap();
+   assert_eq!(Some(6.62), bonds.duration);
+   assert_eq!(None, bonds.credit_quality);
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn load_allocation_not_found() {
+   //! Ensure that we gracefully fail for unknown symbols
+
+   // GIVEN - an error response for an invalid symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _m = base_mock("not_found", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(Allocation::load(symbol)).unwrap();
+
+   // THEN - we get an error
+}
@@ -1,14 +1,16 @@
 use chrono::{Duration, Utc};
-use mockito::{mock, Mock};
-use std::env;
+use mockito::{mock, Matcher, Mock};
 use std::fs::File;
 use std::io::prelude::*;
 use tokio_test::block_on;
-use yahoo_finance::{history, Interval};
+use yahoo_finance::{history, Bar, CancellationToken, Interval, TradingSession};
+use yahoo_finance::history::{Dividend, Granularity, Issue, Period, Range, Split};
+use yahoo_finance::symbols::Kind;
+use yahoo_finance::config::{self, Endpoints};
 
 fn base_mock(test_name: &str, symbol: &str, query: &str) -> std::io::Result<Mock> {
    // Tell the actual code to use a test URL rather than the live one
-   env::set_var("TEST_URL", mockito::server_url());
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
 
    // Load the simulated Yahoo data we want to test against
    let mut file = File::open(format!("tests/history_data/{}.json", test_name))?;
@@ -16,7 +18,7 @@ fn base_mock(test_name: &str, symbol: &str, query: &str) -> std::io::Result<Mock
    file.read_to_string(&mut contents)?;
 
    // Serve up the test data on the test URL
-   Ok(mock("GET", format!("/{symbol}?{query}", symbol=symbol, query=query).as_str())
+   Ok(mock("GET", format!("/v8/finance/chart/{symbol}?{query}", symbol=symbol, query=query).as_str())
       .with_header("content-type", "application/json")
       .with_body(&contents)
       .with_status(200))
@@ -25,6 +27,7 @@ fn base_mock(test_name: &str, symbol: &str, query: &str) -> std::io::Result<Mock
 fn build_interval(interval: Interval) -> String { format!("range={r}&interval={i}", r=interval, i=Interval::_1d) }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn retrieve_valid() {
    //! Ensure that we can load for valid companies
 
@@ -37,6 +40,40 @@ fn retrieve_valid() {
    assert!(result.len() > 0)
 }
 
+#[test]
+fn retrieve_index() {
+   //! Ensure that index symbols are encoded in the URL
+
+   // GIVEN - a valid response for an index
+   let _m = base_mock("aapl", "%5EDJI", build_interval(Interval::_6mo).as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(history::retrieve("^DJI")).unwrap();
+
+   // THEN - the index was loaded
+   assert!(!result.is_empty());
+}
+
+#[test]
+fn retrieve_mirror() {
+   //! Ensure that a mirror living under a path keeps its path
+
+   // GIVEN - a valid response from a mirror under `/yahoo`
+   let symbol = "AAPL";
+   config::set_endpoints(Endpoints::at(&format!("{}/yahoo", mockito::server_url())));
+   let _m = mock("GET", format!("/yahoo/v8/finance/chart/{}?{}", symbol, build_interval(Interval::_6mo)).as_str())
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we load the data
+   let result = block_on(history::retrieve(symbol)).unwrap();
+
+   // THEN - it came from the mirror
+   assert!(!result.is_empty());
+}
+
 #[test]
 #[should_panic(expected = "code: \"Not Found\"")]
 fn retrieve_invalid_symbol() {
@@ -52,6 +89,43 @@ fn retrieve_invalid_symbol() {
    // THEN - we get an error
 }
 
+#[test]
+fn retrieve_invalid_symbol_not_found() {
+   //! Ensure that an unknown symbol can be told apart from other failures
+
+   // GIVEN - a valid response for an invalid symbol
+   let symbol = "FUBAR";
+   let _m = base_mock("not_found", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
+
+   // WHEN - we load the data
+   let error = block_on(history::retrieve(symbol)).unwrap_err();
+
+   // THEN - the error says the symbol wasn't found & retrying won't help
+   assert!(error.is_not_found());
+   assert!(!error.is_retryable());
+}
+
+#[test]
+fn retrieve_failure_body() {
+   //! Ensure that what Yahoo says about a failed call is kept in the error
+
+   // GIVEN - Yahoo turns down the call & says why
+   let symbol = "AAPL";
+   let _m = mock("GET", format!("/v8/finance/chart/{}?{}", symbol, build_interval(Interval::_6mo)).as_str())
+      .with_body(r#"{"finance":{"result":null,"error":{"code":"Unauthorized","description":"Invalid Cookie"}}}"#)
+      .with_status(401)
+      .create();
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // WHEN - we load the data
+   let error = block_on(history::retrieve(symbol)).unwrap_err();
+
+   // THEN - the error has the status & the reason
+   let message = error.to_string();
+   assert!(message.contains("401"));
+   assert!(message.contains("Invalid Cookie"));
+}
+
 #[test]
 #[should_panic(expected = "NoIntraday")]
 fn retrieve_interval_invalid() {
@@ -98,6 +172,7 @@ fn retrieve_range_invalid2() {
 }
 
 #[test]
+#[allow(clippy::len_zero)]
 fn retrieve_no_quote_data() {
    //! Ensure that we gracefully handle the case where Yahoo send us an empty dictionary
    //! of quote data
@@ -126,3 +201,559 @@ fn retrieve_no_timestamp_data() {
 
    // THEN - we get an error
 }
+
+#[test]
+#[should_panic(expected = "Cancelled")]
+fn retrieve_cancelled() {
+   //! Ensure that a cancelled retrieval stops with an error
+
+   // GIVEN - a valid response and a cancelled token
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
+   let token = CancellationToken::new();
+   token.cancel();
+
+   // WHEN - we load the data
+   block_on(token.run(history::retrieve(symbol))).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn update_since() {
+   //! Ensure that we only add newer bars and refresh the last cached bar
+
+   // GIVEN - a cache whose last bar was loaded before the close, and newer data from Yahoo
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
+   let mut cached = block_on(history::retrieve(symbol)).unwrap();
+   cached.truncate(3);
+   cached[2].close = 1.0;
+
+   let _m = mock("GET", Matcher::Regex(r"^/v8/finance/chart/AAPL\?period1=1588167000&period2=\d+&interval=1d$".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we update the cache
+   let added = block_on(history::update_since(symbol, &mut cached)).unwrap();
+
+   // THEN - the new bars are added & the stale bar is refreshed
+   assert_eq!(2, added);
+   assert_eq!(5, cached.len());
+   assert!((cached[2].close - 287.73).abs() < 0.01);
+}
+
+#[test]
+fn retrieve_intraday() {
+   //! Ensure that intraday bars are requested over the longest range Yahoo serves
+
+   // GIVEN - a valid symbol with minute bars
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, "range=5d&interval=1m").unwrap().create();
+
+   // WHEN - we load 1 minute bars
+   let result = block_on(history::retrieve_intraday(symbol, Interval::_1m)).unwrap();
+
+   // THEN - we get the bars back
+   assert_eq!(5, result.len());
+}
+
+#[test]
+#[should_panic(expected = "NotIntraday")]
+fn retrieve_intraday_daily() {
+   //! Ensure that daily intervals are rejected for intraday data
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for daily bars as intraday data
+   block_on(history::retrieve_intraday(symbol, Interval::_1d)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_bars() {
+   //! Ensure that the range and bar size are sent independently
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, "range=1mo&interval=5m").unwrap().create();
+
+   // WHEN - we load a month of 5 minute bars
+   let result = block_on(history::retrieve_bars(symbol, Range::_1mo, Granularity::_5m)).unwrap();
+
+   // THEN - we get the bars back
+   assert_eq!(5, result.len());
+}
+
+#[test]
+#[should_panic(expected = "RangeTooLong")]
+fn retrieve_bars_too_long() {
+   //! Ensure that we don't ask Yahoo for more small bars than it keeps
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for a year of minute bars
+   block_on(history::retrieve_bars(symbol, Range::_1y, Granularity::_1m)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_adjusted() {
+   //! Ensure that adjusted closes are returned alongside the bars
+
+   // GIVEN - a valid symbol with adjusted closes (& one missing)
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_adjusted", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
+
+   // WHEN - we load the adjusted bars
+   let result = block_on(history::retrieve_adjusted(symbol, Interval::_6mo)).unwrap();
+
+   // THEN - we get the adjusted closes, falling back to the close when missing
+   assert_eq!(5, result.len());
+   assert!((result[0].adjusted_close - 70.79).abs() < 0.001);
+   assert!((result[3].adjusted_close - result[3].bar.close).abs() < 0.001);
+}
+
+#[test]
+fn retrieve_extended() {
+   //! Ensure that extended hours bars are requested & tagged with their session
+
+   // GIVEN - a valid symbol with pre & post market bars
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_prepost", symbol, "range=1mo&interval=30m&includePrePost=true").unwrap().create();
+
+   // WHEN - we load the extended bars
+   let result = block_on(history::retrieve_extended(symbol, Interval::_30m)).unwrap();
+
+   // THEN - each bar is tagged with its session
+   assert_eq!(4, result.len());
+   assert!(matches!(result[0].session, TradingSession::PreMarket));
+   assert!(matches!(result[1].session, TradingSession::Regular));
+   assert!(matches!(result[2].session, TradingSession::Regular));
+   assert!(matches!(result[3].session, TradingSession::AfterHours));
+}
+
+#[test]
+fn retrieve_many() {
+   //! Ensure that one failing symbol doesn't stop the rest from loading
+
+   // GIVEN - one valid and one unknown symbol
+   let _m1 = base_mock("aapl", "AAPL", build_interval(Interval::_6mo).as_str()).unwrap().create();
+   let _m2 = base_mock("not_found", "BOOM", build_interval(Interval::_6mo).as_str()).unwrap().create();
+
+   // WHEN - we load both
+   let result = block_on(history::retrieve_many(&["AAPL", "BOOM"], Interval::_6mo));
+
+   // THEN - each symbol gets its own result
+   assert_eq!(2, result.len());
+   assert_eq!(5, result["AAPL"].as_ref().unwrap().len());
+   assert!(result["BOOM"].is_err());
+}
+
+#[test]
+fn builder() {
+   //! Ensure that the builder sends every option & adjusts the bars
+
+   // GIVEN - a valid symbol with adjusted closes & corporate events
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_events", symbol, "range=1y&interval=1d&events=div%7Csplit%7CcapitalGain").unwrap().create();
+
+   // WHEN - we load adjusted bars with events
+   let result = block_on(history::Builder::new(symbol).range(Range::_1y).events(true).adjusted(true).fetch()).unwrap();
+
+   // THEN - the bars are adjusted & the events are returned
+   assert_eq!(5, result.bars.len());
+   assert!((result.bars[0].bar.close - 70.79).abs() < 0.001);
+   assert!((result.bars[0].bar.open - 281.80 * 70.79 / 283.17).abs() < 0.01);
+   assert!((result.bars[3].bar.close - 293.80).abs() < 0.01);
+   assert_eq!(1, result.events.dividends.len());
+   assert_eq!(1, result.events.splits.len());
+   assert!((result.events.splits[0].ratio() - 4.0).abs() < 0.001);
+}
+
+#[test]
+#[should_panic(expected = "RangeTooLong")]
+fn builder_range_too_long() {
+   //! Ensure that the builder doesn't ask Yahoo for more small bars than it keeps
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+
+   // WHEN - we ask for 90 days of minute bars
+   let now = Utc::now();
+   block_on(history::Builder::new(symbol).between(now - Duration::days(90), now).granularity(Granularity::_1m).fetch()).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn retrieve_with_events() {
+   //! Ensure that fund capital gains are returned in order
+
+   // GIVEN - a fund with dividends & capital gains
+   let symbol = "VFIAX";
+   let _m = base_mock("vfiax_events", symbol, "range=5y&interval=1d&events=div%7Csplit%7CcapitalGain").unwrap().create();
+
+   // WHEN - we load the history with events
+   let (bars, events) = block_on(history::retrieve_with_events(symbol, Interval::_5y)).unwrap();
+
+   // THEN - we get the bars and the events
+   assert_eq!(5, bars.len());
+   assert_eq!(1, events.dividends.len());
+   assert_eq!(0, events.splits.len());
+   assert_eq!(2, events.capital_gains.len());
+   assert!((events.capital_gains[0].amount - 0.05).abs() < 0.001);
+   assert!((events.capital_gains[1].amount - 0.31).abs() < 0.001);
+}
+
+#[test]
+fn retrieve_with_meta() {
+   //! Ensure that the symbol information is returned with the bars
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
+
+   // WHEN - we load the history with its meta data
+   let (bars, meta) = block_on(history::retrieve_with_meta(symbol, Interval::_6mo)).unwrap();
+
+   // THEN - we get the information about the symbol
+   assert_eq!(5, bars.len());
+   assert_eq!(Some("USD".to_string()), meta.currency);
+   assert_eq!("NMS", meta.exchange);
+   assert_eq!(Kind::Equity, meta.kind);
+   assert_eq!(-14400, meta.gmt_offset);
+   assert_eq!("America/New_York", meta.exchange_timezone);
+}
+
+#[test]
+fn trading_periods() {
+   //! Ensure that the session times are returned for each day
+
+   // GIVEN - a valid symbol with pre & post market periods
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_prepost", symbol, "range=1d&interval=30m&includePrePost=true").unwrap().create();
+
+   // WHEN - we load the history
+   let history = block_on(history::Builder::new(symbol).range(Range::_1d).granularity(Granularity::_30m).prepost(true).fetch()).unwrap();
+
+   // THEN - we get the trading periods
+   let periods = &history.meta.trading_periods;
+   assert_eq!(1, periods.regular.len());
+   assert_eq!(1588339800, periods.regular[0].start.timestamp());
+   assert_eq!(1588363200, periods.post[0].start.timestamp());
+   assert_eq!(-14400, periods.current.as_ref().unwrap().regular.gmt_offset);
+   assert!(matches!(periods.session_at(1588366800 * 1000), TradingSession::AfterHours));
+}
+
+#[test]
+fn retrieve_bars_weekly() {
+   //! Ensure that weekly bars can be loaded over long ranges
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol, "range=10y&interval=1wk").unwrap().create();
+
+   // WHEN - we load 10 years of weekly bars
+   let result = block_on(history::retrieve_bars(symbol, Range::_10y, Granularity::_1wk)).unwrap();
+
+   // THEN - we get the bars back
+   assert_eq!(5, result.len());
+}
+
+fn daily_bar(timestamp: i64, open: f64, high: f64, low: f64, close: f64, volume: u64) -> Bar {
+   Bar { timestamp: timestamp * 1000, open, high, low, close, volume: Some(volume) }
+}
+
+#[test]
+fn resample_weekly() {
+   //! Ensure that daily bars are aggregated on week boundaries
+
+   // GIVEN - Thursday, Friday & the following Monday
+   let bars = vec![
+      daily_bar(1588253400, 10.0, 12.0, 9.0, 11.0, 100),
+      daily_bar(1588339800, 11.0, 15.0, 10.0, 14.0, 200),
+      daily_bar(1588599000, 14.0, 14.5, 8.0, 9.0, 300)
+   ];
+
+   // WHEN - we resample them into weeks
+   let weeks = history::resample(&bars, Period::Weekly, 0);
+
+   // THEN - the first two bars are combined & the Monday starts a new week
+   assert_eq!(2, weeks.len());
+   assert_eq!(1588253400 * 1000, weeks[0].timestamp);
+   assert!((weeks[0].open - 10.0).abs() < 0.001);
+   assert!((weeks[0].high - 15.0).abs() < 0.001);
+   assert!((weeks[0].low - 9.0).abs() < 0.001);
+   assert!((weeks[0].close - 14.0).abs() < 0.001);
+   assert_eq!(Some(300), weeks[0].volume);
+   assert_eq!(Some(300), weeks[1].volume);
+}
+
+#[test]
+fn resample_monthly() {
+   //! Ensure that daily bars are aggregated on month boundaries
+
+   // GIVEN - the last day in April and the first days of May
+   let bars = vec![
+      daily_bar(1588253400, 10.0, 12.0, 9.0, 11.0, 100),
+      daily_bar(1588339800, 11.0, 15.0, 10.0, 14.0, 200),
+      daily_bar(1588599000, 14.0, 14.5, 8.0, 9.0, 300)
+   ];
+
+   // WHEN - we resample them into months
+   let months = history::resample(&bars, Period::Monthly, 0);
+
+   // THEN - April & May are separate
+   assert_eq!(2, months.len());
+   assert!((months[1].open - 11.0).abs() < 0.001);
+   assert!((months[1].low - 8.0).abs() < 0.001);
+   assert!((months[1].close - 9.0).abs() < 0.001);
+   assert_eq!(Some(500), months[1].volume);
+}
+
+#[test]
+fn resample_exchange_calendar() {
+   //! Ensure that bars are put in the period of their date at the exchange rather than in UTC
+
+   // GIVEN - a Friday & a Monday in Tokyo, which start on the Thursday & the Sunday in UTC
+   let bars = vec![
+      daily_bar(1588258800, 10.0, 12.0, 9.0, 11.0, 100),
+      daily_bar(1588518000, 11.0, 15.0, 10.0, 14.0, 200)
+   ];
+
+   // WHEN - we resample them into weeks with Tokyo's offset
+   let weeks = history::resample(&bars, Period::Weekly, 9 * 3600);
+
+   // THEN - the Monday starts a new week
+   assert_eq!(2, weeks.len());
+   assert_eq!(1588518000 * 1000, weeks[1].timestamp);
+}
+
+#[test]
+fn adjust_for_splits() {
+   //! Ensure that only the bars before a split are adjusted
+
+   // GIVEN - bars around a 4 for 1 split
+   let mut bars = vec![
+      daily_bar(1588253400, 400.0, 420.0, 380.0, 404.0, 100),
+      daily_bar(1588339800, 101.0, 105.0, 100.0, 102.0, 400)
+   ];
+   let splits = vec![ Split { timestamp: 1588339800 * 1000, numerator: 4.0, denominator: 1.0 } ];
+
+   // WHEN - we adjust for the split
+   history::adjust_for_splits(&mut bars, &splits);
+
+   // THEN - the bar before the split is adjusted & the split day is not
+   assert!((bars[0].open - 100.0).abs() < 0.001);
+   assert!((bars[0].high - 105.0).abs() < 0.001);
+   assert!((bars[0].low - 95.0).abs() < 0.001);
+   assert!((bars[0].close - 101.0).abs() < 0.001);
+   assert_eq!(Some(400), bars[0].volume);
+   assert!((bars[1].close - 102.0).abs() < 0.001);
+   assert_eq!(Some(400), bars[1].volume);
+}
+
+#[test]
+fn total_return_series() {
+   //! Ensure that dividends are reinvested on their ex-date
+
+   // GIVEN - flat prices with a dividend on the second day
+   let bars = vec![
+      daily_bar(1588253400, 10.0, 10.0, 10.0, 10.0, 100),
+      daily_bar(1588339800, 10.0, 10.0, 10.0, 10.0, 100),
+      daily_bar(1588599000, 10.0, 10.0, 10.0, 11.0, 100)
+   ];
+   let dividends = vec![ Dividend { timestamp: 1588339800 * 1000, amount: 1.0 } ];
+
+   // WHEN - we build the total return series
+   let series = history::total_return_series(&bars, &dividends);
+
+   // THEN - the dividend adds 10% and then compounds with the price change
+   assert_eq!(3, series.len());
+   assert!((series[0] - 10.0).abs() < 0.001);
+   assert!((series[1] - 11.0).abs() < 0.001);
+   assert!((series[2] - 12.1).abs() < 0.001);
+}
+
+#[test]
+fn validate() {
+   //! Ensure that problems with a series of daily bars are found
+
+   // GIVEN - Thursday, a duplicate Thursday, the following Tuesday (missing Monday) & an out of order Monday
+   let bars = vec![
+      daily_bar(1588253400, 10.0, 10.0, 10.0, 10.0, 100),
+      daily_bar(1588253400, 10.0, 10.0, 10.0, 10.0, 100),
+      daily_bar(1588339800, 10.0, 10.0, 10.0, 10.0, 0),
+      daily_bar(1588685400, 10.0, 10.0, 10.0, 10.0, 100),
+      daily_bar(1588599000, 10.0, 10.0, 10.0, 10.0, 100)
+   ];
+
+   // WHEN - we validate them
+   let issues = history::validate(&bars, Granularity::_1d, -14400);
+
+   // THEN - each problem is reported
+   assert_eq!(vec![
+      Issue::Duplicate { timestamp: 1588253400000 },
+      Issue::ZeroVolume { timestamp: 1588339800000 },
+      Issue::Gap { after: 1588339800000, before: 1588685400000, missing: 1 },
+      Issue::OutOfOrder { timestamp: 1588599000000, previous: 1588685400000 }
+   ], issues);
+}
+
+#[test]
+fn validate_exchange_calendar() {
+   //! Ensure that gaps between bars are counted in days at the exchange rather than in UTC
+
+   // GIVEN - a Friday & the following Monday in Tokyo, which start on the Thursday & the Sunday in UTC
+   let bars = vec![
+      daily_bar(1588258800, 10.0, 12.0, 9.0, 11.0, 100),
+      daily_bar(1588518000, 11.0, 15.0, 10.0, 14.0, 200)
+   ];
+
+   // WHEN - we validate them with Tokyo's offset
+   let issues = history::validate(&bars, Granularity::_1d, 9 * 3600);
+
+   // THEN - no days are missing
+   assert!(issues.is_empty());
+}
+
+#[test]
+fn retrieve_lenient() {
+   //! Ensure that incomplete bars are returned & can be filled in
+
+   // GIVEN - a valid symbol with a day missing its values
+   let symbol = "AAPL";
+   let _m = base_mock("aapl_incomplete", symbol, build_interval(Interval::_6mo).as_str()).unwrap().create();
+
+   // WHEN - we load the bars leniently
+   let bars = block_on(history::retrieve_lenient(symbol, Interval::_6mo)).unwrap();
+
+   // THEN - the incomplete bar is kept & filled from the previous close
+   assert_eq!(5, bars.len());
+   assert!(bars[2].complete().is_none());
+
+   let filled = history::fill_missing(&bars);
+   assert_eq!(5, filled.len());
+   assert!((filled[2].open - 278.58).abs() < 0.01);
+   assert!((filled[2].close - 278.58).abs() < 0.01);
+}
+
+#[test]
+fn retrieve_last_n() {
+   //! Ensure that only the most recent bars are returned
+
+   // GIVEN - a valid symbol
+   let symbol = "AAPL";
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let _m = mock("GET", Matcher::Regex(r"^/v8/finance/chart/AAPL\?period1=\d+&period2=\d+&interval=1d$".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we ask for the last 3 daily bars
+   let result = block_on(history::retrieve_last_n(symbol, 3, Granularity::_1d)).unwrap();
+
+   // THEN - we get the 3 most recent bars
+   assert_eq!(3, result.len());
+   assert_eq!(1588339800 * 1000, result[2].timestamp);
+}
+
+#[test]
+fn retrieve_since() {
+   //! Ensure that only bars newer than the timestamp are returned
+
+   // GIVEN - a valid symbol with data from the last stored timestamp
+   let symbol = "AAPL";
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let _m = mock("GET", Matcher::Regex(r"^/v8/finance/chart/AAPL\?period1=1588080600&period2=\d+&interval=1d$".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .with_status(200)
+      .create();
+
+   // WHEN - we ask for the bars since the last stored one
+   let result = block_on(history::retrieve_since(symbol, 1588080600 * 1000)).unwrap();
+
+   // THEN - we only get the newer bars
+   assert_eq!(3, result.len());
+   assert_eq!(1588167000 * 1000, result[0].timestamp);
+}
+
+#[test]
+fn to_csv() {
+   //! Ensure that bars are written in Yahoo's download format
+
+   // GIVEN - some bars
+   let bars = vec![
+      daily_bar(1588253400, 289.96, 294.53, 288.35, 293.8, 45766000),
+      daily_bar(1588339800, 286.25, 299.0, 285.85, 289.07, 60095200)
+   ];
+
+   // WHEN - we write them as CSV
+   let mut output = Vec::new();
+   history::to_csv(&bars, -14400, &mut output).unwrap();
+
+   // THEN - we get Yahoo's columns
+   assert_eq!(
+      "Date,Open,High,Low,Close,Adj Close,Volume\n2020-04-30,289.96,294.53,288.35,293.8,293.8,45766000\n2020-05-01,286.25,299,285.85,289.07,289.07,60095200\n",
+      String::from_utf8(output).unwrap()
+   );
+}
+
+#[test]
+fn to_csv_exchange_calendar() {
+   //! Ensure that bars are dated on the exchange's calendar rather than in UTC
+
+   // GIVEN - a bar from Tokyo, timestamped at midnight there - the previous day in UTC
+   let bars = vec![ daily_bar(1588258800, 100.0, 110.0, 95.0, 105.0, 1000) ];
+
+   // WHEN - we write it as CSV for the exchange 9 hours ahead of GMT
+   let mut output = Vec::new();
+   history::to_csv(&bars, 9 * 3600, &mut output).unwrap();
+
+   // THEN - it has Tokyo's date
+   assert_eq!(
+      "Date,Open,High,Low,Close,Adj Close,Volume\n2020-05-01,100,110,95,105,105,1000\n",
+      String::from_utf8(output).unwrap()
+   );
+}
+
+#[test]
+fn splits_to_csv() {
+   //! Ensure that splits are written as ratios
+
+   // GIVEN - a split
+   let splits = vec![ Split { timestamp: 1598880600 * 1000, numerator: 4.0, denominator: 1.0 } ];
+
+   // WHEN - we write it as CSV
+   let mut output = Vec::new();
+   history::splits_to_csv(&splits, -14400, &mut output).unwrap();
+
+   // THEN - we get Yahoo's format
+   assert_eq!("Date,Stock Splits\n2020-08-31,4:1\n", String::from_utf8(output).unwrap());
+}
+
+#[test]
+fn session_bar_serde() {
+   //! Ensure that bars with sessions can be persisted
+
+   // GIVEN - a bar in the pre market
+   let bar = history::SessionBar { bar: daily_bar(1588334400, 1.0, 2.0, 0.5, 1.5, 10), session: TradingSession::PreMarket };
+
+   // WHEN - we write it out as JSON and read it back
+   let json = serde_json::to_string(&bar).unwrap();
+   let result: history::SessionBar = serde_json::from_str(&json).unwrap();
+
+   // THEN - we get the same bar
+   assert!(json.contains("\"session\":\"PreMarket\""));
+   assert_eq!(bar.bar.timestamp, result.bar.timestamp);
+   assert!(matches!(result.session, TradingSession::PreMarket));
+}
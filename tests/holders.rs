@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::holders;
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str, modules: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/holders_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules={modules}&crumb=crumb", symbol=symbol, modules=modules).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_institutional_holders() {
+   //! Ensure that we can load the top institutional holders
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_institutions", symbol, "institutionOwnership").unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(holders::institutional(symbol)).unwrap();
+
+   // THEN - we get the holders, largest first
+   assert_eq!(5, result.len());
+   let top = &result[0];
+   assert_eq!("Vanguard Group Inc", top.organization);
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 12, 31), top.report_date);
+   assert_eq!((Some(1_297_093_100), Some(249_727_829_526.0)), (top.shares, top.value));
+   assert_eq!((Some(0.0834), Some(0.0118)), (top.percent_held, top.percent_change));
+   assert_eq!(Some(-0.0107), result[2].percent_change);
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn load_institutional_holders_not_found() {
+   //! Ensure that we gracefully fail for unknown symbols
+
+   // GIVEN - an error response for an unknown symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _m = base_mock("not_found", symbol, "institutionOwnership").unwrap().create();
+
+   // WHEN - we load the data
+   block_on(holders::institutional(symbol)).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn load_insiders() {
+   //! Ensure that we can load the insiders along with their transactions
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl_insiders", symbol, "insiderHolders%2CinsiderTransactions").unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(holders::insiders(symbol)).unwrap();
+
+   // THEN - we get the roster
+   assert_eq!(3, result.roster.len());
+   let ceo = &result.roster[0];
+   assert_eq!(("COOK TIMOTHY D", Some("Chief Executive Officer".to_string())), (ceo.name.as_str(), ceo.relation.clone()));
+   assert_eq!((Some("Sale".to_string()), NaiveDate::from_ymd_opt(2023, 10, 10)), (ceo.latest_transaction.clone(), ceo.latest_transaction_date));
+   assert_eq!((Some(3_280_180), None), (ceo.shares_direct, ceo.shares_indirect));
+   assert_eq!((Some(1_600_000), NaiveDate::from_ymd_opt(2020, 5, 1)), (result.roster[1].shares_indirect, result.roster[1].shares_indirect_date));
+   assert_eq!(None, result.roster[2].relation);
+
+   // AND - the transactions, newest first
+   assert_eq!(3, result.transactions.len());
+   let sale = &result.transactions[0];
+   assert_eq!((NaiveDate::from_ymd_opt(2023, 11, 29), "LEVINSON ARTHUR D"), (sale.date, sale.name.as_str()));
+   assert_eq!((Some(100_000), Some(18_997_000.0), true), (sale.shares, sale.value, sale.direct));
+   let gift = &result.transactions[1];
+   assert_eq!((None, None, false), (gift.description.clone(), gift.value, gift.direct));
+}
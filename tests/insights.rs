@@ -0,0 +1,58 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::Insights;
+use yahoo_finance::insights::Direction;
+use yahoo_finance::config::{self, Endpoints};
+
+fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/insights_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/ws/insights/v1/finance/insights?symbol={symbol}", symbol=symbol).as_str())
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_insights() {
+   //! Ensure that we can load the technical insights for a symbol
+
+   // GIVEN - a valid response and stock symbol
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Insights::load(symbol)).unwrap();
+
+   // THEN - we get the outlooks, levels and valuation
+   assert_eq!(Direction::Bearish, result.short_term.unwrap().direction);
+   assert_eq!(Direction::Bullish, result.long_term.unwrap().direction);
+   assert_eq!(Some(106.01), result.levels.unwrap().support);
+   assert_eq!(Some("Overvalued".to_string()), result.valuation.unwrap().description);
+}
+
+#[test]
+fn load_no_insights() {
+   //! Ensure that symbols without insights load with empty data
+
+   // GIVEN - a response without any instrument info
+   let symbol = "FUBAR";
+   let _m = base_mock("no_insights", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Insights::load(symbol)).unwrap();
+
+   // THEN - nothing is filled in
+   assert!(result.short_term.is_none());
+   assert!(result.levels.is_none());
+   assert!(result.valuation.is_none());
+}
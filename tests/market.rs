@@ -0,0 +1,82 @@
+use mockito::{mock, Matcher, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::market::{self, ExtendedSession, MoverKind, Region};
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/market_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("POST", "/v1/finance/screener?crumb=crumb")
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn movers_by_region() {
+   //! Ensure that we can screen for the movers in a region
+
+   // GIVEN - a valid screener response for UK gainers
+   let _s = session_mocks();
+   let _m = base_mock("gainers").unwrap()
+      .match_body(Matcher::Regex(r#""gb""#.to_string()))
+      .create();
+
+   // WHEN - we load the biggest gainers
+   let result = block_on(market::movers(Region::UnitedKingdom, MoverKind::Gainers, 2)).unwrap();
+
+   // THEN - we get the UK symbols
+   assert_eq!(2, result.len());
+   assert_eq!("OCDO.L", result[0].symbol);
+   assert_eq!(Some("LSE".to_string()), result[0].exchange);
+}
+
+#[test]
+fn movers_by_exchange() {
+   //! Ensure that we can restrict movers to a single exchange
+
+   // GIVEN - a valid screener response for LSE gainers
+   let _s = session_mocks();
+   let _m = base_mock("gainers").unwrap()
+      .match_body(Matcher::Regex(r#""LSE""#.to_string()))
+      .create();
+
+   // WHEN - we load the biggest gainers
+   let result = block_on(market::movers_on(Region::UnitedKingdom, "LSE", MoverKind::Gainers, 2)).unwrap();
+
+   // THEN - we get the exchange symbols
+   assert_eq!(Some(8.16), result[0].change_percent);
+}
+
+#[test]
+fn extended_movers() {
+   //! Ensure that we can screen on extended hours moves
+
+   // GIVEN - a valid screener response sorted by pre market change
+   let _s = session_mocks();
+   let _m = base_mock("pre_market").unwrap()
+      .match_body(Matcher::Regex("premarketchangepercent".to_string()))
+      .create();
+
+   // WHEN - we load the biggest pre market gainers
+   let result = block_on(market::extended_movers(Region::UnitedStates, ExtendedSession::PreMarket, MoverKind::Gainers, 1)).unwrap();
+
+   // THEN - we get the pre market move
+   let pre = result[0].pre_market.clone().unwrap();
+   assert_eq!(512.5, pre.price);
+   assert_eq!(Some(11.98), pre.change_percent);
+   assert!(result[0].after_hours.is_none());
+}
@@ -0,0 +1,70 @@
+use mockito::mock;
+use std::sync::{Arc, Mutex};
+use tokio_test::block_on;
+use yahoo_finance::{history, Financials};
+use yahoo_finance::config::{self, Endpoints};
+use yahoo_finance::metrics::{self, Operation, RequestEvent};
+
+#[test]
+fn observes_requests() {
+   //! Ensure that the request observer hears about successful & failed calls
+
+   // GIVEN - one symbol Yahoo knows about, one it doesn't & an observer
+   let body = std::fs::read_to_string("tests/history_data/aapl.json").unwrap();
+   let _found = mock("GET", "/v8/finance/chart/AAPL?range=6mo&interval=1d")
+      .with_header("content-type", "application/json")
+      .with_body(&body)
+      .create();
+   let _missing = mock("GET", "/v8/finance/chart/NULL?range=6mo&interval=1d")
+      .with_status(404)
+      .create();
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   let events = Arc::new(Mutex::new(Vec::new()));
+   let observed = events.clone();
+   metrics::set_observer(move |event: &RequestEvent| observed.lock().unwrap().push(event.clone()));
+
+   // WHEN - we load both
+   block_on(history::retrieve("AAPL")).unwrap();
+   block_on(history::retrieve("NULL")).unwrap_err();
+   metrics::clear_observer();
+
+   // THEN - each call was observed with its status & size
+   let events = events.lock().unwrap();
+   assert_eq!(2, events.len());
+   assert_eq!(Operation::Get, events[0].operation);
+   assert_eq!(format!("{}/v8/finance/chart/AAPL", mockito::server_url()), events[0].url);
+   assert_eq!(Some(200), events[0].status);
+   assert_eq!(body.len() as u64, events[0].bytes);
+   assert_eq!(Some(404), events[1].status);
+}
+
+#[test]
+fn observer_changed_while_observing() {
+   //! Ensure that an observer can clear itself & that bodies without a length are still measured
+
+   // GIVEN - a session crumb sent in chunks, a quote summary & an observer that stops after the crumb
+   let _cookie = mock("GET", "/").with_header("set-cookie", "A3=session; Domain=.yahoo.com; Path=/").with_status(404).create();
+   let _crumb = mock("GET", "/v1/test/getcrumb").with_body_from_fn(|w| w.write_all(b"crumb")).create();
+   let _summary = mock("GET", "/v10/finance/quoteSummary/AAPL?modules=financialData%2Cprice&crumb=crumb")
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/financials_data/aapl.json").unwrap())
+      .create();
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   let events = Arc::new(Mutex::new(Vec::new()));
+   let observed = events.clone();
+   metrics::set_observer(move |event: &RequestEvent| {
+      observed.lock().unwrap().push(event.clone());
+      if event.url.ends_with("getcrumb") { metrics::clear_observer(); }
+   });
+
+   // WHEN - we load something that needs the session
+   block_on(Financials::load("AAPL")).unwrap();
+
+   // THEN - the observer heard about the session calls, the crumb being measured as read
+   let events = events.lock().unwrap();
+   assert_eq!(2, events.len());
+   assert!(events[1].url.ends_with("/v1/test/getcrumb"));
+   assert_eq!(5, events[1].bytes);
+}
@@ -0,0 +1,238 @@
+use std::fs;
+use yahoo_finance::{parse, Profile};
+use yahoo_finance::symbols::Kind;
+
+#[test]
+fn parse_chart() {
+   //! Ensure that we can read a saved chart response
+
+   // GIVEN - a saved chart response
+   let data = fs::read("tests/history_data/aapl.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::chart(&data).unwrap();
+
+   // THEN - we get the bars
+   assert_eq!(5, result.len());
+   assert_eq!(1_587_994_200_000, result[0].timestamp);
+}
+
+#[test]
+#[should_panic(expected = "code: \"Not Found\"")]
+fn parse_chart_error() {
+   //! Ensure that saved error responses are reported
+
+   // GIVEN - a saved chart error
+   let data = fs::read("tests/history_data/not_found.json").unwrap();
+
+   // WHEN - we parse it
+   parse::chart(&data).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn parse_profile() {
+   //! Ensure that we can read a saved quote page
+
+   // GIVEN - a saved quote page
+   let data = fs::read("tests/profile_data/aapl.html").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::profile(&data).unwrap();
+
+   // THEN - we get the profile
+   match result {
+      Profile::Company(profile) => assert_eq!("Apple Inc.", profile.name),
+      _ => panic!("Needs to be a company profile")
+   }
+}
+
+#[test]
+fn parse_quote_summary_profile() {
+   //! Ensure that we can read a profile out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the profile modules
+   let data = fs::read("tests/profile_data/gld_summary.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_profile(&data).unwrap();
+
+   // THEN - we get the profile
+   match result {
+      Profile::Fund(profile) => assert_eq!("SPDR Gold Trust", profile.name),
+      _ => panic!("Needs to be a fund profile")
+   }
+}
+
+#[test]
+fn parse_quote_summary_allocation() {
+   //! Ensure that we can read a saved quote summary
+
+   // GIVEN - a saved quote summary with the fund holdings
+   let data = fs::read("tests/funds_data/vbiax.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_allocation(&data).unwrap();
+
+   // THEN - we get the allocation
+   assert_eq!(Some(0.6001), result.stocks);
+}
+
+#[test]
+fn parse_quote_type() {
+   //! Ensure that we can read a saved quote type
+
+   // GIVEN - a saved quote type response
+   let data = fs::read("tests/symbols_data/btc.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_type(&data).unwrap();
+
+   // THEN - we get the kind of symbol
+   assert_eq!(Kind::Crypto, result);
+}
+
+#[test]
+fn parse_income_statement() {
+   //! Ensure that we can read saved fundamentals
+
+   // GIVEN - a saved fundamentals timeseries response
+   let data = fs::read("tests/fundamentals_data/aapl_income.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::income_statement(&data).unwrap();
+
+   // THEN - we get the income statements
+   assert_eq!(4, result.len());
+   assert_eq!(Some(6.11), result[2].diluted_eps);
+}
+
+#[test]
+fn parse_balance_sheet() {
+   //! Ensure that we can read saved balance sheets
+
+   // GIVEN - a saved fundamentals timeseries response
+   let data = fs::read("tests/fundamentals_data/aapl_balance.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::balance_sheet(&data).unwrap();
+
+   // THEN - we get the balance sheets
+   assert_eq!(4, result.len());
+   assert_eq!(Some(50_672_000_000.0), result[2].stockholders_equity);
+}
+
+#[test]
+fn parse_quote_summary_earnings() {
+   //! Ensure that we can read the earnings out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the earnings modules
+   let data = fs::read("tests/earnings_data/aapl.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_earnings(&data).unwrap();
+
+   // THEN - we get the earnings
+   assert_eq!(Some(1.43), result.history[0].estimate);
+   assert_eq!("0q", result.estimates[0].period);
+}
+
+#[test]
+fn parse_quote_summary_earnings_date() {
+   //! Ensure that we can read the next earnings date out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the calendar events
+   let data = fs::read("tests/earnings_data/aapl_calendar.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_earnings_date(&data).unwrap();
+
+   // THEN - we get the date
+   assert!(result.unwrap().estimated);
+}
+
+#[test]
+fn parse_quote_summary_surprises() {
+   //! Ensure that we can read the EPS surprises out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the earnings history
+   let data = fs::read("tests/earnings_data/aapl_history.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_surprises(&data).unwrap();
+
+   // THEN - we get the surprises
+   assert_eq!(Some(0.063), result[0].surprise);
+}
+
+#[test]
+fn parse_quote_summary_ratings() {
+   //! Ensure that we can read the analyst ratings out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the recommendation trend
+   let data = fs::read("tests/analysts_data/aapl_trend.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_ratings(&data).unwrap();
+
+   // THEN - we get the ratings
+   assert_eq!(4, result.len());
+   assert_eq!(24, result[0].buy);
+}
+
+#[test]
+fn parse_quote_summary_rating_changes() {
+   //! Ensure that we can read the rating changes out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the upgrade / downgrade history
+   let data = fs::read("tests/analysts_data/aapl_grades.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_rating_changes(&data).unwrap();
+
+   // THEN - we get the changes
+   assert_eq!("Morgan Stanley", result[5].firm);
+}
+
+#[test]
+fn parse_quote_summary_price_targets() {
+   //! Ensure that we can read the price targets out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the financial data
+   let data = fs::read("tests/analysts_data/aapl_targets.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_price_targets(&data).unwrap();
+
+   // THEN - we get the targets
+   assert_eq!(Some(200.0), result.median);
+}
+
+#[test]
+fn parse_quote_summary_institutional_holders() {
+   //! Ensure that we can read the institutional holders out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the institution ownership
+   let data = fs::read("tests/holders_data/aapl_institutions.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_institutional_holders(&data).unwrap();
+
+   // THEN - we get the holders
+   assert_eq!("FMR, LLC", result[4].organization);
+}
+
+#[test]
+fn parse_quote_summary_insiders() {
+   //! Ensure that we can read the insiders out of a saved quote summary
+
+   // GIVEN - a saved quote summary with the insider modules
+   let data = fs::read("tests/holders_data/aapl_insiders.json").unwrap();
+
+   // WHEN - we parse it
+   let result = parse::quote_summary_insiders(&data).unwrap();
+
+   // THEN - we get the roster & transactions
+   assert_eq!((3, 3), (result.roster.len(), result.transactions.len()));
+}
@@ -0,0 +1,26 @@
+#![cfg(feature = "polars")]
+
+use yahoo_finance::{history, Bar};
+
+#[test]
+fn dataframe_round_trip() {
+   //! Ensure that bars survive a trip through a data frame
+
+   // GIVEN - some bars, one without volume
+   let bars = vec![
+      Bar { timestamp: 1588253400000, open: 289.96, high: 294.53, low: 288.35, close: 293.8, volume: Some(45766000) },
+      Bar { timestamp: 1588339800000, open: 286.25, high: 299.0, low: 285.85, close: 289.07, volume: None }
+   ];
+
+   // WHEN - we convert them to a data frame & back
+   let frame = history::to_dataframe(&bars).unwrap();
+   let result = history::from_dataframe(&frame).unwrap();
+
+   // THEN - we get the same bars back
+   assert_eq!((2, 6), frame.shape());
+   assert_eq!(2, result.len());
+   assert_eq!(1588339800000, result[1].timestamp);
+   assert!((result[1].high - 299.0).abs() < 0.001);
+   assert_eq!(Some(45766000), result[0].volume);
+   assert_eq!(None, result[1].volume);
+}
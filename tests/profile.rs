@@ -1,13 +1,17 @@
 use mockito::{mock, Mock};
-use std::env;
 use std::fs::File;
 use std::io::prelude::*;
+use std::sync::{Arc, Mutex};
 use tokio_test::block_on;
-use yahoo_finance::{Profile};
+use yahoo_finance::{schema, Profile};
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
 
 fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
    // Tell the actual code to use a test URL rather than the live one
-   env::set_var("TEST_URL", mockito::server_url());
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
 
    // Load the simulated Yahoo data we want to test against
    let mut file = File::open(format!("tests/profile_data/{}.html", test_name))?;
@@ -21,6 +25,21 @@ fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
       .with_status(200))
 }
 
+fn summary_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   let mut file = File::open(format!("tests/profile_data/{}_summary.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   let path = format!("/v10/finance/quoteSummary/{}?modules=assetProfile%2CquoteType%2CfundProfile%2Cprice%2CfinancialData&crumb=crumb", symbol);
+   Ok(mock("GET", path.as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
 #[test]
 fn load_company() {
    //! Ensure that we can load for valid companies
@@ -113,3 +132,171 @@ fn load_missing_data() {
 
    // THEN - we get an error
 }
+
+#[test]
+fn load_maintenance_page() {
+   //! Ensures that Yahoo's maintenance page is reported as an outage rather than missing data
+
+   // GIVEN - Yahoo is down for maintenance
+   let symbol = "AAPL";
+   let _m = base_mock("maintenance", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let error = block_on(Profile::load(symbol)).unwrap_err();
+
+   // THEN - we're told Yahoo is unavailable & to try again later
+   assert!(format!("{:?}", error).contains("ServiceUnavailable"));
+   assert!(error.is_retryable());
+}
+
+#[test]
+fn load_blocked_page() {
+   //! Ensures that Yahoo's captcha page is reported as being blocked rather than missing data
+
+   // GIVEN - Yahoo wants us to fill in a captcha
+   let symbol = "AAPL";
+   let _m = base_mock("blocked", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let error = block_on(Profile::load(symbol)).unwrap_err();
+
+   // THEN - we're told we were blocked
+   assert!(format!("{:?}", error).contains("Blocked"));
+   assert!(error.is_rate_limited());
+}
+
+#[test]
+fn load_company_currencies() {
+   //! Ensure that we know when a company reports in a different currency than it trades in
+
+   // GIVEN - a company trading in USD and reporting in TWD
+   let symbol = "TSM";
+   let _m = base_mock("tsm", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Profile::load(symbol)).unwrap();
+
+   // THEN - both currencies are available and can be converted
+   match result {
+      Profile::Company(profile) => {
+         assert_eq!(Some("USD".to_string()), profile.currencies.trading);
+         assert_eq!(Some("TWD".to_string()), profile.currencies.financial);
+         assert!(profile.currencies.is_mixed());
+
+         let converted = profile.currencies.to_trading(100.0, &|from: &str, to: &str| {
+            if from == "TWD" && to == "USD" { Some(0.25) } else { None }
+         });
+         assert_eq!(Some(25.0), converted);
+      },
+      _ => panic!("Needs to be a company profile")
+   }
+}
+
+#[test]
+fn load_fund_schema_fallback() {
+   //! Ensures that we fall back to an older schema version when Yahoo changes its data
+
+   // GIVEN - a fund without a long name and an observer watching for schema matches
+   let symbol = "GLD";
+   let _m = base_mock("no_long_name", symbol).unwrap().create();
+
+   let matches = Arc::new(Mutex::new(Vec::new()));
+   let observed = matches.clone();
+   schema::set_observer(move |matched| observed.lock().unwrap().push(matched.clone()));
+
+   // WHEN - we load the data
+   let result = block_on(Profile::load(symbol)).unwrap();
+
+   // THEN - the short name is used and the fallback is reported
+   match result {
+      Profile::Fund(profile) => assert_eq!("SPDR Gold Trust", profile.name),
+      _ => panic!("Needs to be a fund profile")
+   }
+   assert!(matches.lock().unwrap().iter().any(|m| m.endpoint == "web_scraper" && m.version == "short-name" && m.fallback));
+}
+
+#[test]
+fn schema_observer_clears_itself() {
+   //! Ensures that an observer can remove itself while it is being notified
+
+   // GIVEN - an observer that only wants to hear about the first match
+   let symbol = "GLD";
+   let _m = base_mock("no_long_name", symbol).unwrap().expect(2).create();
+
+   let calls = Arc::new(Mutex::new(0));
+   let counted = calls.clone();
+   schema::set_observer(move |_| {
+      *counted.lock().unwrap() += 1;
+      schema::clear_observer();
+   });
+
+   // WHEN - we load the data twice
+   block_on(Profile::load(symbol)).unwrap();
+   block_on(Profile::load(symbol)).unwrap();
+
+   // THEN - it was notified just the once
+   assert_eq!(1, *calls.lock().unwrap());
+}
+
+#[test]
+fn profile_serde_round_trip() {
+   //! Ensure that profiles can be persisted & read back
+
+   // GIVEN - a loaded profile
+   let symbol = "AAPL";
+   let _m = base_mock("aapl", symbol).unwrap().create();
+   let profile = block_on(Profile::load(symbol)).unwrap();
+
+   // WHEN - we write it out as JSON and read it back
+   let json = serde_json::to_string(&profile).unwrap();
+   let result: Profile = serde_json::from_str(&json).unwrap();
+
+   // THEN - we get the same profile
+   assert_eq!(profile, result);
+}
+
+#[test]
+fn load_company_quote_summary() {
+   //! Ensure that the profile comes from the quote summary without touching the quote page
+
+   // GIVEN - a quote summary for a company & no quote page
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = summary_mock("aapl", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Profile::load(symbol)).unwrap();
+
+   // THEN - the company profile came from the quote summary
+   match result {
+      Profile::Company(profile) => {
+         assert_eq!("Apple Inc.", profile.name);
+         assert_eq!(Some("Consumer Electronics".to_string()), profile.industry);
+         assert_eq!(Some("Cupertino".to_string()), profile.address.unwrap().city);
+         assert_eq!(Some("USD".to_string()), profile.currencies.financial);
+      },
+      _ => panic!("Needs to be a company profile")
+   }
+}
+
+#[test]
+fn load_fund_quote_summary() {
+   //! Ensure that a fund without a long name is loaded from the quote summary by its short name
+
+   // GIVEN - a quote summary for a fund without a long name
+   let symbol = "GLD";
+   let _s = session_mocks();
+   let _m = summary_mock("gld", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Profile::load(symbol)).unwrap();
+
+   // THEN - we get the fund
+   match result {
+      Profile::Fund(profile) => {
+         assert_eq!("SPDR Gold Trust", profile.name);
+         assert_eq!("Exchange Traded Fund", profile.kind);
+      },
+      _ => panic!("Needs to be a fund profile")
+   }
+}
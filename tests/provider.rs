@@ -0,0 +1,60 @@
+use chrono::{DateTime, Duration, Utc};
+use futures::future::BoxFuture;
+use mockito::{mock, Matcher};
+use tokio_test::block_on;
+use yahoo_finance::{Bar, Result};
+use yahoo_finance::provider::{FallbackProvider, Provider};
+use yahoo_finance::config::{self, Endpoints};
+
+struct Backup;
+
+impl Provider for Backup {
+   fn name(&self) -> &str { "backup" }
+
+   fn history<'a>(&'a self, _symbol: &'a str, start: DateTime<Utc>, _end: DateTime<Utc>) -> BoxFuture<'a, Result<Vec<Bar>>> {
+      let bar = Bar { timestamp: start.timestamp_millis(), open: 1.0, high: 2.0, low: 0.5, close: 1.5, volume: Some(100) };
+      Box::pin(async move { Ok(vec![bar]) })
+   }
+}
+
+fn chart_mock(status: usize, body: &str) -> mockito::Mock {
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   mock("GET", Matcher::Regex(r"^/v8/finance/chart/AAPL\?".to_string()))
+      .with_header("content-type", "application/json")
+      .with_body(body)
+      .with_status(status)
+}
+
+#[test]
+fn fallback_on_outage() {
+   //! Ensure that the secondary provider serves the data when Yahoo is down
+
+   // GIVEN - Yahoo is rate limiting us
+   let _m = chart_mock(429, "").create();
+   let provider = FallbackProvider::new(Backup);
+
+   // WHEN - we retrieve data
+   let now = Utc::now();
+   let served = block_on(provider.retrieve("AAPL", now - Duration::days(10), now)).unwrap();
+
+   // THEN - the backup served it
+   assert_eq!("backup", served.source);
+   assert_eq!(1, served.data.len());
+}
+
+#[test]
+#[should_panic(expected = "ChartFailed")]
+fn no_fallback_on_bad_symbol() {
+   //! Ensure that failures other than outages are not hidden by the secondary provider
+
+   // GIVEN - Yahoo doesn't know the symbol
+   let _m = chart_mock(200, &std::fs::read_to_string("tests/history_data/not_found.json").unwrap()).create();
+   let provider = FallbackProvider::new(Backup);
+
+   // WHEN - we retrieve data
+   let now = Utc::now();
+   block_on(provider.retrieve("AAPL", now - Duration::days(10), now)).unwrap();
+
+   // THEN - we get the failure from Yahoo
+}
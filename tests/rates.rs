@@ -0,0 +1,38 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::rates::{self, Treasury};
+use yahoo_finance::config::{self, Endpoints};
+
+fn base_mock(test_name: &str, path: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/rates_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", path)
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn treasury_yield() {
+   //! Ensure that we can load the current yield for a maturity
+
+   // GIVEN - a valid response for the 10 year yield index
+   let _m = base_mock("tnx", "/v8/finance/chart/%5ETNX?range=5d&interval=1d").unwrap().create();
+
+   // WHEN - we load the current yield
+   let result = block_on(rates::treasury_yield(Treasury::TenYear)).unwrap();
+
+   // THEN - we get the yield
+   assert_eq!(Treasury::TenYear, result.maturity);
+   assert!((result.rate - 0.72).abs() < 1e-6);
+   assert_eq!("US 10Y", result.maturity.to_string());
+}
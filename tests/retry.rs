@@ -0,0 +1,142 @@
+use mockito::mock;
+use std::time::Duration;
+use tokio_test::block_on;
+use yahoo_finance::{config, history};
+use yahoo_finance::config::{Endpoints, RetryPolicy};
+
+fn policy() -> RetryPolicy {
+   RetryPolicy { max_attempts: 3, initial_backoff: Duration::from_millis(1), max_backoff: Duration::from_millis(5), jitter: true }
+}
+
+// Settings are shared by the whole process, so each test changes them only
+// after creating its mocks - which holds the mock server's lock & keeps the
+// tests from seeing each other's settings.
+
+#[test]
+fn retries_outages() {
+   //! Ensure that temporary failures are retried until we run out of attempts
+
+   // GIVEN - Yahoo is having problems & we retry 3 times
+   let m = mock("GET", "/v8/finance/chart/AAPL?range=6mo&interval=1d")
+      .with_status(503)
+      .expect(3)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_retry_policy(policy());
+
+   // WHEN - we load some history
+   let result = block_on(history::retrieve("AAPL"));
+
+   // THEN - every attempt was made before failing
+   m.assert();
+   let error = result.unwrap_err();
+   assert!(format!("{:?}", error).contains("CallFailed"));
+   assert!(error.is_retryable());
+   assert!(!error.is_rate_limited());
+}
+
+#[test]
+fn no_retry_on_permanent_failure() {
+   //! Ensure that failures which won't go away aren't retried
+
+   // GIVEN - Yahoo doesn't have what we asked for
+   let m = mock("GET", "/v8/finance/chart/MSFT?range=6mo&interval=1d")
+      .with_status(404)
+      .expect(1)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_retry_policy(policy());
+
+   // WHEN - we load some history
+   let result = block_on(history::retrieve("MSFT"));
+
+   // THEN - only one call was made
+   m.assert();
+   let error = result.unwrap_err();
+   assert!(error.is_not_found());
+   assert!(!error.is_retryable());
+}
+
+#[test]
+fn fails_over_to_second_host() {
+   //! Ensure that chart calls move on to the second query host when the first is down
+
+   // GIVEN - the first host is failing while the second one is fine
+   let address = mockito::server_address();
+   let mut endpoints = Endpoints::at(&format!("http://{}", address));
+   endpoints.query_fallback = Some(format!("http://localhost:{}", address.port()));
+
+   let first = mock("GET", "/v8/finance/chart/AAPL?range=6mo&interval=1d")
+      .match_header("host", address.to_string().as_str())
+      .with_status(502)
+      .expect(1)
+      .create();
+   let second = mock("GET", "/v8/finance/chart/AAPL?range=6mo&interval=1d")
+      .match_header("host", format!("localhost:{}", address.port()).as_str())
+      .with_header("content-type", "application/json")
+      .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+      .expect(1)
+      .create();
+
+   config::set_endpoints(endpoints);
+   config::set_retry_policy(RetryPolicy::NONE);
+
+   // WHEN - we load some history
+   let result = block_on(history::retrieve("AAPL"));
+
+   // THEN - the second host answered
+   first.assert();
+   second.assert();
+   assert!(result.is_ok());
+}
+
+#[test]
+fn throttled_calls() {
+   //! Ensure that rate limiting is reported with how long Yahoo wants us to wait, & retried
+
+   // GIVEN - Yahoo is rate limiting us & says to retry right away
+   let m = mock("GET", "/v8/finance/chart/TSLA?range=6mo&interval=1d")
+      .with_header("retry-after", "0")
+      .with_status(429)
+      .expect(3)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_retry_policy(policy());
+
+   // WHEN - we load some history
+   let error = block_on(history::retrieve("TSLA")).unwrap_err();
+
+   // THEN - every attempt was made & the wait is part of the error
+   m.assert();
+   assert!(format!("{:?}", error).contains("CallThrottled"));
+   assert!(error.is_rate_limited());
+   assert!(error.is_retryable());
+   assert!(!error.is_not_found());
+   assert_eq!(Some(Duration::from_secs(0)), error.retry_after());
+}
+
+#[test]
+fn throttled_for_too_long() {
+   //! Ensure that we don't wait longer than the retry policy allows when Yahoo asks us to
+
+   // GIVEN - Yahoo is rate limiting us for an hour
+   let m = mock("GET", "/v8/finance/chart/NFLX?range=6mo&interval=1d")
+      .with_header("retry-after", "3600")
+      .with_status(429)
+      .expect(1)
+      .create();
+
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   config::set_retry_policy(policy());
+
+   // WHEN - we load some history
+   let error = block_on(history::retrieve("NFLX")).unwrap_err();
+
+   // THEN - we gave up right away, with how long Yahoo wants us to wait
+   m.assert();
+   assert!(error.is_rate_limited());
+   assert_eq!(Some(Duration::from_secs(3600)), error.retry_after());
+}
@@ -0,0 +1,42 @@
+use mockito::mock;
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::funds::Allocation;
+use yahoo_finance::config::{self, Endpoints};
+
+#[test]
+fn refreshes_rejected_session() {
+   //! Ensure that a session Yahoo! no longer accepts is replaced with a new one
+
+   // GIVEN - a session that has been loaded & used
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let mut file = File::open("tests/funds_data/vbiax.json").unwrap();
+   let mut contents = String::new();
+   file.read_to_string(&mut contents).unwrap();
+
+   let _cookie = mock("GET", "/").with_header("set-cookie", "A3=session; Path=/").with_status(404).create();
+   let old_crumb = mock("GET", "/v1/test/getcrumb").match_header("cookie", "A3=session").with_body("old").create();
+   let summary = mock("GET", "/v10/finance/quoteSummary/VBIAX?modules=topHoldings&crumb=old")
+      .match_header("cookie", "A3=session")
+      .with_body(&contents)
+      .create();
+   block_on(Allocation::load("VBIAX")).unwrap();
+   drop(old_crumb);
+   drop(summary);
+
+   // ... which Yahoo! then turns down
+   let _new_crumb = mock("GET", "/v1/test/getcrumb").with_body("new").expect(1).create();
+   let _rejected = mock("GET", "/v10/finance/quoteSummary/VBIAX?modules=topHoldings&crumb=old").with_status(401).create();
+   let accepted = mock("GET", "/v10/finance/quoteSummary/VBIAX?modules=topHoldings&crumb=new")
+      .match_header("cookie", "A3=session")
+      .with_body(&contents)
+      .create();
+
+   // WHEN - we load the data again
+   let result = block_on(Allocation::load("VBIAX")).unwrap();
+
+   // THEN - a new crumb was fetched & used
+   assert_eq!(Some(0.6001), result.stocks);
+   accepted.assert();
+}
@@ -0,0 +1,28 @@
+use futures::join;
+use mockito::mock;
+use tokio_test::block_on;
+use yahoo_finance::funds::Allocation;
+use yahoo_finance::config::{self, Endpoints};
+
+#[test]
+fn shares_session_fetch() {
+   //! Ensure that calls needing a session at the same time only fetch one
+
+   // GIVEN - no session yet & data that needs one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+   let contents = std::fs::read_to_string("tests/funds_data/vbiax.json").unwrap();
+   let _cookie = mock("GET", "/").with_header("set-cookie", "A3=session; Path=/").with_status(404).create();
+   let crumb = mock("GET", "/v1/test/getcrumb").match_header("cookie", "A3=session").with_body("crumb").expect(1).create();
+   let _summary = mock("GET", "/v10/finance/quoteSummary/VBIAX?modules=topHoldings&crumb=crumb")
+      .match_header("cookie", "A3=session")
+      .with_body(&contents)
+      .expect(2)
+      .create();
+
+   // WHEN - we load the data twice at once
+   let (first, second) = block_on(async { join!(Allocation::load("VBIAX"), Allocation::load("VBIAX")) });
+
+   // THEN - both loaded with the one crumb
+   assert!(first.is_ok() && second.is_ok());
+   crumb.assert();
+}
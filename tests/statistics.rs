@@ -0,0 +1,69 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::Statistics;
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/statistics_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules=defaultKeyStatistics%2CsummaryDetail%2CfinancialData&crumb=crumb", symbol=symbol).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_statistics() {
+   //! Ensure that we can load the key statistics for a company
+
+   // GIVEN - a valid response for a stock
+   let symbol = "AAPL";
+   let _s = session_mocks();
+   let _m = base_mock("aapl", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Statistics::load(symbol)).unwrap();
+
+   // THEN - we get the statistics from both modules
+   assert_eq!(Some(2_943_293_243_392.0), result.market_cap);
+   assert_eq!(Some(30.893965), result.trailing_pe);
+   assert_eq!(Some(26.412819), result.forward_pe);
+   assert_eq!(Some(6.13), result.trailing_eps);
+   assert_eq!(Some(1.286802), result.beta);
+   assert_eq!(Some(15_535_332_918), result.float_shares);
+   assert_eq!(Some(1.84), result.short_ratio);
+   assert_eq!(Some(0.3756218), result.change_52_week);
+   assert_eq!(Some(0.20118737), result.sp500_change_52_week);
+
+   // AND - the currencies it trades & reports in
+   assert_eq!((Some("USD".to_string()), Some("USD".to_string())), (result.currencies.trading, result.currencies.financial));
+}
+
+#[test]
+#[should_panic(expected = "MissingData")]
+fn load_statistics_missing() {
+   //! Ensure that we gracefully fail when Yahoo has no key statistics for the symbol
+
+   // GIVEN - a response for an index, without any key statistics
+   let symbol = "SPX";
+   let _s = session_mocks();
+   let _m = base_mock("no_statistics", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(Statistics::load(symbol)).unwrap();
+
+   // THEN - we get an error
+}
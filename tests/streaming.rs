@@ -0,0 +1,1237 @@
+use futures::{SinkExt, StreamExt};
+use mockito::{mock, Matcher};
+use tokio::net::TcpListener;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio_tungstenite::tungstenite::Message;
+use yahoo_finance::{Bar, CandleAggregator, Interval, Overflow, PricingData, Quote, Replay, StreamEvent, Streamer, SubscriptionEvent, TradingSession};
+use yahoo_finance::config::{Endpoints, RetryPolicy};
+use yahoo_finance::pricing_data::QuoteType;
+use yahoo_finance::symbols::{Kind, OptionKind};
+
+/// A streamed update, encoded the way Yahoo sends them
+struct Frame {
+   symbol: &'static str,
+   kind: u64,
+   price: f32,
+   time: i64,
+   session: u64,
+   volume: i64,
+   day_high: f32,
+   day_low: f32,
+   bid: f32,
+   ask: f32,
+   market_cap: f64,
+   open_interest: i64,
+   last_size: i64,
+   volume_24h: i64,
+   circulating_supply: f64
+}
+impl Frame {
+   fn new(symbol: &'static str, price: f32, time: i64) -> Frame {
+      Frame { symbol, kind: 0, price, time, session: 1, volume: 1000, day_high: 0.0, day_low: 0.0, bid: 0.0, ask: 0.0, market_cap: 0.0, open_interest: 0, last_size: 0, volume_24h: 0, circulating_supply: 0.0 }
+   }
+
+   fn encode(&self) -> Message {
+      let mut bytes = Vec::new();
+      key(1, 2, &mut bytes);
+      varint(self.symbol.len() as u64, &mut bytes);
+      bytes.extend_from_slice(self.symbol.as_bytes());
+      key(2, 5, &mut bytes);
+      bytes.extend_from_slice(&self.price.to_le_bytes());
+      key(3, 0, &mut bytes);
+      varint(zigzag(self.time), &mut bytes);
+      if self.kind != 0 {
+         key(6, 0, &mut bytes);
+         varint(self.kind, &mut bytes);
+      }
+      key(7, 0, &mut bytes);
+      varint(self.session, &mut bytes);
+      key(9, 0, &mut bytes);
+      varint(zigzag(self.volume), &mut bytes);
+
+      // like Yahoo, leave out whatever isn't known
+      for (field, value) in &[(10, self.day_high), (11, self.day_low), (23, self.bid), (25, self.ask)] {
+         if *value != 0.0 {
+            key(*field, 5, &mut bytes);
+            bytes.extend_from_slice(&value.to_le_bytes());
+         }
+      }
+      for (field, value) in &[(19, self.open_interest), (22, self.last_size), (28, self.volume_24h)] {
+         if *value != 0 {
+            key(*field, 0, &mut bytes);
+            varint(zigzag(*value), &mut bytes);
+         }
+      }
+      for (field, value) in &[(32, self.circulating_supply), (33, self.market_cap)] {
+         if *value != 0.0 {
+            key(*field, 1, &mut bytes);
+            bytes.extend_from_slice(&value.to_le_bytes());
+         }
+      }
+      Message::Text(base64::encode(&bytes))
+   }
+}
+
+fn key(field: u64, wire_type: u64, bytes: &mut Vec<u8>) { varint(field << 3 | wire_type, bytes) }
+
+fn zigzag(value: i64) -> u64 { ((value << 1) ^ (value >> 63)) as u64 }
+
+fn varint(mut value: u64, bytes: &mut Vec<u8>) {
+   while value >= 0x80 {
+      bytes.push((value as u8) | 0x80);
+      value >>= 7;
+   }
+   bytes.push(value as u8);
+}
+
+/// Starts a websocket server that waits for the subscription, sends the
+/// messages & hangs up - returning its URL.  The server has its own thread
+/// & runtime so it keeps going whatever the streamer does to its runtime.
+fn serve(messages: Vec<Message>) -> String {
+   serve_sessions(vec![messages.into_iter().map(Step::Send).collect()]).0
+}
+
+/// Something the server does once a connection has subscribed
+enum Step {
+   Send(Message),
+
+   /// Waits for the next text message from the streamer
+   Receive,
+
+   /// Gives the streamer time to get ready
+   Pause(Duration)
+}
+
+/// Like `serve`, but takes a connection for each set of steps before going
+/// away, also handing back the text messages it receives.
+fn serve_sessions(sessions: Vec<Vec<Step>>) -> (String, std::sync::mpsc::Receiver<String>) {
+   let (tx, rx) = std::sync::mpsc::channel();
+   let (subs_tx, subs_rx) = std::sync::mpsc::channel();
+   std::thread::spawn(move || {
+      tokio_test::block_on(async move {
+         let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+         tx.send(format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+
+         for steps in sessions {
+            let (socket, _) = listener.accept().await.unwrap();
+            let mut socket = tokio_tungstenite::accept_async(socket).await.unwrap();
+            for step in std::iter::once(Step::Receive).chain(steps) {
+               match step {
+                  Step::Send(message) => socket.send(message).await.unwrap(),
+                  Step::Receive => if let Some(Ok(Message::Text(text))) = socket.next().await { let _ = subs_tx.send(text); },
+                  Step::Pause(duration) => std::thread::sleep(duration)
+               }
+            }
+            let _ = socket.close(None).await;
+         }
+      })
+   });
+   (rx.recv().unwrap(), subs_rx)
+}
+
+/// Like `serve_sessions`, but handles the connections side by side - giving
+/// up on a connection that waits more than a second for a text message.
+fn serve_concurrently(sessions: Vec<Vec<Step>>) -> String {
+   let (tx, rx) = std::sync::mpsc::channel();
+   std::thread::spawn(move || {
+      tokio_test::block_on(async move {
+         let mut listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+         tx.send(format!("ws://{}", listener.local_addr().unwrap())).unwrap();
+
+         let mut connections = Vec::new();
+         for steps in sessions {
+            let (socket, _) = listener.accept().await.unwrap();
+            connections.push(tokio::spawn(async move {
+               let mut socket = tokio_tungstenite::accept_async(socket).await.unwrap();
+               'steps: for step in std::iter::once(Step::Receive).chain(steps) {
+                  match step {
+                     Step::Send(message) => socket.send(message).await.unwrap(),
+                     Step::Receive => loop {
+                        match tokio::time::timeout(Duration::from_secs(1), socket.next()).await {
+                           Ok(Some(Ok(Message::Text(_)))) => break,
+                           Ok(Some(Ok(_))) => continue,
+                           _ => break 'steps
+                        }
+                     },
+                     Step::Pause(duration) => tokio::time::delay_for(duration).await
+                  }
+               }
+               let _ = socket.close(None).await;
+            }));
+         }
+         futures::future::join_all(connections).await;
+      })
+   });
+   rx.recv().unwrap()
+}
+
+/// Runs a test on a multi threaded runtime, like most applications use
+fn run<F: Future>(test: F) -> F::Output {
+   tokio::runtime::Builder::new().threaded_scheduler().enable_all().build().unwrap().block_on(test)
+}
+
+/// Streams from the test server, with the calls to Yahoo going nowhere
+fn endpoints(url: String) -> Endpoints {
+   Endpoints { streaming: url, ..Endpoints::at("http://127.0.0.1:1") }
+}
+
+/// Streams from the test server, with the calls to Yahoo going to the mock server
+fn mocked_endpoints(url: String) -> Endpoints {
+   Endpoints { streaming: url, ..Endpoints::at(&mockito::server_url()) }
+}
+
+#[test]
+fn stream_skips_bad_frames() {
+   //! Ensure that a frame we can't read is reported without ending the stream
+
+   run(async {
+      // GIVEN - a good quote, garbage & another good quote
+      let url = serve(vec![
+         Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+         Message::Text("not a quote!".to_string()),
+         Frame::new("MSFT", 210.5, 1_600_000_001_000).encode()
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the quotes
+      let quotes = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).build().unwrap().stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the garbage is an error between the two quotes
+      assert_eq!(3, quotes.len());
+      assert_eq!("AAPL", quotes[0].as_ref().unwrap().symbol);
+      assert_eq!(150.0, quotes[0].as_ref().unwrap().price);
+      assert!(format!("{:?}", quotes[1].as_ref().unwrap_err()).contains("BadFrame"));
+      assert_eq!("MSFT", quotes[2].as_ref().unwrap().symbol);
+      assert_eq!(1_600_000_001_000, quotes[2].as_ref().unwrap().timestamp);
+   });
+}
+
+#[test]
+fn stream_market_data() {
+   //! Ensure that the bid / ask, day range & market cap come through when Yahoo sends them
+
+   run(async {
+      // GIVEN - a quote with all of the market data & one without
+      let full = Frame { day_high: 152.0, day_low: 148.5, bid: 149.75, ask: 150.25, market_cap: 2.5e12, ..Frame::new("AAPL", 150.0, 1_600_000_000_000) };
+      let url = serve(vec![full.encode(), Frame::new("MSFT", 210.5, 1_600_000_001_000).encode()]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the quotes
+      let quotes = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).build().unwrap().stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the data is there when it was sent
+      let full = quotes[0].as_ref().unwrap();
+      assert_eq!(Some(152.0), full.day_high);
+      assert_eq!(Some(148.5), full.day_low);
+      assert_eq!(Some(149.75), full.bid);
+      assert_eq!(Some(150.25), full.ask);
+      assert_eq!(Some(2.5e12), full.market_cap);
+
+      let empty = quotes[1].as_ref().unwrap();
+      assert_eq!((None, None, None, None, None), (empty.day_high, empty.day_low, empty.bid, empty.ask, empty.market_cap));
+   });
+}
+
+#[test]
+fn stream_quote_kinds() {
+   //! Ensure that a mixed stream can be told apart by the kind of security
+
+   run(async {
+      // GIVEN - an index, a crypto currency & a quote without a type
+      let url = serve(vec![
+         Frame { kind: 9, ..Frame::new("^DJI", 28_000.0, 1_600_000_000_000) }.encode(),
+         Frame { kind: 41, ..Frame::new("BTC-USD", 10_500.0, 1_600_000_001_000) }.encode(),
+         Frame::new("AAPL", 150.0, 1_600_000_002_000).encode()
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the quotes
+      let quotes = Streamer::builder(vec!["^DJI", "BTC-USD", "AAPL"]).endpoints(endpoints).build().unwrap().stream().await.collect::<Vec<_>>().await;
+
+      // THEN - each quote has its kind
+      let kinds = quotes.iter().map(|quote| quote.as_ref().unwrap().kind.clone()).collect::<Vec<_>>();
+      assert_eq!(vec![Some(Kind::Index), Some(Kind::Crypto), None], kinds);
+   });
+}
+
+#[test]
+fn stream_broadcast() {
+   //! Ensure that every consumer of a broadcast gets all of the quotes
+
+   run(async {
+      // GIVEN - a server that waits a moment before sending two quotes
+      let (url, _) = serve_sessions(vec![vec![
+         Step::Pause(Duration::from_millis(200)),
+         Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()),
+         Step::Send(Frame::new("MSFT", 210.5, 1_600_000_001_000).encode())
+      ]]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we broadcast the quotes to two consumers
+      let broadcast = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).build().unwrap().broadcast(16).await;
+      let (first, second) = futures::future::join(
+         broadcast.subscribe().collect::<Vec<_>>(),
+         broadcast.subscribe().collect::<Vec<_>>()
+      ).await;
+
+      // THEN - both got both quotes & nothing is left to subscribe to
+      for quotes in &[first, second] {
+         let symbols = quotes.iter().map(|quote| quote.symbol.as_str()).collect::<Vec<_>>();
+         assert_eq!(vec!["AAPL", "MSFT"], symbols);
+      }
+      assert_eq!(0, broadcast.subscribe().collect::<Vec<_>>().await.len());
+   });
+}
+
+#[test]
+fn stream_broadcast_without_capacity() {
+   //! Ensure that a broadcast asked to keep no quotes still hands them out
+
+   run(async {
+      // GIVEN - a server that waits a moment before sending a quote
+      let (url, _) = serve_sessions(vec![vec![
+         Step::Pause(Duration::from_millis(200)),
+         Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode())
+      ]]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we broadcast the quotes with a capacity of 0
+      let broadcast = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap().broadcast(0).await;
+      let quotes = broadcast.subscribe().collect::<Vec<_>>().await;
+
+      // THEN - the quote came through
+      assert_eq!(1, quotes.len());
+      assert_eq!("AAPL", quotes[0].symbol);
+   });
+}
+
+#[test]
+fn stream_conflated() {
+   //! Ensure that conflation keeps only the latest of a burst of quotes
+
+   run(async {
+      // GIVEN - a burst of AAPL quotes with an MSFT quote in between
+      let url = serve(vec![
+         Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+         Frame::new("AAPL", 151.0, 1_600_000_000_100).encode(),
+         Frame::new("MSFT", 210.5, 1_600_000_000_200).encode(),
+         Frame::new("AAPL", 152.0, 1_600_000_000_300).encode()
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the quotes conflated over a long interval
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).conflate(Duration::from_secs(10)).build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the first of each goes out straight away & the latest AAPL at the end
+      let prices = quotes.iter().map(|quote| quote.as_ref().unwrap().price).collect::<Vec<_>>();
+      assert_eq!(vec![150.0, 210.5, 152.0], prices);
+   });
+}
+
+#[test]
+fn stream_buffer_overflow() {
+   //! Ensure that a consumer that falls behind gets the quotes its overflow policy keeps
+
+   for &(overflow, ref expected) in &[
+      (Overflow::DropOldest, vec![151.0, 152.0]),
+      (Overflow::DropNewest, vec![150.0, 151.0]),
+      (Overflow::Block, vec![150.0, 151.0, 152.0])
+   ] {
+      run(async {
+         // GIVEN - three quotes for a buffer of two
+         let url = serve(vec![
+            Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+            Frame::new("AAPL", 151.0, 1_600_000_001_000).encode(),
+            Frame::new("AAPL", 152.0, 1_600_000_002_000).encode()
+         ]);
+         let endpoints = endpoints(url);
+
+         // WHEN - we only start reading once they've all been sent
+         let quotes = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).buffer(2, overflow).build().unwrap().stream().await;
+         tokio::time::delay_for(Duration::from_millis(300)).await;
+         let quotes = quotes.collect::<Vec<_>>().await;
+
+         // THEN - we get what the policy kept
+         let prices = quotes.iter().map(|quote| quote.as_ref().unwrap().price).collect::<Vec<_>>();
+         assert_eq!(expected, &prices, "{:?}", overflow);
+      });
+   }
+}
+
+#[test]
+fn stream_raw_data() {
+   //! Ensure that the raw stream has everything Yahoo sent, including what quotes leave out
+
+   run(async {
+      // GIVEN - an index quote
+      let url = serve(vec![Frame { kind: 9, day_high: 28_100.0, ..Frame::new("^DJI", 28_000.0, 1_600_000_000_000) }.encode()]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the raw data
+      let data = Streamer::builder(vec!["^DJI"]).endpoints(endpoints).build().unwrap().raw_stream().await.collect::<Vec<_>>().await;
+
+      // THEN - we have the pricing data as sent
+      assert_eq!(1, data.len());
+      let data = data[0].as_ref().unwrap();
+      assert_eq!("^DJI", data.id);
+      assert_eq!(QuoteType::Index, data.quote_type());
+      assert_eq!(28_100.0, data.day_high);
+      assert_eq!(1_600_000_000_000, data.time);
+   });
+}
+
+#[test]
+fn stream_custom_url() {
+   //! Ensure that a streamer can connect somewhere other than the configured endpoint
+
+   run(async {
+      // GIVEN - a relay while the configured endpoint has nothing listening
+      let relay = serve(vec![Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()]);
+      let endpoints = endpoints(format!("ws://{}", std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap()));
+
+      // WHEN - we stream through the relay
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).url(&relay).header("X-Relay-Key", "secret").build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the quote came through the relay
+      assert_eq!(1, quotes.len());
+      assert_eq!("AAPL", quotes[0].as_ref().unwrap().symbol);
+   });
+}
+
+/// A TLS setup that trusts the usual roots
+#[cfg(all(feature = "native-tls", not(feature = "rustls")))]
+fn tls_connector() -> yahoo_finance::TlsConnector { native_tls::TlsConnector::new().unwrap() }
+
+#[cfg(feature = "rustls")]
+fn tls_connector() -> yahoo_finance::TlsConnector { Arc::new(tokio_rustls::rustls::ClientConfig::new()) }
+
+#[test]
+#[cfg(any(feature = "native-tls", feature = "rustls"))]
+fn stream_custom_tls() {
+   //! Ensure that giving our own TLS setup still connects
+
+   run(async {
+      // GIVEN - a server with a quote
+      let url = serve(vec![Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream with our own TLS setup
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).tls(tls_connector()).build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the quote came through
+      assert_eq!(1, quotes.len());
+      assert_eq!("AAPL", quotes[0].as_ref().unwrap().symbol);
+   });
+}
+
+#[test]
+fn stream_invalid_header() {
+   //! Ensure that a header that can't be sent is caught when building the streamer
+
+   // GIVEN - a header name with a space in it
+   let builder = Streamer::builder(vec!["AAPL"]).header("Bad Header", "value");
+
+   // WHEN - we build the streamer
+   let result = builder.build();
+
+   // THEN - we're told about the header
+   assert!(format!("{:?}", result.err().unwrap()).contains("InvalidHeader"));
+}
+
+#[test]
+fn stream_watchdog() {
+   //! Ensure that quiet symbols are reported & a quiet connection is dropped
+
+   run(async {
+      // GIVEN - a server that keeps AAPL going for a while, never sends MSFT & then goes quiet
+      let (url, _) = serve_sessions(vec![vec![
+         Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()),
+         Step::Pause(Duration::from_millis(150)),
+         Step::Send(Frame::new("AAPL", 151.0, 1_600_000_001_000).encode()),
+         Step::Pause(Duration::from_millis(150)),
+         Step::Send(Frame::new("AAPL", 152.0, 1_600_000_002_000).encode()),
+         Step::Pause(Duration::from_secs(2))
+      ]]);
+      let endpoints = endpoints(url);
+      let events = Arc::new(Mutex::new(Vec::new()));
+      let seen = events.clone();
+
+      // WHEN - we stream with a watchdog
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints)
+         .watchdog(Duration::from_millis(250))
+         .on_event(move |event| seen.lock().unwrap().push(event.clone()))
+         .build().unwrap();
+      let started = std::time::Instant::now();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - we got AAPL & gave up on the connection before the server hung up
+      assert_eq!(3, quotes.len());
+      assert!(started.elapsed() < Duration::from_secs(1));
+
+      // AND - MSFT was reported before the connection
+      assert_eq!(*events.lock().unwrap(), vec![
+         StreamEvent::Stale { symbol: Some("MSFT".to_string()) },
+         StreamEvent::Stale { symbol: None },
+         StreamEvent::Disconnected
+      ]);
+   });
+}
+
+#[test]
+fn stream_disconnected_without_quotes() {
+   //! Ensure that losing a connection is reported even when no quotes came through it
+
+   run(async {
+      // GIVEN - a server that hangs up as soon as we subscribe
+      let (url, _subscriptions) = serve_sessions(vec![vec![]]);
+      let endpoints = endpoints(url);
+      let policy = RetryPolicy { max_attempts: 1, initial_backoff: Duration::from_millis(10), max_backoff: Duration::from_millis(10), jitter: false };
+      let events = Arc::new(Mutex::new(Vec::new()));
+      let seen = events.clone();
+
+      // WHEN - we stream without any quotes coming through
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints)
+         .reconnect(policy)
+         .on_event(move |event| seen.lock().unwrap().push(event.clone()))
+         .build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - we were told the connection went away
+      assert!(quotes.is_empty());
+      assert_eq!(*events.lock().unwrap(), vec![StreamEvent::Disconnected]);
+   });
+}
+
+#[test]
+fn stream_stats() {
+   //! Ensure that the streamer keeps count of how the feed has been doing
+
+   run(async {
+      // GIVEN - a connection with a quote & garbage, then one with another quote
+      let (url, _) = serve_sessions(vec![
+         vec![Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()), Step::Send(Message::Text("not a quote!".to_string()))],
+         vec![Step::Send(Frame::new("MSFT", 210.5, 1_600_000_001_000).encode())]
+      ]);
+      let endpoints = endpoints(url);
+      let policy = RetryPolicy { max_attempts: 2, initial_backoff: Duration::from_millis(10), max_backoff: Duration::from_millis(10), jitter: false };
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).reconnect(policy).build().unwrap();
+
+      // WHEN - we've streamed everything
+      streamer.stream().await.collect::<Vec<_>>().await;
+      let stats = streamer.stats();
+
+      // THEN - the counts add up
+      assert_eq!(3, stats.messages);
+      assert_eq!(1, stats.parse_errors);
+      assert_eq!(1, stats.reconnects);
+      assert_eq!(2, stats.subscriptions);
+      assert!(stats.last_message.is_some());
+   });
+}
+
+#[test]
+fn stream_connection_refused() {
+   //! Ensure that failing to connect is an error rather than a panic
+
+   run(async {
+      // GIVEN - nothing listening for websockets
+      let url = format!("ws://{}", std::net::TcpListener::bind("127.0.0.1:0").unwrap().local_addr().unwrap());
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the quotes
+      let quotes = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap().stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the only item is the failure
+      assert_eq!(1, quotes.len());
+      assert!(format!("{:?}", quotes[0].as_ref().unwrap_err()).contains("StreamFailed"));
+   });
+}
+
+#[test]
+fn stream_reconnects() {
+   //! Ensure that a dropped connection is picked back up with the same subscription
+
+   run(async {
+      // GIVEN - a server that hangs up after each quote & then goes away
+      let (url, subscriptions) = serve_sessions(vec![
+         vec![Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode())],
+         vec![Step::Send(Frame::new("MSFT", 210.5, 1_600_000_001_000).encode())]
+      ]);
+      let endpoints = endpoints(url);
+      let policy = RetryPolicy { max_attempts: 2, initial_backoff: Duration::from_millis(10), max_backoff: Duration::from_millis(10), jitter: false };
+      let events = Arc::new(Mutex::new(Vec::new()));
+      let seen = events.clone();
+
+      // WHEN - we stream the quotes, reconnecting
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints)
+         .reconnect(policy)
+         .on_event(move |event| seen.lock().unwrap().push(event.clone()))
+         .build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - both quotes come through, followed by the final failure to connect
+      assert_eq!(3, quotes.len());
+      assert_eq!("AAPL", quotes[0].as_ref().unwrap().symbol);
+      assert_eq!("MSFT", quotes[1].as_ref().unwrap().symbol);
+      assert!(format!("{:?}", quotes[2].as_ref().unwrap_err()).contains("StreamFailed"));
+
+      // AND - both connections subscribed to both symbols
+      let subscriptions = subscriptions.try_iter().collect::<Vec<_>>();
+      assert_eq!(2, subscriptions.len());
+      assert!(subscriptions.iter().all(|subs| subs.contains("AAPL") && subs.contains("MSFT")));
+
+      // AND - we were told about the reconnects
+      let delay = Duration::from_millis(10);
+      assert_eq!(*events.lock().unwrap(), vec![
+         StreamEvent::Disconnected,
+         StreamEvent::Reconnecting { attempt: 1, delay },
+         StreamEvent::Reconnected,
+         StreamEvent::Disconnected,
+         StreamEvent::Reconnecting { attempt: 1, delay }
+      ]);
+   });
+}
+
+#[test]
+fn stream_changes_subscriptions() {
+   //! Ensure that symbols can be added & removed without reconnecting
+
+   run(async {
+      // GIVEN - a server that waits for changes to the subscription after the first quote
+      let (url, subscriptions) = serve_sessions(vec![vec![
+         Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()),
+         Step::Receive,
+         Step::Receive,
+         Step::Send(Frame::new("MSFT", 210.5, 1_600_000_001_000).encode())
+      ]]);
+      let endpoints = endpoints(url);
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap();
+      let mut quotes = streamer.stream().await;
+      let first = quotes.next().await.unwrap().unwrap();
+
+      // WHEN - we swap AAPL for MSFT
+      streamer.subscribe(vec!["MSFT"]);
+      streamer.unsubscribe(vec!["AAPL"]);
+      let rest = quotes.collect::<Vec<_>>().await;
+
+      // THEN - the quotes come through on the one connection
+      assert_eq!("AAPL", first.symbol);
+      assert_eq!(1, rest.len());
+      assert_eq!("MSFT", rest[0].as_ref().unwrap().symbol);
+
+      // AND - yahoo was told about the changes
+      assert_eq!(subscriptions.try_iter().collect::<Vec<_>>(), vec![
+         r#"{"subscribe":["AAPL"]}"#,
+         r#"{"subscribe":["MSFT"]}"#,
+         r#"{"unsubscribe":["AAPL"]}"#
+      ]);
+   });
+}
+
+#[test]
+fn stream_changes_subscriptions_concurrently() {
+   //! Ensure that subscription changes reach every open stream, even once another one has ended
+
+   run(async {
+      // GIVEN - a server where the first connection hangs up shortly after a quote & the second waits for a new symbol
+      let url = serve_concurrently(vec![
+         vec![Step::Send(Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()), Step::Pause(Duration::from_millis(300))],
+         vec![
+            Step::Send(Frame::new("AAPL", 150.5, 1_600_000_001_000).encode()),
+            Step::Receive,
+            Step::Send(Frame::new("MSFT", 210.5, 1_600_000_002_000).encode())
+         ]
+      ]);
+      let endpoints = endpoints(url);
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap();
+      let mut first = streamer.stream().await;
+      assert_eq!(150.0, first.next().await.unwrap().unwrap().price);
+      let mut second = streamer.stream().await;
+      assert_eq!(150.5, second.next().await.unwrap().unwrap().price);
+      assert!(first.collect::<Vec<_>>().await.is_empty());
+
+      // WHEN - we add MSFT after the first stream has ended
+      streamer.subscribe(vec!["MSFT"]);
+      let rest = second.collect::<Vec<_>>().await;
+
+      // THEN - the second stream still picks up the new symbol
+      assert_eq!(1, rest.len());
+      assert_eq!("MSFT", rest[0].as_ref().unwrap().symbol);
+   });
+}
+
+#[test]
+fn stream_candles() {
+   //! Ensure that quotes are built into candles, split by interval & by trading session
+
+   run(async {
+      // GIVEN - a minute of pre market & regular quotes, then one in the next minute
+      let start = 1_599_999_960_000;
+      let quote = |price, offset, session, volume| Frame { session, volume, ..Frame::new("AAPL", price, start + offset) }.encode();
+      let url = serve(vec![
+         quote(100.0, 1_000, 0, 1000),
+         quote(101.0, 2_000, 1, 1100),
+         quote(103.0, 30_000, 1, 1300),
+         quote(99.0, 50_000, 1, 1400),
+         quote(102.0, 61_000, 1, 1600)
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we build 1 minute candles
+      let aggregator = CandleAggregator::new(Interval::_1m).unwrap();
+      let candles = aggregator.aggregate(Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap().stream().await).collect::<Vec<_>>().await;
+
+      // THEN - the session change & the new minute each start a candle
+      assert_eq!(3, candles.len());
+      assert!(matches!(candles[0].session, TradingSession::PreMarket));
+      assert_eq!(Bar { timestamp: start, open: 100.0, high: 100.0, low: 100.0, close: 100.0, volume: Some(0) }, candles[0].bar);
+
+      assert!(matches!(candles[1].session, TradingSession::Regular));
+      assert_eq!(Bar { timestamp: start, open: 101.0, high: 103.0, low: 99.0, close: 99.0, volume: Some(400) }, candles[1].bar);
+
+      // AND - the last candle is finished when the stream ends
+      assert_eq!(Bar { timestamp: start + 60_000, open: 102.0, high: 102.0, low: 102.0, close: 102.0, volume: Some(200) }, candles[2].bar);
+   });
+
+   // AND - only intraday intervals make candles
+   assert!(CandleAggregator::new(Interval::_1d).is_err());
+}
+
+#[test]
+fn stream_snapshot() {
+   //! Ensure that a snapshot of each symbol comes through ahead of the live quotes
+
+   run(async {
+      // GIVEN - a chart for AAPL & a live quote for it
+      let chart = mock("GET", "/v8/finance/chart/AAPL")
+         .match_query(Matcher::Any)
+         .with_header("content-type", "application/json")
+         .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+         .create();
+      let url = serve(vec![Frame::new("AAPL", 290.0, 1_588_363_260_000).encode()]);
+      let endpoints = mocked_endpoints(url);
+
+      // WHEN - we stream with a snapshot
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).snapshot().build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the latest quote from the chart comes first
+      assert_eq!(2, quotes.len());
+      let snapshot = quotes[0].as_ref().unwrap();
+      assert_eq!(("AAPL", 1_588_363_201_000, 289.07), (snapshot.symbol.as_str(), snapshot.timestamp, snapshot.price as f32));
+      assert!(matches!(snapshot.session, TradingSession::Regular));
+      assert_eq!(Some(Kind::Equity), snapshot.kind);
+      assert!((snapshot.change.unwrap() - 6.1).abs() < 0.001);
+
+      // AND - the live quote follows
+      assert_eq!(290.0, quotes[1].as_ref().unwrap().price);
+      chart.assert();
+   });
+}
+
+#[test]
+fn stream_after_hours_start() {
+   //! Ensure that a stream started after the close works out the after hours change from the regular close
+
+   run(async {
+      // GIVEN - a chart that closed at 289.07 over a previous close of 282.97 & an after hours quote
+      let chart = mock("GET", "/v8/finance/chart/AAPL")
+         .match_query(Matcher::Any)
+         .with_header("content-type", "application/json")
+         .with_body(std::fs::read_to_string("tests/history_data/aapl.json").unwrap())
+         .create();
+      let url = serve(vec![Frame { session: 2, ..Frame::new("AAPL", 290.0, 1_588_365_000_000) }.encode()]);
+      let endpoints = mocked_endpoints(url);
+
+      // WHEN - we start streaming in the after hours session
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the change is from the regular close rather than the previous close
+      assert_eq!(1, quotes.len());
+      let quote = quotes[0].as_ref().unwrap();
+      assert!(matches!(quote.session, TradingSession::AfterHours));
+      assert!((quote.change.unwrap() - 0.93).abs() < 0.001);
+      chart.assert();
+   });
+}
+
+#[test]
+fn stream_sessions() {
+   //! Ensure that only quotes from the chosen trading sessions come through
+
+   run(async {
+      // GIVEN - a pre market, a regular & an after hours quote
+      let url = serve(vec![
+         Frame { session: 0, ..Frame::new("AAPL", 149.0, 1_600_000_000_000) }.encode(),
+         Frame { session: 1, ..Frame::new("AAPL", 150.0, 1_600_000_001_000) }.encode(),
+         Frame { session: 2, ..Frame::new("AAPL", 151.0, 1_600_000_002_000) }.encode()
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we only stream the regular session
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).sessions(vec![TradingSession::Regular]).build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the pre market & after hours quotes are left out
+      assert_eq!(1, quotes.len());
+      assert_eq!(150.0, quotes[0].as_ref().unwrap().price);
+   });
+}
+
+#[test]
+fn stream_sessions_conflated() {
+   //! Ensure that quotes outside the chosen sessions don't take the place of ones inside them when conflating
+
+   run(async {
+      // GIVEN - a burst of regular session quotes between pre market & after hours ones
+      let url = serve(vec![
+         Frame { session: 0, ..Frame::new("AAPL", 149.0, 1_600_000_000_000) }.encode(),
+         Frame::new("AAPL", 150.0, 1_600_000_000_100).encode(),
+         Frame::new("AAPL", 151.0, 1_600_000_000_200).encode(),
+         Frame { session: 2, ..Frame::new("AAPL", 152.0, 1_600_000_000_300) }.encode()
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the regular session conflated over a long interval
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints)
+         .sessions(vec![TradingSession::Regular])
+         .conflate(Duration::from_secs(10))
+         .build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the first regular quote goes out straight away & the latest regular one at the end
+      let prices = quotes.iter().map(|quote| quote.as_ref().unwrap().price).collect::<Vec<_>>();
+      assert_eq!(vec![150.0, 151.0], prices);
+   });
+}
+
+#[test]
+fn stream_record_replay() {
+   //! Ensure that a recorded stream plays back the same quotes, keeping to the recorded pace
+
+   let path = std::env::temp_dir().join(format!("yahoo-finance-{}.rec", std::process::id()));
+   run(async {
+      // GIVEN - a recording of a couple of quotes & some garbage
+      let url = serve(vec![
+         Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+         Message::Text("not a quote!".to_string()),
+         Frame::new("MSFT", 210.5, 1_600_000_001_000).encode()
+      ]);
+      let endpoints = endpoints(url);
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).record(&path).build().unwrap();
+      let live = streamer.stream().await.collect::<Vec<_>>().await;
+      assert_eq!(3, live.len());
+
+      // WHEN - we play it back as fast as possible
+      let replayed = Replay::open(&path).unwrap().speed(f64::INFINITY).stream().collect::<Vec<_>>().await;
+
+      // THEN - the same quotes & errors come through
+      assert_eq!(3, replayed.len());
+      assert_eq!(("AAPL", 150.0), (replayed[0].as_ref().unwrap().symbol.as_str(), replayed[0].as_ref().unwrap().price));
+      assert!(format!("{:?}", replayed[1].as_ref().unwrap_err()).contains("BadFrame"));
+      assert_eq!(("MSFT", 210.5), (replayed[2].as_ref().unwrap().symbol.as_str(), replayed[2].as_ref().unwrap().price));
+   });
+
+   // AND - a played back recording keeps to the gaps between the frames
+   let frame = "CgRBQVBMFQAAFkM=";
+   std::fs::write(&path, format!("1000\t{}\n1200\t{}\n", frame, frame)).unwrap();
+   let started = std::time::Instant::now();
+   let replayed = run(Replay::open(&path).unwrap().raw_stream().collect::<Vec<_>>());
+   assert_eq!(2, replayed.len());
+   assert!(started.elapsed() >= Duration::from_millis(200));
+
+   // AND - a recording played far too slowly waits rather than failing
+   let waited = run(async {
+      let mut replayed = Replay::open(&path).unwrap().speed(f64::MIN_POSITIVE).raw_stream();
+      replayed.next().await;
+      tokio::time::timeout(Duration::from_millis(50), replayed.next()).await
+   });
+   assert!(waited.is_err());
+
+   // AND - a missing recording can't be opened
+   std::fs::remove_file(&path).unwrap();
+   assert!(Replay::open(&path).is_err());
+}
+
+/// A regular session quote, as if streamed
+fn quote(symbol: &str, price: f64, timestamp: i64) -> Quote {
+   Quote {
+      symbol: symbol.to_string(),
+      kind: None,
+      timestamp,
+      session: TradingSession::Regular,
+      price,
+      volume: 1000,
+      change: None,
+      change_percent: None,
+      bid: None,
+      ask: None,
+      day_high: None,
+      day_low: None,
+      market_cap: None,
+      last_size: None,
+      volume_24h: None,
+      circulating_supply: None
+   }
+}
+
+#[test]
+fn candles_aligned_per_symbol() {
+   //! Ensure that lining up one symbol's intervals leaves the others on the clock
+
+   // GIVEN - hourly candles with AAPL's session starting at half past
+   let hour = 3_600_000;
+   let start = 1_600_000_000_000 - 1_600_000_000_000 % hour;
+   let mut aggregator = CandleAggregator::new(Interval::_60m).unwrap();
+   aggregator.align("AAPL", start + hour / 2);
+
+   // WHEN - quotes for AAPL & MSFT come in over the hour & into the next
+   assert!(aggregator.push(&quote("AAPL", 100.0, start + hour / 2 + 1_000)).is_none());
+   assert!(aggregator.push(&quote("MSFT", 200.0, start + hour / 2 + 1_000)).is_none());
+   let aapl = aggregator.push(&quote("AAPL", 101.0, start + hour + hour / 2 + 1_000)).unwrap();
+   let msft = aggregator.push(&quote("MSFT", 201.0, start + hour + 1_000)).unwrap();
+
+   // THEN - AAPL's candle starts at half past while MSFT's starts on the hour
+   assert_eq!(start + hour / 2, aapl.bar.timestamp);
+   assert_eq!(start, msft.bar.timestamp);
+}
+
+#[test]
+fn candles_finished_on_time() {
+   //! Ensure that a candle is finished once its interval ends, without waiting on the next quote
+
+   run(async {
+      // GIVEN - a quote just before the end of a minute & then nothing more
+      let start = 1_599_999_960_000;
+      let quotes = futures::stream::iter(vec![Ok(quote("AAPL", 100.0, start + 59_500))]).chain(futures::stream::pending());
+
+      // WHEN - we build 1 minute candles
+      let aggregator = CandleAggregator::new(Interval::_1m).unwrap();
+      let mut candles = Box::pin(aggregator.aggregate(quotes));
+      let candle = tokio::time::timeout(Duration::from_secs(5), candles.next()).await.unwrap().unwrap();
+
+      // THEN - the candle is finished by the clock
+      assert_eq!(Bar { timestamp: start, open: 100.0, high: 100.0, low: 100.0, close: 100.0, volume: None }, candle.bar);
+   });
+}
+
+#[test]
+fn stream_candles_backfilled() {
+   //! Ensure that the history & the live candles join up without any gap or overlap
+
+
+   // keep clear of the end of the interval so the latest bar is still trading throughout
+   let width = 300_000;
+   let mut now = chrono::Utc::now().timestamp_millis();
+   if now % width > width - 10_000 {
+      std::thread::sleep(Duration::from_millis((width - now % width) as u64 + 100));
+      now = chrono::Utc::now().timestamp_millis();
+   }
+   let current = now - now % width;
+
+   run(async {
+      // GIVEN - the history up to the bar that's still trading, loaded for the previous close too
+      let times = [current - 2 * width, current - width, current].iter().map(|time| (time / 1000).to_string()).collect::<Vec<_>>();
+      let chart = mock("GET", "/v8/finance/chart/AAPL")
+         .match_query(Matcher::Any)
+         .with_header("content-type", "application/json")
+         .with_body(format!(r#"{{"chart":{{"result":[{{
+            "meta":{{"symbol":"AAPL","firstTradeDate":345479400,"regularMarketPrice":100.5,"chartPreviousClose":98.0}},
+            "timestamp":[{}],
+            "indicators":{{"quote":[{{"open":[97.0,98.0,100.0],"high":[98.0,99.0,101.0],"low":[96.0,97.0,99.0],"close":[98.0,99.5,100.5],"volume":[100,200,300]}}]}}
+         }}],"error":null}}}}"#, times.join(",")))
+         .expect(2)
+         .create();
+
+      // AND - live quotes for the current bar, a late one for the bar before & one for the next bar
+      let quote = |price, time, volume| Frame { volume, ..Frame::new("AAPL", price, time) }.encode();
+      let url = serve(vec![
+         quote(102.0, current + 5_000, 10_000),
+         quote(50.0, current - width + 1_000, 10_100),
+         quote(101.0, current + 10_000, 10_200),
+         quote(103.0, current + width + 1_000, 10_500)
+      ]);
+      let endpoints = mocked_endpoints(url);
+
+      // WHEN - we stream 5 minute candles
+      let candles = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap().candles(Interval::_5m).await.unwrap().collect::<Vec<_>>().await;
+
+      // THEN - the finished history comes first
+      let bars = candles.iter().map(|candle| candle.bar).collect::<Vec<_>>();
+      assert_eq!(4, bars.len());
+      assert_eq!(Bar { timestamp: current - 2 * width, open: 97.0, high: 98.0, low: 96.0, close: 98.0, volume: Some(100) }, bars[0]);
+      assert_eq!(Bar { timestamp: current - width, open: 98.0, high: 99.0, low: 97.0, close: 99.5, volume: Some(200) }, bars[1]);
+
+      // AND - the live quotes carry on from the bar that was still trading, leaving out the late quote
+      assert_eq!(Bar { timestamp: current, open: 100.0, high: 102.0, low: 99.0, close: 101.0, volume: Some(500) }, bars[2]);
+      assert_eq!(Bar { timestamp: current + width, open: 103.0, high: 103.0, low: 103.0, close: 103.0, volume: Some(300) }, bars[3]);
+      chart.assert();
+   });
+}
+
+#[test]
+fn stream_polling() {
+   //! Ensure that polling the quote endpoint streams the same quotes, once per change
+
+   run(async {
+      // GIVEN - a session & the quotes for a regular & an after hours symbol
+      let endpoints = mocked_endpoints("ws://127.0.0.1:1".to_string());
+      let _cookie = mock("GET", "/").with_header("set-cookie", "A3=session; Domain=.yahoo.com; Path=/").with_status(404).create();
+      let _crumb = mock("GET", "/v1/test/getcrumb").with_body("crumb").create();
+      let quotes = mock("GET", "/v7/finance/quote?symbols=AAPL%2CMSFT&crumb=crumb")
+         .match_header("cookie", "A3=session")
+         .with_header("content-type", "application/json")
+         .with_body(r#"{"quoteResponse":{"result":[
+            {"symbol":"AAPL","quoteType":"EQUITY","marketState":"REGULAR","regularMarketPrice":150.0,"regularMarketTime":1600000000,"regularMarketPreviousClose":148.0,"regularMarketVolume":1000},
+            {"symbol":"MSFT","quoteType":"EQUITY","marketState":"POST","regularMarketPrice":210.0,"regularMarketTime":1600000000,"postMarketPrice":211.5,"postMarketTime":1600000100}
+         ],"error":null}}"#)
+         .expect_at_least(2)
+         .create();
+
+      // WHEN - we poll a few times
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).poll(Duration::from_millis(50)).build().unwrap();
+      let mut stream = streamer.stream().await;
+      let first = [stream.next().await.unwrap().unwrap(), stream.next().await.unwrap().unwrap()];
+      let unchanged = tokio::time::timeout(Duration::from_millis(200), stream.next()).await;
+
+      // THEN - the quotes come through like streamed ones
+      assert_eq!(("AAPL", 150.0, 1_600_000_000_000, 1000), (first[0].symbol.as_str(), first[0].price, first[0].timestamp, first[0].volume));
+      assert!(matches!(first[0].session, TradingSession::Regular));
+      assert_eq!(Some(Kind::Equity), first[0].kind);
+      assert_eq!(Some(2.0), first[0].change);
+
+      assert_eq!(("MSFT", 211.5, 1_600_000_100_000), (first[1].symbol.as_str(), first[1].price, first[1].timestamp));
+      assert!(matches!(first[1].session, TradingSession::AfterHours));
+
+      // AND - quotes that haven't changed since the last poll are left out
+      assert!(unchanged.is_err());
+      quotes.assert();
+   });
+}
+
+#[test]
+fn stream_runtime_handle() {
+   //! Ensure that a streamer given a runtime can be read from outside of it
+
+
+   // GIVEN - a runtime for the streamer & a couple of quotes
+   let runtime = tokio::runtime::Builder::new().threaded_scheduler().core_threads(2).enable_all().build().unwrap();
+   let url = serve(vec![
+      Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+      Frame::new("MSFT", 210.5, 1_600_000_001_000).encode()
+   ]);
+   let endpoints = endpoints(url);
+
+   // WHEN - we read the quotes with an executor that isn't tokio
+   let streamer = Streamer::builder(vec!["AAPL", "MSFT"]).endpoints(endpoints).runtime(runtime.handle().clone()).conflate(Duration::from_millis(10)).build().unwrap();
+   let quotes = futures::executor::block_on(async { streamer.stream().await.collect::<Vec<_>>().await });
+
+   // THEN - everything came through
+   assert_eq!(vec!["AAPL", "MSFT"], quotes.iter().map(|quote| quote.as_ref().unwrap().symbol.as_str()).collect::<Vec<_>>());
+}
+
+#[test]
+fn stream_stops() {
+   //! Ensure that stopping the streamer ends a quiet stream straight away, even on a single thread
+
+   let url = serve_sessions(vec![vec![Step::Pause(Duration::from_secs(5))]]).0;
+   let endpoints = endpoints(url);
+
+   tokio::runtime::Builder::new().basic_scheduler().enable_all().build().unwrap().block_on(async {
+      // GIVEN - a connection that doesn't send anything
+      let mut streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints).build().unwrap();
+      let mut quotes = streamer.stream().await;
+      tokio::time::delay_for(Duration::from_millis(100)).await;
+
+      // WHEN - we stop the streamer
+      streamer.stop();
+
+      // THEN - the stream ends without waiting on the connection
+      let next = tokio::time::timeout(Duration::from_secs(1), quotes.next()).await;
+      assert!(matches!(next, Ok(None)));
+   });
+}
+
+#[test]
+fn stream_symbol_lists() {
+   //! Ensure that the symbols can come from owned strings, arrays or slices
+
+   // GIVEN - symbols loaded from somewhere else
+   let owned = vec!["AAPL".to_string(), "MSFT".to_string()];
+   let borrowed: &[&str] = &["AAPL", "MSFT", "TSLA"];
+
+   // WHEN - we create streamers from each of them
+   let streamers = [Streamer::new(owned), Streamer::new(["^DJI"]), Streamer::from(borrowed)];
+
+   // THEN - they're all subscribed
+   assert_eq!(vec![2, 1, 3], streamers.iter().map(|streamer| streamer.stats().subscriptions).collect::<Vec<_>>());
+}
+
+#[test]
+fn stream_latest() {
+   //! Ensure that the latest quote for each symbol can be read on demand
+
+   run(async {
+      // GIVEN - a couple of quotes for one symbol & one for another
+      let url = serve(vec![
+         Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+         Frame::new("MSFT", 210.5, 1_600_000_001_000).encode(),
+         Frame::new("AAPL", 151.0, 1_600_000_002_000).encode()
+      ]);
+      let endpoints = endpoints(url);
+      let streamer = Streamer::builder(vec!["AAPL", "MSFT", "TSLA"]).endpoints(endpoints).build().unwrap();
+
+      // WHEN - we've streamed everything & then drop a symbol
+      streamer.stream().await.collect::<Vec<_>>().await;
+      let latest = streamer.latest("AAPL").map(|quote| quote.price);
+      streamer.unsubscribe(vec!["MSFT"]);
+
+      // THEN - we get the newest quote, if there is one
+      assert_eq!(Some(151.0), latest);
+      assert!(streamer.latest("MSFT").is_none());
+      assert!(streamer.latest("TSLA").is_none());
+   });
+}
+
+#[test]
+fn stream_options() {
+   //! Ensure that option contracts are streamed with their details & everything else is left out
+
+   run(async {
+      // GIVEN - a call with its open interest, a stock & a put
+      let call = Frame { kind: 13, open_interest: 1200, ..Frame::new("AAPL240119C00190000", 4.25, 1_600_000_000_000) };
+      let url = serve(vec![
+         call.encode(),
+         Frame::new("AAPL", 150.0, 1_600_000_001_000).encode(),
+         Frame { kind: 13, ..Frame::new("AAPL240119P00170000", 1.5, 1_600_000_002_000) }.encode()
+      ]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the options
+      let streamer = Streamer::builder(vec!["AAPL240119C00190000", "AAPL", "AAPL240119P00170000"]).endpoints(endpoints).build().unwrap();
+      let options = streamer.option_stream().await.collect::<Vec<_>>().await;
+
+      // THEN - only the contracts came through
+      let options = options.into_iter().map(Result::unwrap).collect::<Vec<_>>();
+      assert_eq!(vec![(OptionKind::Call, 190.0, Some(1200), 4.25), (OptionKind::Put, 170.0, None, 1.5)],
+         options.iter().map(|option| (option.contract.kind, option.contract.strike, option.open_interest, option.quote.price)).collect::<Vec<_>>());
+      assert_eq!("AAPL", options[0].contract.underlying);
+   });
+}
+
+#[test]
+fn stream_crypto_data() {
+   //! Ensure that the 24 hour volume, circulating supply & last trade size come through for crypto
+
+   run(async {
+      // GIVEN - a crypto quote with its extra data & a stock quote without
+      let crypto = Frame { kind: 41, last_size: 2, volume_24h: 35_000_000_000, circulating_supply: 19_500_000.0, ..Frame::new("BTC-USD", 60000.0, 1_600_000_000_000) };
+      let url = serve(vec![crypto.encode(), Frame::new("AAPL", 150.0, 1_600_000_001_000).encode()]);
+      let endpoints = endpoints(url);
+
+      // WHEN - we stream the quotes
+      let quotes = Streamer::builder(vec!["BTC-USD", "AAPL"]).endpoints(endpoints).build().unwrap().stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the data is there when it was sent
+      let crypto = quotes[0].as_ref().unwrap();
+      assert_eq!((Some(2), Some(35_000_000_000), Some(19_500_000.0)), (crypto.last_size, crypto.volume_24h, crypto.circulating_supply));
+
+      let stock = quotes[1].as_ref().unwrap();
+      assert_eq!((None, None, None), (stock.last_size, stock.volume_24h, stock.circulating_supply));
+   });
+}
+
+#[test]
+fn pricing_data_decoding() {
+   //! Ensure that the pricing data is read field by field, skipping unknown fields & catching cut short messages
+
+   // GIVEN - a quote with a field Yahoo might add later & a negative change
+   let mut bytes = Vec::new();
+   key(1, 2, &mut bytes);
+   varint(4, &mut bytes);
+   bytes.extend_from_slice(b"AAPL");
+   key(99, 0, &mut bytes);
+   varint(12345, &mut bytes);
+   key(12, 5, &mut bytes);
+   bytes.extend_from_slice(&(-1.5f32).to_le_bytes());
+   key(3, 0, &mut bytes);
+   varint(zigzag(-42), &mut bytes);
+
+   // WHEN - we read it & the same quote cut short
+   let data = PricingData::parse(&bytes).unwrap();
+   let cut = PricingData::parse(&bytes[..bytes.len() - 1]);
+
+   // THEN - the known fields are there & the cut short one fails
+   assert_eq!(("AAPL", -1.5, -42), (data.id.as_str(), data.change, data.time));
+   assert!(format!("{:?}", cut.unwrap_err()).contains("BadFrame"));
+}
+
+#[test]
+fn stream_subscription_events() {
+   //! Ensure that acknowledgements, rejections & symbols going live are reported without being taken as bad frames
+
+   run(async {
+      // GIVEN - Yahoo acknowledging one symbol, turning down another & then streaming the first
+      let url = serve(vec![
+         Message::Text(r#"{"subscribed":["AAPL"]}"#.to_string()),
+         Message::Text(r#"{"error":{"message":"Unknown symbol"},"symbols":["FUBAR"]}"#.to_string()),
+         Frame::new("AAPL", 150.0, 1_600_000_000_000).encode(),
+         Frame::new("AAPL", 151.0, 1_600_000_001_000).encode()
+      ]);
+      let endpoints = endpoints(url);
+      let events = Arc::new(Mutex::new(Vec::new()));
+      let seen = events.clone();
+
+      // WHEN - we stream the quotes
+      let streamer = Streamer::builder(vec!["AAPL", "FUBAR"]).endpoints(endpoints)
+         .on_subscription(move |event| seen.lock().unwrap().push(event.clone()))
+         .build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - only the quotes were streamed
+      assert!(quotes.iter().all(Result::is_ok));
+      assert_eq!(2, quotes.len());
+
+      // AND - we heard about each subscription once
+      assert_eq!(*events.lock().unwrap(), vec![
+         SubscriptionEvent::Acknowledged { symbols: vec!["AAPL".to_string()] },
+         SubscriptionEvent::Rejected { symbols: vec!["FUBAR".to_string()], reason: "Unknown symbol".to_string() },
+         SubscriptionEvent::Live { symbol: "AAPL".to_string() }
+      ]);
+   });
+}
+
+#[test]
+fn stream_unknown_control_message() {
+   //! Ensure that JSON we don't recognize isn't taken as an acknowledgement
+
+   run(async {
+      // GIVEN - a message that isn't about the subscriptions & then a quote
+      let url = serve(vec![
+         Message::Text(r#"{"type":"heartbeat"}"#.to_string()),
+         Frame::new("AAPL", 150.0, 1_600_000_000_000).encode()
+      ]);
+      let endpoints = endpoints(url);
+      let events = Arc::new(Mutex::new(Vec::new()));
+      let seen = events.clone();
+
+      // WHEN - we stream the quotes
+      let streamer = Streamer::builder(vec!["AAPL"]).endpoints(endpoints)
+         .on_subscription(move |event| seen.lock().unwrap().push(event.clone()))
+         .build().unwrap();
+      let quotes = streamer.stream().await.collect::<Vec<_>>().await;
+
+      // THEN - the message is reported as a frame we couldn't read
+      assert_eq!(2, quotes.len());
+      assert!(format!("{:?}", quotes[0].as_ref().unwrap_err()).contains("BadFrame"));
+
+      // AND - the subscription only went live with the quote
+      assert_eq!(*events.lock().unwrap(), vec![SubscriptionEvent::Live { symbol: "AAPL".to_string() }]);
+   });
+}
@@ -0,0 +1,65 @@
+use chrono::NaiveDate;
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use yahoo_finance::Summary;
+use yahoo_finance::config::{self, Endpoints};
+
+mod common;
+use common::session_mocks;
+
+fn base_mock(test_name: &str, symbol: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/summary_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", format!("/v10/finance/quoteSummary/{symbol}?modules=summaryDetail%2CfinancialData&crumb=crumb", symbol=symbol).as_str())
+      .match_header("cookie", "A3=session")
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn load_summary() {
+   //! Ensure that we can load the summary for a dividend paying stock
+
+   // GIVEN - a valid response for a stock
+   let symbol = "KO";
+   let _s = session_mocks();
+   let _m = base_mock("ko", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   let result = block_on(Summary::load(symbol)).unwrap();
+
+   // THEN - we get the ranges, the book & the dividends
+   assert_eq!((Some(58.72), Some(59.28)), (result.day_low, result.day_high));
+   assert_eq!((Some(51.55), Some(64.99)), (result.fifty_two_week_low, result.fifty_two_week_high));
+   assert_eq!((Some(58.91), Some(1100), Some(58.95), Some(900)), (result.bid, result.bid_size, result.ask, result.ask_size));
+   assert_eq!((Some(14_867_380), Some(12_938_420)), (result.average_volume, result.average_volume_10_day));
+   assert_eq!((Some(1.84), Some(0.0312)), (result.dividend_rate, result.dividend_yield));
+   assert_eq!(NaiveDate::from_ymd_opt(2023, 11, 30), result.ex_dividend_date);
+   assert_eq!((Some("USD".to_string()), Some("USD".to_string())), (result.currencies.trading, result.currencies.financial));
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn load_summary_not_found() {
+   //! Ensure that we gracefully fail for unknown symbols
+
+   // GIVEN - an error response for an unknown symbol
+   let symbol = "FUBAR";
+   let _s = session_mocks();
+   let _m = base_mock("not_found", symbol).unwrap().create();
+
+   // WHEN - we load the data
+   block_on(Summary::load(symbol)).unwrap();
+
+   // THEN - we get an error
+}
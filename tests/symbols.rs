@@ -0,0 +1,156 @@
+use mockito::{mock, Mock};
+use std::fs::File;
+use std::io::prelude::*;
+use tokio_test::block_on;
+use chrono::NaiveDate;
+use yahoo_finance::symbols::{self, Kind, OptionContract, OptionKind};
+use yahoo_finance::config::{self, Endpoints};
+
+fn base_mock(test_name: &str, path: &str) -> std::io::Result<Mock> {
+   // Tell the actual code to use a test URL rather than the live one
+   config::set_endpoints(Endpoints::at(&mockito::server_url()));
+
+   // Load the simulated Yahoo data we want to test against
+   let mut file = File::open(format!("tests/symbols_data/{}.json", test_name))?;
+   let mut contents = String::new();
+   file.read_to_string(&mut contents)?;
+
+   // Serve up the test data on the test URL
+   Ok(mock("GET", path)
+      .with_header("content-type", "application/json")
+      .with_body(&contents)
+      .with_status(200))
+}
+
+#[test]
+fn kind_equity() {
+   //! Ensure that we can identify common stock
+
+   // GIVEN - a valid response for a stock symbol
+   let _m = base_mock("aapl", "/v1/finance/quoteType/AAPL").unwrap().create();
+
+   // WHEN - we look up the kind of security
+   let result = block_on(symbols::kind("AAPL")).unwrap();
+
+   // THEN - we get an equity
+   assert_eq!(Kind::Equity, result);
+}
+
+#[test]
+fn kind_crypto() {
+   //! Ensure that we can identify crypto currencies
+
+   // GIVEN - a valid response for a crypto symbol
+   let _m = base_mock("btc", "/v1/finance/quoteType/BTC-USD").unwrap().create();
+
+   // WHEN - we look up the kind of security
+   let result = block_on(symbols::kind("BTC-USD")).unwrap();
+
+   // THEN - we get a crypto currency
+   assert_eq!(Kind::Crypto, result);
+}
+
+#[test]
+#[should_panic(expected = "SymbolNotFound")]
+fn kind_not_found() {
+   //! Ensure that we gracefully fail when Yahoo doesn't know the symbol
+
+   // GIVEN - an empty response for an invalid symbol
+   let _m = base_mock("not_found", "/v1/finance/quoteType/FUBAR").unwrap().create();
+
+   // WHEN - we look up the kind of security
+   block_on(symbols::kind("FUBAR")).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn validate_many() {
+   //! Ensure that we can tell which symbols Yahoo recognizes
+
+   // GIVEN - a validation response for a mix of valid & invalid symbols
+   let _m = base_mock("validate", "/v6/finance/quote/validate?symbols=AAPL%2CQQQ%2CFUBAR").unwrap().create();
+
+   // WHEN - we validate the symbols
+   let result = block_on(symbols::validate_many(&["AAPL", "QQQ", "FUBAR"])).unwrap();
+
+   // THEN - only the real symbols are valid
+   assert_eq!(3, result.len());
+   assert_eq!(Some(&true), result.get("AAPL"));
+   assert_eq!(Some(&true), result.get("QQQ"));
+   assert_eq!(Some(&false), result.get("FUBAR"));
+}
+
+#[test]
+fn validate_many_lowercase() {
+   //! Ensure that symbols are validated whatever their case
+
+   // GIVEN - a validation response for symbols asked for in lowercase
+   let _m = base_mock("validate", "/v6/finance/quote/validate?symbols=aapl%2Cqqq").unwrap().create();
+
+   // WHEN - we validate the symbols
+   let result = block_on(symbols::validate_many(&["aapl", "qqq"])).unwrap();
+
+   // THEN - they are valid under the names we asked for
+   assert_eq!(2, result.len());
+   assert_eq!(Some(&true), result.get("aapl"));
+   assert_eq!(Some(&true), result.get("qqq"));
+}
+
+#[test]
+fn recommended() {
+   //! Ensure that we can load related symbols ordered by similarity
+
+   // GIVEN - a valid recommendations response
+   let _m = base_mock("recommended", "/v6/finance/recommendationsbysymbol/AAPL").unwrap().create();
+
+   // WHEN - we load the recommendations
+   let result = block_on(symbols::recommended("AAPL")).unwrap();
+
+   // THEN - the most similar symbol comes first
+   assert_eq!(5, result.len());
+   assert_eq!("MSFT", result[0].symbol);
+   assert!(result.windows(2).all(|w| w[0].score >= w[1].score));
+}
+
+#[test]
+#[should_panic(expected = "ApiFailed")]
+fn recommended_not_found() {
+   //! Ensure that we gracefully fail when Yahoo has no recommendations
+
+   // GIVEN - an error response for an invalid symbol
+   let _m = base_mock("recommended_not_found", "/v6/finance/recommendationsbysymbol/FUBAR").unwrap().create();
+
+   // WHEN - we load the recommendations
+   block_on(symbols::recommended("FUBAR")).unwrap();
+
+   // THEN - we get an error
+}
+
+#[test]
+fn option_contract() {
+   //! Ensure that we can read an option contract's details out of its symbol
+
+   // GIVEN - a put on an index with a fractional strike
+   let symbol = "SPX240621P04512500";
+
+   // WHEN - we parse the symbol
+   let contract = OptionContract::parse(symbol).unwrap();
+
+   // THEN - we get the contract & can write the symbol back out
+   assert_eq!("SPX", contract.underlying);
+   assert_eq!(NaiveDate::from_ymd_opt(2024, 6, 21).unwrap(), contract.expiry);
+   assert_eq!(OptionKind::Put, contract.kind);
+   assert_eq!(4512.5, contract.strike);
+   assert_eq!(symbol, contract.to_string());
+}
+
+#[test]
+fn option_contract_invalid() {
+   //! Ensure that symbols that aren't option contracts are turned down
+
+   for symbol in &["AAPL", "240119C00190000", "AAPL241319C00190000", "AAPL240119X00190000", "AAPL240119C0019000A"] {
+      let result = OptionContract::parse(symbol);
+      assert!(format!("{:?}", result.err().unwrap()).contains("InvalidOptionSymbol"), "{}", symbol);
+   }
+}